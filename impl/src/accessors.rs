@@ -0,0 +1,83 @@
+use crate::ast::{Enum, Variant};
+use crate::case::snake_case;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+impl Enum<'_> {
+    pub(crate) fn accessor_methods(&self) -> Option<TokenStream> {
+        self.attrs.accessors?;
+        let ty = &self.ident;
+        let methods = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let snake = snake_case(&ident.to_string());
+            let is = format_ident!("is_{}", snake, span = ident.span());
+            let is_doc = format!("Returns true if this is the `{}` variant.", ident);
+            let is_method = quote! {
+                #[doc = #is_doc]
+                pub fn #is(&self) -> bool {
+                    ::core::matches!(self, #ty::#ident {..})
+                }
+            };
+            if variant.fields.is_empty() {
+                return is_method;
+            }
+
+            let as_ = format_ident!("as_{}", snake, span = ident.span());
+            let into = format_ident!("into_{}", snake, span = ident.span());
+            let as_doc = format!(
+                "Borrows the contents of the `{}` variant, or returns None for any other variant.",
+                ident,
+            );
+            let into_doc = format!(
+                "Takes the contents of the `{}` variant, or hands back the error for any other variant.",
+                ident,
+            );
+            let (pattern, values) = destructure(variant);
+            let (ref_ty, owned_ty) = field_types(variant);
+            quote! {
+                #is_method
+
+                #[doc = #as_doc]
+                pub fn #as_(&self) -> ::core::option::Option<#ref_ty> {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #ty::#ident #pattern => ::core::option::Option::Some(#values),
+                        _ => ::core::option::Option::None,
+                    }
+                }
+
+                #[doc = #into_doc]
+                pub fn #into(self) -> ::core::result::Result<#owned_ty, Self> {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #ty::#ident #pattern => ::core::result::Result::Ok(#values),
+                        __other => ::core::result::Result::Err(__other),
+                    }
+                }
+            }
+        });
+        Some(quote!(#(#methods)*))
+    }
+}
+
+// A single field is returned as is, several as a tuple in declaration order.
+fn destructure(variant: &Variant) -> (TokenStream, TokenStream) {
+    let bindings: Vec<_> = (0..variant.fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let members = variant.fields.iter().map(|field| &field.member);
+    let pattern = quote!({ #(#members: #bindings),* });
+    let values = match bindings.as_slice() {
+        [binding] => quote!(#binding),
+        bindings => quote!((#(#bindings),*)),
+    };
+    (pattern, values)
+}
+
+fn field_types(variant: &Variant) -> (TokenStream, TokenStream) {
+    let types: Vec<_> = variant.fields.iter().map(|field| field.ty).collect();
+    match types.as_slice() {
+        [ty] => (quote!(&#ty), quote!(#ty)),
+        types => (quote!((#(&#types),*)), quote!((#(#types),*))),
+    }
+}
@@ -1,4 +1,5 @@
-use crate::attr::{self, Attrs};
+use crate::attr::{self, Attrs, FromWrap};
+use crate::prop::type_parameter_of;
 use syn::{
     Data, DataEnum, DataStruct, DeriveInput, Error, Fields, Generics, Ident, Index, Member, Result,
     Type,
@@ -10,6 +11,7 @@ pub enum Input<'a> {
 }
 
 pub struct Struct<'a> {
+    pub original: &'a DeriveInput,
     pub attrs: Attrs<'a>,
     pub ident: Ident,
     pub generics: &'a Generics,
@@ -17,6 +19,7 @@ pub struct Struct<'a> {
 }
 
 pub struct Enum<'a> {
+    pub original: &'a DeriveInput,
     pub attrs: Attrs<'a>,
     pub ident: Ident,
     pub generics: &'a Generics,
@@ -53,11 +56,25 @@ impl<'a> Input<'a> {
 impl<'a> Struct<'a> {
     fn from_syn(node: &'a DeriveInput, data: &'a DataStruct) -> Result<Self> {
         let mut attrs = attr::get(&node.attrs)?;
-        let fields = Field::multiple_from_syn(&data.fields)?;
+        if attrs.derive_diagnostic.is_some() {
+            attr::get_diagnostic(&mut attrs)?;
+        }
+        let mut fields = Field::multiple_from_syn(&data.fields)?;
+        if attrs.clone.is_some() {
+            wrap_arc_sources(&mut fields);
+        }
+        let fuzz_safe = attrs.fuzz_safe.is_some();
         if let Some(display) = &mut attrs.display {
-            display.expand_shorthand(&fields);
+            display.expand_shorthand(&fields, fuzz_safe)?;
+        }
+        if let Some(diagnostic) = &mut attrs.diagnostic {
+            diagnostic.expand_shorthand(&fields)?;
+        }
+        if let Some(help) = &mut attrs.help {
+            help.expand_shorthand(&fields, false)?;
         }
         Ok(Struct {
+            original: node,
             attrs,
             ident: node.ident.clone(),
             generics: &node.generics,
@@ -68,24 +85,59 @@ impl<'a> Struct<'a> {
 
 impl<'a> Enum<'a> {
     fn from_syn(node: &'a DeriveInput, data: &'a DataEnum) -> Result<Self> {
-        let attrs = attr::get(&node.attrs)?;
+        let mut attrs = attr::get(&node.attrs)?;
+        if attrs.derive_diagnostic.is_some() {
+            attr::get_diagnostic(&mut attrs)?;
+        }
         let variants = data
             .variants
             .iter()
             .map(|node| {
                 let mut variant = Variant::from_syn(node)?;
+                if attrs.derive_diagnostic.is_some() {
+                    attr::get_diagnostic(&mut variant.attrs)?;
+                }
+                if attrs.clone.is_some() {
+                    wrap_arc_sources(&mut variant.fields);
+                }
                 if let display @ None = &mut variant.attrs.display {
                     *display = attrs.display.clone();
                 }
+                let fuzz_safe = attrs.fuzz_safe.is_some() || variant.attrs.fuzz_safe.is_some();
                 if let Some(display) = &mut variant.attrs.display {
-                    display.expand_shorthand(&variant.fields);
+                    display.expand_shorthand(&variant.fields, fuzz_safe)?;
                 } else if variant.attrs.transparent.is_none() {
                     variant.attrs.transparent = attrs.transparent;
                 }
+                if let Some(container) = &attrs.diagnostic {
+                    let diagnostic = variant
+                        .attrs
+                        .diagnostic
+                        .get_or_insert_with(|| container.clone());
+                    if diagnostic.code.is_none() {
+                        diagnostic.code = container.code.clone();
+                    }
+                    if diagnostic.help.is_none() {
+                        diagnostic.help = container.help.clone();
+                    }
+                    if diagnostic.url.is_none() {
+                        diagnostic.url = container.url.clone();
+                    }
+                }
+                if let Some(diagnostic) = &mut variant.attrs.diagnostic {
+                    diagnostic.expand_shorthand(&variant.fields)?;
+                }
+                if let help @ None = &mut variant.attrs.help {
+                    *help = attrs.help.clone();
+                }
+                if let Some(help) = &mut variant.attrs.help {
+                    help.expand_shorthand(&variant.fields, false)?;
+                }
                 Ok(variant)
             })
             .collect::<Result<_>>()?;
         Ok(Enum {
+            original: node,
             attrs,
             ident: node.ident.clone(),
             generics: &node.generics,
@@ -127,3 +179,19 @@ impl<'a> Field<'a> {
         })
     }
 }
+
+// Under #[error(clone)], a `#[from] Arc<T>` source converts from a plain `T`,
+// as if written `#[from(arc)]`, so that non-Clone errors can be stored shared.
+fn wrap_arc_sources(fields: &mut [Field]) {
+    for field in fields {
+        if let Some(from) = &mut field.attrs.from {
+            if from.wrap.is_none()
+                && from.types.is_empty()
+                && from.with.is_none()
+                && type_parameter_of(field.ty, "Arc").is_some()
+            {
+                from.wrap = Some(FromWrap::Arc);
+            }
+        }
+    }
+}
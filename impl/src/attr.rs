@@ -1,35 +1,356 @@
-use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
+use std::collections::HashSet as Set;
 use std::iter::FromIterator;
+use syn::ext::IdentExt;
 use syn::parse::{Nothing, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{
-    braced, bracketed, parenthesized, token, Attribute, Error, Ident, Index, LitInt, LitStr,
-    Result, Token,
+    braced, bracketed, parenthesized, token, Attribute, Error, Expr, Ident, Index, Lit, LitBool,
+    LitInt, LitStr, Member, Path, Result, Token, Type, WherePredicate,
 };
 
 pub struct Attrs<'a> {
     pub display: Option<Display<'a>>,
     pub source: Option<&'a Attribute>,
     pub backtrace: Option<&'a Attribute>,
-    pub from: Option<&'a Attribute>,
+    pub from: Option<From<'a>>,
+    pub from_default: Option<&'a Attribute>,
+    pub from_match: Option<FromMatch<'a>>,
+    pub from_parts: Option<&'a Attribute>,
+    pub try_into: Option<&'a Attribute>,
+    pub into: Option<Into<'a>>,
+    pub code: Option<LitStr>,
+    pub diagnostic: Option<Diagnostic<'a>>,
+    pub diagnostic_attrs: Vec<&'a Attribute>,
+    pub derive_diagnostic: Option<&'a Attribute>,
+    pub from_str: Option<&'a Attribute>,
     pub transparent: Option<&'a Attribute>,
+    pub chain: Option<&'a Attribute>,
+    pub owned: Option<&'a Attribute>,
+    pub fuzz_safe: Option<&'a Attribute>,
+    pub message: Option<&'a Attribute>,
+    pub serialize: Option<&'a Attribute>,
+    pub deserialize: Option<&'a Attribute>,
+    pub status: Option<Status>,
+    pub expose: Option<LitBool>,
+    pub into_response: Option<Response<'a>>,
+    pub response_error: Option<Response<'a>>,
+    pub jsonrpc: Option<JsonRpc<'a>>,
+    pub exit_code: Option<LitInt>,
+    pub errno: Option<Errno<'a>>,
+    pub repr_code: Option<ReprCode<'a>>,
+    pub hresult: Option<HResult<'a>>,
+    pub sentry: Option<Sentry<'a>>,
+    pub meta: Option<Meta<'a>>,
+    pub py: Option<Py<'a>>,
+    pub js_value: Option<&'a Attribute>,
+    pub trace: Option<&'a Attribute>,
+    pub hook: Option<&'a Attribute>,
+    pub log: Option<&'a Attribute>,
+    pub metrics: Option<&'a Attribute>,
+    pub defmt: Option<&'a Attribute>,
+    pub from_anyhow: Option<&'a Attribute>,
+    pub from_eyre: Option<&'a Attribute>,
+    pub into_report: Option<&'a Attribute>,
+    pub json_schema: Option<&'a Attribute>,
+    pub into_responses: Option<&'a Attribute>,
+    pub accessors: Option<&'a Attribute>,
+    pub name: Option<&'a Attribute>,
+    pub kind: Option<&'a Attribute>,
+    pub code_method: Option<&'a Attribute>,
+    pub constructors: Option<&'a Attribute>,
+    pub context: Option<&'a Attribute>,
+    pub macros: Option<&'a Attribute>,
+    pub fallback: Option<&'a Attribute>,
+    pub builder: Option<&'a Attribute>,
+    pub whatever: Option<&'a Attribute>,
+    pub other: Option<&'a Attribute>,
+    pub catalog: Option<&'a Attribute>,
+    pub describe: Option<&'a Attribute>,
+    pub deref: Option<&'a Attribute>,
+    pub partial_eq: Option<&'a Attribute>,
+    pub clone: Option<&'a Attribute>,
+    pub no_alloc: Option<&'a Attribute>,
+    pub custom_trait: Option<Path>,
+    pub crate_path: Option<Path>,
+    pub bound: Option<Bound<'a>>,
+    pub deny_unused: Option<&'a Attribute>,
+    pub dispatch: Option<&'a Attribute>,
+    pub inline: Option<&'a Attribute>,
+    pub cold: Option<&'a Attribute>,
+    pub style: Option<Style<'a>>,
+    pub level: Option<Ident>,
+    pub severity: Option<Ident>,
+    pub help: Option<Display<'a>>,
+    pub retryable: Option<Retryable<'a>>,
+    pub sensitive: Option<Sensitive<'a>>,
+    pub display_with: Option<DisplayWith<'a>>,
+    pub display_with_error: Option<Error>,
+    pub error_id: Option<&'a Attribute>,
+    pub timestamp: Option<&'a Attribute>,
+}
+
+pub struct From<'a> {
+    pub original: &'a Attribute,
+    pub types: Vec<Type>,
+    pub wrap: Option<FromWrap>,
+    pub with: Option<Path>,
+}
+
+#[derive(Clone)]
+pub struct Diagnostic<'a> {
+    pub original: &'a Attribute,
+    pub code: Option<LitStr>,
+    pub help: Option<Display<'a>>,
+    pub url: Option<Display<'a>>,
+}
+
+pub struct FromMatch<'a> {
+    pub original: &'a Attribute,
+    pub ty: Type,
+    pub arm: TokenStream,
+}
+
+pub struct Into<'a> {
+    pub original: &'a Attribute,
+    pub target: Option<Type>,
+    pub mapping: Option<IntoMapping>,
+}
+
+pub enum IntoMapping {
+    Kind(Expr),
+    With(Path),
+}
+
+pub struct Py<'a> {
+    pub original: &'a Attribute,
+    pub exception: Path,
+}
+
+pub struct Errno<'a> {
+    pub original: &'a Attribute,
+    // None for `#[errno(other)]`, the variant receiving unknown values.
+    pub value: Option<Expr>,
+}
+
+pub struct Sentry<'a> {
+    pub original: &'a Attribute,
+    pub fingerprint: Vec<LitStr>,
+    pub tags: Vec<(LitStr, LitStr)>,
+}
+
+pub struct Meta<'a> {
+    pub original: &'a Attribute,
+    pub entries: Vec<(LitStr, MetaValue)>,
+}
+
+pub enum MetaValue {
+    // `key = "value"`, or any other literal.
+    Lit(Lit),
+    // `key = .field`, rendered through the field's Display impl.
+    Field(Member),
+}
+
+pub struct HResult<'a> {
+    pub original: &'a Attribute,
+    // None for `#[hresult(other)]`, the variant receiving unknown HRESULTs.
+    pub value: Option<HResultValue>,
+}
+
+pub enum HResultValue {
+    Code(LitInt),
+    Const(Path),
+}
+
+pub struct Retryable<'a> {
+    pub original: &'a Attribute,
+    // The predicate of `#[retryable(if = path)]`, called with the error.
+    pub condition: Option<Path>,
+}
+
+pub struct Sensitive<'a> {
+    pub original: &'a Attribute,
+    pub reveal: Reveal,
+}
+
+// `#[display(with = path)]`, naming a function that formats the field
+// wherever it is interpolated, or `#[display(humantime)]`.
+pub struct DisplayWith<'a> {
+    pub original: &'a Attribute,
+    pub adapter: Adapter,
+}
+
+pub enum Adapter {
+    With(Path),
+    // Durations like `2.3s` and times in RFC 3339.
+    Humantime,
+}
+
+// How much of a #[sensitive] field its placeholder still gives away.
+#[derive(Copy, Clone)]
+pub enum Reveal {
+    // `#[sensitive]`
+    Nothing,
+    // `#[sensitive(hash)]`
+    Hash,
+    // `#[sensitive(last4)]`
+    Last4,
+}
+
+pub struct ReprCode<'a> {
+    pub original: &'a Attribute,
+    // None for a bare `#[repr_code]`, which takes the next code in sequence.
+    pub value: Option<i32>,
+}
+
+pub struct JsonRpc<'a> {
+    pub original: &'a Attribute,
+    pub code: Option<LitInt>,
+    pub data: Option<Member>,
+}
+
+pub struct Response<'a> {
+    pub original: &'a Attribute,
+    pub json: Option<Ident>,
+    pub with: Option<Path>,
+}
+
+// The where-predicates of #[error(bound = "...")], which stand in for the
+// inferred bounds on the Display and Error impls.
+pub struct Bound<'a> {
+    pub original: &'a Attribute,
+    pub predicates: Punctuated<WherePredicate, Token![,]>,
+}
+
+// The message conventions that #[error(style(...))] enforces.
+pub struct Style<'a> {
+    pub original: &'a Attribute,
+    pub lowercase_start: bool,
+    pub no_trailing_period: bool,
+}
+
+pub enum Status {
+    Code(LitInt),
+    Const(Path),
+}
+
+#[derive(Copy, Clone)]
+pub enum FromWrap {
+    Arc,
+    Box,
+    Raw,
 }
 
 #[derive(Clone)]
 pub struct Display<'a> {
     pub original: &'a Attribute,
+    pub template: LitStr,
     pub fmt: LitStr,
     pub args: TokenStream,
     pub has_bonus_display: bool,
+    pub implied_bounds: Set<(Member, Trait)>,
+    // The argument of a message that is nothing but one `{field}`, which is
+    // then written by a shared runtime helper instead of a write! per variant.
+    pub only_arg: Option<TokenStream>,
 }
 
-pub fn get(input: &[Attribute]) -> Result<Attrs> {
+// The formatting trait that a `{field}` placeholder requires of the field,
+// chosen by the last character of its format spec.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Trait {
+    Debug,
+    Display,
+    Octal,
+    LowerHex,
+    UpperHex,
+    Pointer,
+    Binary,
+    LowerExp,
+    UpperExp,
+}
+
+pub fn get(input: &[Attribute]) -> Result<Attrs<'_>> {
     let mut attrs = Attrs {
         display: None,
         source: None,
         backtrace: None,
         from: None,
+        from_default: None,
+        from_match: None,
+        from_parts: None,
+        try_into: None,
+        into: None,
+        code: None,
+        diagnostic: None,
+        diagnostic_attrs: Vec::new(),
+        derive_diagnostic: None,
+        from_str: None,
         transparent: None,
+        chain: None,
+        owned: None,
+        fuzz_safe: None,
+        message: None,
+        serialize: None,
+        deserialize: None,
+        status: None,
+        expose: None,
+        into_response: None,
+        response_error: None,
+        jsonrpc: None,
+        exit_code: None,
+        errno: None,
+        repr_code: None,
+        hresult: None,
+        sentry: None,
+        meta: None,
+        py: None,
+        js_value: None,
+        trace: None,
+        hook: None,
+        log: None,
+        metrics: None,
+        defmt: None,
+        from_anyhow: None,
+        from_eyre: None,
+        into_report: None,
+        json_schema: None,
+        into_responses: None,
+        accessors: None,
+        name: None,
+        kind: None,
+        code_method: None,
+        constructors: None,
+        context: None,
+        macros: None,
+        fallback: None,
+        builder: None,
+        whatever: None,
+        other: None,
+        catalog: None,
+        describe: None,
+        deref: None,
+        partial_eq: None,
+        clone: None,
+        no_alloc: None,
+        custom_trait: None,
+        crate_path: None,
+        bound: None,
+        deny_unused: None,
+        dispatch: None,
+        inline: None,
+        cold: None,
+        style: None,
+        level: None,
+        severity: None,
+        help: None,
+        retryable: None,
+        sensitive: None,
+        display_with: None,
+        display_with_error: None,
+        error_id: None,
+        timestamp: None,
     };
 
     for attr in input {
@@ -47,41 +368,689 @@ pub fn get(input: &[Attribute]) -> Result<Attrs> {
                 return Err(Error::new_spanned(attr, "duplicate #[backtrace] attribute"));
             }
             attrs.backtrace = Some(attr);
+        } else if attr.path.is_ident("error_id") {
+            require_empty_attribute(attr)?;
+            if attrs.error_id.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[error_id] attribute"));
+            }
+            attrs.error_id = Some(attr);
+        } else if attr.path.is_ident("timestamp") {
+            require_empty_attribute(attr)?;
+            if attrs.timestamp.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[timestamp] attribute"));
+            }
+            attrs.timestamp = Some(attr);
+        } else if attr.path.is_ident("whatever") {
+            require_empty_attribute(attr)?;
+            if attrs.whatever.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[whatever] attribute"));
+            }
+            attrs.whatever = Some(attr);
         } else if attr.path.is_ident("from") {
-            if !attr.tokens.is_empty() {
-                // Assume this is meant for derive_more crate or something.
+            parse_from_attribute(&mut attrs, attr)?;
+        } else if attr.path.is_ident("code") {
+            if attrs.code.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[code] attribute"));
+            }
+            attrs.code = Some(attr.parse_args()?);
+        } else if attr.path.is_ident("status") {
+            if attrs.status.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[status] attribute"));
+            }
+            attrs.status = Some(attr.parse_args_with(|input: ParseStream| {
+                // Either `#[status(404)]` or `#[status(StatusCode::NOT_FOUND)]`.
+                if input.peek(LitInt) {
+                    let status: LitInt = input.parse()?;
+                    status.base10_parse::<u16>()?;
+                    Ok(Status::Code(status))
+                } else {
+                    input.parse().map(Status::Const)
+                }
+            })?);
+        } else if attr.path.is_ident("exit_code") {
+            if attrs.exit_code.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[exit_code] attribute"));
+            }
+            let exit_code: LitInt = attr.parse_args()?;
+            exit_code.base10_parse::<u8>()?;
+            attrs.exit_code = Some(exit_code);
+        } else if attr.path.is_ident("level") {
+            if attrs.level.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[level] attribute"));
+            }
+            let level: Ident = attr.parse_args()?;
+            if !["error", "warn", "info", "debug", "trace"]
+                .iter()
+                .any(|known| level == known)
+            {
+                return Err(Error::new_spanned(
+                    level,
+                    "expected one of `error`, `warn`, `info`, `debug`, `trace`",
+                ));
+            }
+            attrs.level = Some(level);
+        } else if attr.path.is_ident("severity") {
+            if attrs.severity.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[severity] attribute"));
+            }
+            let severity: Ident = attr.parse_args()?;
+            if !["Info", "Warning", "Error", "Fatal"]
+                .iter()
+                .any(|known| severity == known)
+            {
+                return Err(Error::new_spanned(
+                    severity,
+                    "expected one of `Info`, `Warning`, `Error`, `Fatal`",
+                ));
+            }
+            attrs.severity = Some(severity);
+        } else if attr.path.is_ident("help") {
+            // A bare #[help] marks the help field for miette's own derive.
+            if attr.tokens.is_empty() {
                 continue;
             }
-            if attrs.from.is_some() {
-                return Err(Error::new_spanned(attr, "duplicate #[from] attribute"));
+            if attrs.help.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[help] attribute"));
+            }
+            attrs.help = Some(attr.parse_args_with(|input: ParseStream| {
+                let fmt: LitStr = input.parse()?;
+                Ok(Display {
+                    original: attr,
+                    template: fmt.clone(),
+                    fmt,
+                    args: parse_token_expr(input, false)?,
+                    has_bonus_display: false,
+                    implied_bounds: Set::new(),
+                    only_arg: None,
+                })
+            })?);
+        } else if attr.path.is_ident("py") {
+            if attrs.py.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[py] attribute"));
+            }
+            attrs.py = Some(attr.parse_args_with(|input: ParseStream| {
+                syn::custom_keyword!(exception);
+                input.parse::<exception>()?;
+                input.parse::<Token![=]>()?;
+                let exception = input.parse()?;
+                input.parse::<Option<Token![,]>>()?;
+                Ok(Py {
+                    original: attr,
+                    exception,
+                })
+            })?);
+        } else if attr.path.is_ident("errno") {
+            if attrs.errno.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[errno] attribute"));
+            }
+            attrs.errno = Some(attr.parse_args_with(|input: ParseStream| {
+                syn::custom_keyword!(other);
+                let fork = input.fork();
+                let value = if fork.parse::<other>().is_ok() && fork.is_empty() {
+                    input.parse::<other>()?;
+                    None
+                } else {
+                    Some(input.parse()?)
+                };
+                Ok(Errno {
+                    original: attr,
+                    value,
+                })
+            })?);
+        } else if attr.path.is_ident("sentry") {
+            if attrs.sentry.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[sentry] attribute"));
+            }
+            attrs.sentry =
+                Some(attr.parse_args_with(|input: ParseStream| parse_sentry_args(attr, input))?);
+        } else if attr.path.is_ident("meta") {
+            if attrs.meta.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[meta] attribute"));
+            }
+            attrs.meta =
+                Some(attr.parse_args_with(|input: ParseStream| parse_meta_args(attr, input))?);
+        } else if attr.path.is_ident("hresult") {
+            if attrs.hresult.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[hresult] attribute"));
+            }
+            attrs.hresult = Some(attr.parse_args_with(|input: ParseStream| {
+                // Either `#[hresult(0x8007_0005)]`, `#[hresult(E_ACCESSDENIED)]`
+                // naming an HRESULT constant, or `#[hresult(other)]`.
+                syn::custom_keyword!(other);
+                let fork = input.fork();
+                let value = if fork.parse::<other>().is_ok() && fork.is_empty() {
+                    input.parse::<other>()?;
+                    None
+                } else if input.peek(LitInt) {
+                    let code: LitInt = input.parse()?;
+                    code.base10_parse::<u32>()?;
+                    Some(HResultValue::Code(code))
+                } else {
+                    Some(HResultValue::Const(input.parse()?))
+                };
+                Ok(HResult {
+                    original: attr,
+                    value,
+                })
+            })?);
+        } else if attr.path.is_ident("retryable") {
+            if attrs.retryable.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[retryable] attribute"));
+            }
+            attrs.retryable = Some(if attr.tokens.is_empty() {
+                Retryable {
+                    original: attr,
+                    condition: None,
+                }
+            } else {
+                attr.parse_args_with(|input: ParseStream| {
+                    input.parse::<Token![if]>()?;
+                    input.parse::<Token![=]>()?;
+                    let condition: Path = input.parse()?;
+                    Ok(Retryable {
+                        original: attr,
+                        condition: Some(condition),
+                    })
+                })?
+            });
+        } else if attr.path.is_ident("sensitive") {
+            if attrs.sensitive.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[sensitive] attribute"));
+            }
+            let reveal = if attr.tokens.is_empty() {
+                Reveal::Nothing
+            } else {
+                attr.parse_args_with(|input: ParseStream| {
+                    syn::custom_keyword!(hash);
+                    syn::custom_keyword!(last4);
+                    let lookahead = input.lookahead1();
+                    if lookahead.peek(hash) {
+                        input.parse::<hash>()?;
+                        Ok(Reveal::Hash)
+                    } else if lookahead.peek(last4) {
+                        input.parse::<last4>()?;
+                        Ok(Reveal::Last4)
+                    } else {
+                        Err(lookahead.error())
+                    }
+                })?
+            };
+            attrs.sensitive = Some(Sensitive {
+                original: attr,
+                reveal,
+            });
+        } else if attr.path.is_ident("display") {
+            let display_with = attr.parse_args_with(|input: ParseStream| {
+                syn::custom_keyword!(with);
+                syn::custom_keyword!(humantime);
+                let lookahead = input.lookahead1();
+                let adapter = if lookahead.peek(humantime) {
+                    input.parse::<humantime>()?;
+                    Adapter::Humantime
+                } else if lookahead.peek(with) {
+                    input.parse::<with>()?;
+                    input.parse::<Token![=]>()?;
+                    Adapter::With(input.parse()?)
+                } else {
+                    return Err(lookahead.error());
+                };
+                input.parse::<Option<Token![,]>>()?;
+                Ok(DisplayWith {
+                    original: attr,
+                    adapter,
+                })
+            });
+            let display_with = match display_with {
+                Ok(display_with) => display_with,
+                // Assume this is meant for derive_more crate or something. Only
+                // on a field is the attribute surely thiserror's, so that is
+                // where validation reports the error.
+                Err(error) => {
+                    attrs.display_with_error = Some(error);
+                    continue;
+                }
+            };
+            if attrs.display_with.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[display(...)] attribute",
+                ));
+            }
+            attrs.display_with = Some(display_with);
+        } else if attr.path.is_ident("repr_code") {
+            if attrs.repr_code.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[repr_code] attribute"));
+            }
+            attrs.repr_code = Some(if attr.tokens.is_empty() {
+                ReprCode {
+                    original: attr,
+                    value: None,
+                }
+            } else {
+                attr.parse_args_with(|input: ParseStream| {
+                    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+                    let code: LitInt = input.parse()?;
+                    let value = if negative {
+                        format!("-{}", code.base10_digits()).parse::<i32>()
+                    } else {
+                        code.base10_digits().parse::<i32>()
+                    };
+                    match value {
+                        Ok(value) => Ok(ReprCode {
+                            original: attr,
+                            value: Some(value),
+                        }),
+                        Err(_) => Err(Error::new_spanned(code, "code does not fit in an i32")),
+                    }
+                })?
+            });
+        } else if attr.path.is_ident("jsonrpc") {
+            if attrs.jsonrpc.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[jsonrpc] attribute"));
+            }
+            attrs.jsonrpc = Some(if attr.tokens.is_empty() {
+                JsonRpc {
+                    original: attr,
+                    code: None,
+                    data: None,
+                }
+            } else {
+                attr.parse_args_with(|input: ParseStream| parse_jsonrpc_args(attr, input))?
+            });
+        } else if attr.path.is_ident("diagnostic") {
+            // Left to miette's own derive unless the type asks for
+            // #[error(diagnostic)], see get_diagnostic.
+            attrs.diagnostic_attrs.push(attr);
+        } else if attr.path.is_ident("into") {
+            let into = match attr.parse_args_with(|input: ParseStream| parse_into_args(attr, input))
+            {
+                Ok(into) => into,
+                // Assume this is meant for derive_more crate or something.
+                Err(_) => continue,
+            };
+            if attrs.into.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[into] attribute"));
+            }
+            attrs.into = Some(into);
+        } else if attr.path.is_ident("from_parts") {
+            require_empty_attribute(attr)?;
+            if attrs.from_parts.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[from_parts] attribute",
+                ));
+            }
+            attrs.from_parts = Some(attr);
+        } else if attr.path.is_ident("try_into") {
+            require_empty_attribute(attr)?;
+            if attrs.try_into.is_some() {
+                return Err(Error::new_spanned(attr, "duplicate #[try_into] attribute"));
+            }
+            attrs.try_into = Some(attr);
+        } else if attr.path.is_ident("from_match") {
+            if attrs.from_match.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "duplicate #[from_match] attribute",
+                ));
             }
-            attrs.from = Some(attr);
+            attrs.from_match = Some(attr.parse_args_with(|input: ParseStream| {
+                let ty: Type = input.parse()?;
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                let arm = if input.is_empty() {
+                    quote!(_)
+                } else {
+                    input.parse()?
+                };
+                Ok(FromMatch {
+                    original: attr,
+                    ty,
+                    arm,
+                })
+            })?);
         }
     }
 
     Ok(attrs)
 }
 
-fn parse_error_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
-    syn::custom_keyword!(transparent);
+fn parse_from_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
+    let mut from = From {
+        original: attr,
+        types: Vec::new(),
+        wrap: None,
+        with: None,
+    };
+    if !attr.tokens.is_empty() {
+        match attr.parse_args_with(|input: ParseStream| parse_from_args(&mut from, input)) {
+            Ok(FromArgs::Default) => {
+                if attrs.from_default.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "duplicate #[from(default)] attribute",
+                    ));
+                }
+                attrs.from_default = Some(attr);
+                return Ok(());
+            }
+            Ok(FromArgs::Conversion) => {}
+            // Assume this is meant for derive_more crate or something.
+            Err(_) => return Ok(()),
+        }
+    }
+    if attrs.from.is_some() {
+        return Err(Error::new_spanned(attr, "duplicate #[from] attribute"));
+    }
+    attrs.from = Some(from);
+    Ok(())
+}
 
-    attr.parse_args_with(|input: ParseStream| {
-        if input.parse::<Option<transparent>>()?.is_some() {
-            if attrs.transparent.is_some() {
-                return Err(Error::new_spanned(
-                    attr,
-                    "duplicate #[error(transparent)] attribute",
+enum FromArgs {
+    Default,
+    Conversion,
+}
+
+fn parse_from_args(from: &mut From, input: ParseStream) -> Result<FromArgs> {
+    syn::custom_keyword!(default);
+    syn::custom_keyword!(arc);
+    syn::custom_keyword!(raw);
+    syn::custom_keyword!(with);
+
+    if input.peek(default) {
+        input.parse::<default>()?;
+        input.parse::<Option<Token![,]>>()?;
+        return Ok(FromArgs::Default);
+    }
+
+    loop {
+        let wrap = if input.peek(arc) {
+            Some((FromWrap::Arc, input.parse::<arc>()?.span))
+        } else if input.peek(Token![box]) {
+            Some((FromWrap::Box, input.parse::<Token![box]>()?.span))
+        } else if input.peek(raw) {
+            Some((FromWrap::Raw, input.parse::<raw>()?.span))
+        } else if input.peek(with) && input.peek2(Token![=]) {
+            let with: with = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if from.with.is_some() {
+                return Err(Error::new(with.span, "duplicate `with` in #[from(...)]"));
+            }
+            from.with = Some(input.parse()?);
+            None
+        } else {
+            from.types.push(input.parse()?);
+            None
+        };
+        if let Some((wrap, span)) = wrap {
+            if from.wrap.is_some() {
+                return Err(Error::new(span, "duplicate wrapper in #[from(...)]"));
+            }
+            from.wrap = Some(wrap);
+        }
+        if input.is_empty() {
+            return Ok(FromArgs::Conversion);
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(FromArgs::Conversion);
+        }
+    }
+}
+
+fn parse_into_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Into<'a>> {
+    syn::custom_keyword!(kind);
+    syn::custom_keyword!(with);
+
+    let mut into = Into {
+        original: attr,
+        target: None,
+        mapping: None,
+    };
+    if !(input.peek(kind) || input.peek(with)) || !input.peek2(Token![=]) {
+        into.target = Some(input.parse()?);
+        if input.is_empty() {
+            return Ok(into);
+        }
+        input.parse::<Token![,]>()?;
+    }
+    let lookahead = input.lookahead1();
+    if lookahead.peek(kind) {
+        input.parse::<kind>()?;
+        input.parse::<Token![=]>()?;
+        into.mapping = Some(IntoMapping::Kind(input.parse()?));
+    } else if lookahead.peek(with) {
+        input.parse::<with>()?;
+        input.parse::<Token![=]>()?;
+        into.mapping = Some(IntoMapping::With(input.parse()?));
+    } else {
+        return Err(lookahead.error());
+    }
+    input.parse::<Option<Token![,]>>()?;
+    Ok(into)
+}
+
+// Parses `fingerprint = "..."`, which may be repeated to build up the list,
+// and `tag(subsystem = "io", ...)`. Tag keys are identifiers or, for keys such
+// as `os.name`, string literals.
+fn parse_sentry_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Sentry<'a>> {
+    syn::custom_keyword!(fingerprint);
+    syn::custom_keyword!(tag);
+
+    let mut sentry = Sentry {
+        original: attr,
+        fingerprint: Vec::new(),
+        tags: Vec::new(),
+    };
+    loop {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(fingerprint) {
+            input.parse::<fingerprint>()?;
+            input.parse::<Token![=]>()?;
+            sentry.fingerprint.push(input.parse()?);
+        } else if lookahead.peek(tag) {
+            input.parse::<tag>()?;
+            let content;
+            parenthesized!(content in input);
+            loop {
+                let key = if content.peek(LitStr) {
+                    content.parse::<LitStr>()?
+                } else {
+                    let ident = content.call(Ident::parse_any)?;
+                    LitStr::new(&ident.unraw().to_string(), ident.span())
+                };
+                content.parse::<Token![=]>()?;
+                let value: LitStr = content.parse()?;
+                if sentry
+                    .tags
+                    .iter()
+                    .any(|(existing, _)| existing.value() == key.value())
+                {
+                    return Err(Error::new_spanned(key, "duplicate tag in #[sentry(...)]"));
+                }
+                sentry.tags.push((key, value));
+                if content.is_empty() {
+                    break;
+                }
+                content.parse::<Token![,]>()?;
+                if content.is_empty() {
+                    break;
+                }
+            }
+        } else {
+            return Err(lookahead.error());
+        }
+        if input.is_empty() {
+            return Ok(sentry);
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(sentry);
+        }
+    }
+}
+
+// Parses `subsystem = "storage", tenant = .tenant_id`. As with Sentry tags,
+// keys are identifiers or string literals.
+fn parse_meta_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Meta<'a>> {
+    let mut meta = Meta {
+        original: attr,
+        entries: Vec::new(),
+    };
+    loop {
+        let key = if input.peek(LitStr) {
+            input.parse::<LitStr>()?
+        } else {
+            let ident = input.call(Ident::parse_any)?;
+            LitStr::new(&ident.unraw().to_string(), ident.span())
+        };
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            MetaValue::Field(input.parse()?)
+        } else {
+            MetaValue::Lit(input.parse()?)
+        };
+        if meta
+            .entries
+            .iter()
+            .any(|(existing, _)| existing.value() == key.value())
+        {
+            return Err(Error::new_spanned(key, "duplicate key in #[meta(...)]"));
+        }
+        meta.entries.push((key, value));
+        if input.is_empty() {
+            return Ok(meta);
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(meta);
+        }
+    }
+}
+
+// Parses the #[diagnostic(...)] attributes collected by get, once the type is
+// known to have opted into #[error(diagnostic)].
+pub fn get_diagnostic(attrs: &mut Attrs) -> Result<()> {
+    if let Some(duplicate) = attrs.diagnostic_attrs.get(1) {
+        return Err(Error::new_spanned(
+            duplicate,
+            "duplicate #[diagnostic] attribute",
+        ));
+    }
+    if let Some(attr) = attrs.diagnostic_attrs.first().copied() {
+        attrs.diagnostic =
+            Some(attr.parse_args_with(|input: ParseStream| parse_diagnostic_args(attr, input))?);
+    }
+    Ok(())
+}
+
+fn parse_diagnostic_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Diagnostic<'a>> {
+    syn::custom_keyword!(code);
+    syn::custom_keyword!(help);
+    syn::custom_keyword!(url);
+
+    let mut diagnostic = Diagnostic {
+        original: attr,
+        code: None,
+        help: None,
+        url: None,
+    };
+    loop {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(code) {
+            let keyword: code = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            // Accept both `code(my_app::bad_input)` and `code("my_app::bad_input")`.
+            let value = if content.peek(LitStr) {
+                content.parse()?
+            } else {
+                let path: Path = content.parse()?;
+                let repr = path.to_token_stream().to_string().replace(' ', "");
+                LitStr::new(&repr, path.span())
+            };
+            content.parse::<Nothing>()?;
+            if diagnostic.code.replace(value).is_some() {
+                return Err(Error::new(
+                    keyword.span,
+                    "duplicate code in #[diagnostic(...)]",
                 ));
             }
-            attrs.transparent = Some(attr);
-            return Ok(());
+        } else if lookahead.peek(help) {
+            let keyword: help = input.parse()?;
+            let help = parse_diagnostic_message(attr, input)?;
+            if diagnostic.help.replace(help).is_some() {
+                return Err(Error::new(
+                    keyword.span,
+                    "duplicate help in #[diagnostic(...)]",
+                ));
+            }
+        } else if lookahead.peek(url) {
+            let keyword: url = input.parse()?;
+            let url = parse_diagnostic_message(attr, input)?;
+            if diagnostic.url.replace(url).is_some() {
+                return Err(Error::new(
+                    keyword.span,
+                    "duplicate url in #[diagnostic(...)]",
+                ));
+            }
+        } else if input.peek(Ident::peek_any) {
+            // Keys like `severity(...)` belong to miette's own derive.
+            skip_diagnostic_arg(input)?;
+        } else {
+            return Err(lookahead.error());
+        }
+        if input.is_empty() {
+            return Ok(diagnostic);
         }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(diagnostic);
+        }
+    }
+}
 
+fn skip_diagnostic_arg(input: ParseStream) -> Result<()> {
+    while !input.is_empty() && !input.peek(Token![,]) {
+        input.parse::<TokenTree>()?;
+    }
+    Ok(())
+}
+
+// The `("format {}", args)` of a help or url, interpolating fields the same
+// way as an #[error("...")] message.
+fn parse_diagnostic_message<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Display<'a>> {
+    let content;
+    parenthesized!(content in input);
+    let fmt: LitStr = content.parse()?;
+    Ok(Display {
+        original: attr,
+        template: fmt.clone(),
+        fmt,
+        args: parse_token_expr(&content, false)?,
+        has_bonus_display: false,
+        implied_bounds: Set::new(),
+        only_arg: None,
+    })
+}
+
+fn parse_error_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Result<()> {
+    attr.parse_args_with(|input: ParseStream| {
+        if !input.peek(LitStr) {
+            return parse_error_options(attrs, attr, input);
+        }
+
+        let fmt: LitStr = input.parse()?;
         let display = Display {
             original: attr,
-            fmt: input.parse()?,
+            template: fmt.clone(),
+            fmt,
             args: parse_token_expr(input, false)?,
             has_bonus_display: false,
+            implied_bounds: Set::new(),
+            only_arg: None,
         };
         if attrs.display.is_some() {
             return Err(Error::new_spanned(
@@ -94,6 +1063,358 @@ fn parse_error_attribute<'a>(attrs: &mut Attrs<'a>, attr: &'a Attribute) -> Resu
     })
 }
 
+fn parse_error_options<'a>(
+    attrs: &mut Attrs<'a>,
+    attr: &'a Attribute,
+    input: ParseStream,
+) -> Result<()> {
+    syn::custom_keyword!(transparent);
+    syn::custom_keyword!(chain);
+    syn::custom_keyword!(fuzz_safe);
+    syn::custom_keyword!(owned);
+    syn::custom_keyword!(from_str);
+    syn::custom_keyword!(message);
+    syn::custom_keyword!(serialize);
+    syn::custom_keyword!(deserialize);
+    syn::custom_keyword!(status);
+    syn::custom_keyword!(expose);
+    syn::custom_keyword!(into_response);
+    syn::custom_keyword!(response_error);
+    syn::custom_keyword!(js_value);
+    syn::custom_keyword!(trace);
+    syn::custom_keyword!(hook);
+    syn::custom_keyword!(log);
+    syn::custom_keyword!(metrics);
+    syn::custom_keyword!(diagnostic);
+    syn::custom_keyword!(defmt);
+    syn::custom_keyword!(from_anyhow);
+    syn::custom_keyword!(from_eyre);
+    syn::custom_keyword!(into_report);
+    syn::custom_keyword!(json_schema);
+    syn::custom_keyword!(into_responses);
+    syn::custom_keyword!(accessors);
+    syn::custom_keyword!(name);
+    syn::custom_keyword!(kind);
+    syn::custom_keyword!(code);
+    syn::custom_keyword!(constructors);
+    syn::custom_keyword!(context);
+    syn::custom_keyword!(macros);
+    syn::custom_keyword!(fallback);
+    syn::custom_keyword!(builder);
+    syn::custom_keyword!(other);
+    syn::custom_keyword!(catalog);
+    syn::custom_keyword!(describe);
+    syn::custom_keyword!(deref);
+    syn::custom_keyword!(partial_eq);
+    syn::custom_keyword!(clone);
+    syn::custom_keyword!(no_alloc);
+    syn::custom_keyword!(bound);
+    syn::custom_keyword!(deny_unused);
+    syn::custom_keyword!(dispatch);
+    syn::custom_keyword!(inline);
+    syn::custom_keyword!(cold);
+    syn::custom_keyword!(style);
+
+    loop {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(transparent) {
+            input.parse::<transparent>()?;
+            set_option(&mut attrs.transparent, attr, attr, "transparent")?;
+        } else if lookahead.peek(chain) {
+            input.parse::<chain>()?;
+            set_option(&mut attrs.chain, attr, attr, "chain")?;
+        } else if lookahead.peek(owned) {
+            input.parse::<owned>()?;
+            set_option(&mut attrs.owned, attr, attr, "owned")?;
+        } else if lookahead.peek(from_str) {
+            input.parse::<from_str>()?;
+            set_option(&mut attrs.from_str, attr, attr, "from_str")?;
+        } else if lookahead.peek(message) {
+            input.parse::<message>()?;
+            set_option(&mut attrs.message, attr, attr, "message")?;
+        } else if lookahead.peek(serialize) {
+            input.parse::<serialize>()?;
+            set_option(&mut attrs.serialize, attr, attr, "serialize")?;
+        } else if lookahead.peek(deserialize) {
+            input.parse::<deserialize>()?;
+            set_option(&mut attrs.deserialize, attr, attr, "deserialize")?;
+        } else if lookahead.peek(fuzz_safe) {
+            input.parse::<fuzz_safe>()?;
+            set_option(&mut attrs.fuzz_safe, attr, attr, "fuzz_safe")?;
+        } else if lookahead.peek(status) {
+            input.parse::<status>()?;
+            input.parse::<Token![=]>()?;
+            let status: LitInt = input.parse()?;
+            status.base10_parse::<u16>()?;
+            set_option(
+                &mut attrs.status,
+                Status::Code(status),
+                attr,
+                "status = ...",
+            )?;
+        } else if lookahead.peek(into_response) {
+            input.parse::<into_response>()?;
+            let response = parse_response_args(attr, input)?;
+            set_option(&mut attrs.into_response, response, attr, "into_response")?;
+        } else if lookahead.peek(response_error) {
+            input.parse::<response_error>()?;
+            let response = parse_response_args(attr, input)?;
+            set_option(&mut attrs.response_error, response, attr, "response_error")?;
+        } else if lookahead.peek(js_value) {
+            input.parse::<js_value>()?;
+            set_option(&mut attrs.js_value, attr, attr, "js_value")?;
+        } else if lookahead.peek(trace) {
+            input.parse::<trace>()?;
+            set_option(&mut attrs.trace, attr, attr, "trace")?;
+        } else if lookahead.peek(hook) {
+            input.parse::<hook>()?;
+            set_option(&mut attrs.hook, attr, attr, "hook")?;
+        } else if lookahead.peek(log) {
+            input.parse::<log>()?;
+            set_option(&mut attrs.log, attr, attr, "log")?;
+        } else if lookahead.peek(metrics) {
+            input.parse::<metrics>()?;
+            set_option(&mut attrs.metrics, attr, attr, "metrics")?;
+        } else if lookahead.peek(diagnostic) {
+            input.parse::<diagnostic>()?;
+            set_option(&mut attrs.derive_diagnostic, attr, attr, "diagnostic")?;
+        } else if lookahead.peek(defmt) {
+            input.parse::<defmt>()?;
+            set_option(&mut attrs.defmt, attr, attr, "defmt")?;
+        } else if lookahead.peek(from_anyhow) {
+            input.parse::<from_anyhow>()?;
+            set_option(&mut attrs.from_anyhow, attr, attr, "from_anyhow")?;
+        } else if lookahead.peek(from_eyre) {
+            input.parse::<from_eyre>()?;
+            set_option(&mut attrs.from_eyre, attr, attr, "from_eyre")?;
+        } else if lookahead.peek(into_report) {
+            input.parse::<into_report>()?;
+            set_option(&mut attrs.into_report, attr, attr, "into_report")?;
+        } else if lookahead.peek(json_schema) {
+            input.parse::<json_schema>()?;
+            set_option(&mut attrs.json_schema, attr, attr, "json_schema")?;
+        } else if lookahead.peek(into_responses) {
+            input.parse::<into_responses>()?;
+            set_option(&mut attrs.into_responses, attr, attr, "into_responses")?;
+        } else if lookahead.peek(accessors) {
+            input.parse::<accessors>()?;
+            set_option(&mut attrs.accessors, attr, attr, "accessors")?;
+        } else if lookahead.peek(name) {
+            input.parse::<name>()?;
+            set_option(&mut attrs.name, attr, attr, "name")?;
+        } else if lookahead.peek(kind) {
+            input.parse::<kind>()?;
+            set_option(&mut attrs.kind, attr, attr, "kind")?;
+        } else if lookahead.peek(code) {
+            input.parse::<code>()?;
+            set_option(&mut attrs.code_method, attr, attr, "code")?;
+        } else if lookahead.peek(constructors) {
+            input.parse::<constructors>()?;
+            set_option(&mut attrs.constructors, attr, attr, "constructors")?;
+        } else if lookahead.peek(context) {
+            input.parse::<context>()?;
+            set_option(&mut attrs.context, attr, attr, "context")?;
+        } else if lookahead.peek(macros) {
+            input.parse::<macros>()?;
+            set_option(&mut attrs.macros, attr, attr, "macros")?;
+        } else if lookahead.peek(fallback) {
+            input.parse::<fallback>()?;
+            set_option(&mut attrs.fallback, attr, attr, "fallback")?;
+        } else if lookahead.peek(builder) {
+            input.parse::<builder>()?;
+            set_option(&mut attrs.builder, attr, attr, "builder")?;
+        } else if lookahead.peek(other) {
+            input.parse::<other>()?;
+            set_option(&mut attrs.other, attr, attr, "other")?;
+        } else if lookahead.peek(catalog) {
+            input.parse::<catalog>()?;
+            set_option(&mut attrs.catalog, attr, attr, "catalog")?;
+        } else if lookahead.peek(describe) {
+            input.parse::<describe>()?;
+            set_option(&mut attrs.describe, attr, attr, "describe")?;
+        } else if lookahead.peek(deref) {
+            input.parse::<deref>()?;
+            set_option(&mut attrs.deref, attr, attr, "deref")?;
+        } else if lookahead.peek(partial_eq) {
+            input.parse::<partial_eq>()?;
+            set_option(&mut attrs.partial_eq, attr, attr, "partial_eq")?;
+        } else if lookahead.peek(clone) {
+            input.parse::<clone>()?;
+            set_option(&mut attrs.clone, attr, attr, "clone")?;
+        } else if lookahead.peek(no_alloc) {
+            input.parse::<no_alloc>()?;
+            set_option(&mut attrs.no_alloc, attr, attr, "no_alloc")?;
+        } else if lookahead.peek(deny_unused) {
+            input.parse::<deny_unused>()?;
+            set_option(&mut attrs.deny_unused, attr, attr, "deny_unused")?;
+        } else if lookahead.peek(dispatch) {
+            input.parse::<dispatch>()?;
+            set_option(&mut attrs.dispatch, attr, attr, "dispatch")?;
+        } else if lookahead.peek(inline) {
+            input.parse::<inline>()?;
+            set_option(&mut attrs.inline, attr, attr, "inline")?;
+        } else if lookahead.peek(cold) {
+            input.parse::<cold>()?;
+            set_option(&mut attrs.cold, attr, attr, "cold")?;
+        } else if lookahead.peek(style) {
+            input.parse::<style>()?;
+            let style = parse_style(attr, input)?;
+            set_option(&mut attrs.style, style, attr, "style(...)")?;
+        } else if lookahead.peek(Token![trait]) {
+            input.parse::<Token![trait]>()?;
+            input.parse::<Token![=]>()?;
+            let custom_trait: Path = input.parse()?;
+            set_option(&mut attrs.custom_trait, custom_trait, attr, "trait = ...")?;
+        } else if lookahead.peek(Token![crate]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            let crate_path: Path = input.parse()?;
+            set_option(&mut attrs.crate_path, crate_path, attr, "crate = ...")?;
+        } else if lookahead.peek(expose) {
+            input.parse::<expose>()?;
+            input.parse::<Token![=]>()?;
+            let expose: LitBool = input.parse()?;
+            set_option(&mut attrs.expose, expose, attr, "expose = ...")?;
+        } else if lookahead.peek(bound) {
+            input.parse::<bound>()?;
+            input.parse::<Token![=]>()?;
+            let predicates: LitStr = input.parse()?;
+            let bound = Bound {
+                original: attr,
+                predicates: predicates.parse_with(Punctuated::parse_terminated)?,
+            };
+            set_option(&mut attrs.bound, bound, attr, "bound = ...")?;
+        } else {
+            return Err(lookahead.error());
+        }
+        if input.is_empty() {
+            return Ok(());
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(());
+        }
+    }
+}
+
+fn parse_jsonrpc_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<JsonRpc<'a>> {
+    syn::custom_keyword!(code);
+    syn::custom_keyword!(data);
+
+    let mut jsonrpc = JsonRpc {
+        original: attr,
+        code: None,
+        data: None,
+    };
+    loop {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(code) {
+            let keyword: code = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: LitInt = input.parse()?;
+            value.base10_parse::<i64>()?;
+            if jsonrpc.code.replace(value).is_some() {
+                return Err(Error::new(
+                    keyword.span,
+                    "duplicate code in #[jsonrpc(...)]",
+                ));
+            }
+        } else if lookahead.peek(data) {
+            let keyword: data = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if jsonrpc.data.replace(input.parse()?).is_some() {
+                return Err(Error::new(
+                    keyword.span,
+                    "duplicate data in #[jsonrpc(...)]",
+                ));
+            }
+        } else {
+            return Err(lookahead.error());
+        }
+        if input.is_empty() {
+            return Ok(jsonrpc);
+        }
+        input.parse::<Token![,]>()?;
+        if input.is_empty() {
+            return Ok(jsonrpc);
+        }
+    }
+}
+
+// Parses the optional arguments that follow a response option such as
+// `into_response`: either `json`, selecting the `{"code": ..., "message": ...}`
+// body layout, or `with = path` naming a function that builds the response.
+fn parse_response_args<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Response<'a>> {
+    syn::custom_keyword!(json);
+    syn::custom_keyword!(with);
+
+    let mut response = Response {
+        original: attr,
+        json: None,
+        with: None,
+    };
+    if input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        let lookahead = content.lookahead1();
+        if lookahead.peek(json) {
+            let keyword: json = content.parse()?;
+            response.json = Some(Ident::new("json", keyword.span));
+        } else if lookahead.peek(with) {
+            content.parse::<with>()?;
+            content.parse::<Token![=]>()?;
+            response.with = Some(content.parse()?);
+        } else {
+            return Err(lookahead.error());
+        }
+        content.parse::<Nothing>()?;
+    }
+    Ok(response)
+}
+
+fn parse_style<'a>(attr: &'a Attribute, input: ParseStream) -> Result<Style<'a>> {
+    syn::custom_keyword!(lowercase_start);
+    syn::custom_keyword!(no_trailing_period);
+
+    let mut style = Style {
+        original: attr,
+        lowercase_start: false,
+        no_trailing_period: false,
+    };
+    let content;
+    parenthesized!(content in input);
+    loop {
+        let lookahead = content.lookahead1();
+        if lookahead.peek(lowercase_start) {
+            content.parse::<lowercase_start>()?;
+            style.lowercase_start = true;
+        } else if lookahead.peek(no_trailing_period) {
+            content.parse::<no_trailing_period>()?;
+            style.no_trailing_period = true;
+        } else {
+            return Err(lookahead.error());
+        }
+        if content.is_empty() {
+            return Ok(style);
+        }
+        content.parse::<Token![,]>()?;
+        if content.is_empty() {
+            return Ok(style);
+        }
+    }
+}
+
+fn set_option<T>(slot: &mut Option<T>, value: T, attr: &Attribute, name: &str) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            format!("duplicate #[error({})] attribute", name),
+        ));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
 fn parse_token_expr(input: ParseStream, mut last_is_comma: bool) -> Result<TokenStream> {
     let mut tokens = Vec::new();
     while !input.is_empty() {
@@ -147,12 +1468,120 @@ fn require_empty_attribute(attr: &Attribute) -> Result<()> {
     Ok(())
 }
 
+impl ToTokens for From<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Into<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for FromMatch<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Py<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Errno<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Sentry<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Meta<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for HResult<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for ReprCode<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for JsonRpc<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Response<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.original.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for Status {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Status::Code(status) => status.to_tokens(tokens),
+            Status::Const(status) => status.to_tokens(tokens),
+        }
+    }
+}
+
+impl ToTokens for Trait {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let trait_name = match self {
+            Trait::Debug => "Debug",
+            Trait::Display => "Display",
+            Trait::Octal => "Octal",
+            Trait::LowerHex => "LowerHex",
+            Trait::UpperHex => "UpperHex",
+            Trait::Pointer => "Pointer",
+            Trait::Binary => "Binary",
+            Trait::LowerExp => "LowerExp",
+            Trait::UpperExp => "UpperExp",
+        };
+        let trait_name = Ident::new(trait_name, Span::call_site());
+        tokens.extend(quote!(::core::fmt::#trait_name));
+    }
+}
+
 impl ToTokens for Display<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        // Most messages are either fixed text or a single field. Neither
+        // needs the Arguments that write! builds inline for every variant.
+        if let Some(only_arg) = &self.only_arg {
+            tokens.extend(quote! {
+                thiserror::private::write_display(__formatter, &#only_arg)
+            });
+            return;
+        }
         let fmt = &self.fmt;
         let args = &self.args;
+        let value = fmt.value();
+        if args.is_empty() && !value.contains('{') && !value.contains('}') {
+            tokens.extend(quote! {
+                __formatter.write_str(#fmt)
+            });
+            return;
+        }
         tokens.extend(quote! {
-            write!(__formatter, #fmt #args)
+            ::core::write!(__formatter, #fmt #args)
         });
     }
 }
@@ -0,0 +1,128 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::type_is_option;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Generics, Ident};
+
+// Backtraces only exist in std. They are handed out through
+// thiserror::Backtraced rather than Error::backtrace, which never left
+// nightly.
+impl Struct<'_> {
+    pub(crate) fn backtraced_impl(&self) -> Option<TokenStream> {
+        let backtrace_field = self.backtrace_field().filter(|_| cfg!(feature = "std"))?;
+        let backtrace = &backtrace_field.member;
+        let own_backtrace = if type_is_option(backtrace_field.ty) {
+            quote!(self.#backtrace.as_ref())
+        } else {
+            quote!(::core::option::Option::Some(&self.#backtrace))
+        };
+        let body = match self.static_source_field() {
+            Some(source_field) => {
+                let source = &source_field.member;
+                let source_backtrace =
+                    source_backtrace(source_field, quote_spanned!(source.span()=> &self.#source));
+                quote!(#source_backtrace.or(#own_backtrace))
+            }
+            None => own_backtrace,
+        };
+        Some(backtraced_impl(&self.ident, self.generics, body))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn backtraced_impl(&self) -> Option<TokenStream> {
+        if !(self.has_backtrace() && cfg!(feature = "std")) {
+            return None;
+        }
+        if self.attrs.dispatch.is_some() {
+            let body = quote! {
+                let mut backtrace = ::core::option::Option::None;
+                self.__thiserror_dispatch(&mut |dispatch| backtrace = (dispatch.backtrace)());
+                backtrace
+            };
+            return Some(backtraced_impl(&self.ident, self.generics, body));
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match (variant.backtrace_field(), variant.static_source_field()) {
+                (Some(backtrace_field), Some(source_field))
+                    if backtrace_field.member != source_field.member =>
+                {
+                    let backtrace = &backtrace_field.member;
+                    let source = &source_field.member;
+                    let source_backtrace = source_backtrace(source_field, quote!(source));
+                    let own_backtrace = if type_is_option(backtrace_field.ty) {
+                        quote!(backtrace.as_ref())
+                    } else {
+                        quote!(::core::option::Option::Some(backtrace))
+                    };
+                    quote! {
+                        #ty::#ident {
+                            #backtrace: backtrace,
+                            #source: source,
+                            ..
+                        } => #source_backtrace.or(#own_backtrace),
+                    }
+                }
+                (Some(backtrace_field), _) => {
+                    let backtrace = &backtrace_field.member;
+                    let body = if type_is_option(backtrace_field.ty) {
+                        quote!(backtrace.as_ref())
+                    } else {
+                        quote!(::core::option::Option::Some(backtrace))
+                    };
+                    quote! {
+                        #ty::#ident {#backtrace: backtrace, ..} => #body,
+                    }
+                }
+                (None, _) => quote! {
+                    #ty::#ident {..} => ::core::option::Option::None,
+                },
+            }
+        });
+        let body = quote! {
+            match self {
+                #(#arms)*
+            }
+        };
+        Some(backtraced_impl(ty, self.generics, body))
+    }
+}
+
+// The backtrace carried by a source, given an expression borrowing it. The
+// source is asked through thiserror::Backtraced if it implements it, and
+// otherwise counts as having no backtrace.
+pub(crate) fn source_backtrace(source_field: &Field, source: TokenStream) -> TokenStream {
+    let span = source_field.member.span();
+    let backtrace_of = |source: TokenStream| {
+        quote_spanned! {span=>
+            (&thiserror::private::BacktraceOf(#source)).backtrace_ref()
+        }
+    };
+    let backtrace = if type_is_option(source_field.ty) {
+        let backtrace = backtrace_of(quote!(source));
+        quote! {
+            ::core::option::Option::as_ref(#source).and_then(|source| #backtrace)
+        }
+    } else {
+        backtrace_of(source)
+    };
+    quote!({
+        #[allow(unused_imports)]
+        use thiserror::private::{BacktracedDelegate, BacktracedFallback};
+        #backtrace
+    })
+}
+
+fn backtraced_impl(ty: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics thiserror::Backtraced for #ty #ty_generics #where_clause {
+            fn backtrace_ref(&self) -> ::core::option::Option<&thiserror::private::std::backtrace::Backtrace> {
+                #body
+            }
+        }
+    }
+}
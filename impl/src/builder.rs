@@ -0,0 +1,169 @@
+use crate::ast::{Enum, Field, Variant};
+use crate::case::snake_case;
+use crate::constructors::captured;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Enum<'_> {
+    pub(crate) fn builder_methods(&self) -> Option<TokenStream> {
+        let ty = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let methods: Vec<TokenStream> = self
+            .variants
+            .iter()
+            .filter(|variant| variant.attrs.builder.is_some())
+            .map(|variant| {
+                let ident = &variant.ident;
+                let name = format_ident!(
+                    "{}_builder",
+                    snake_case(&ident.to_string()),
+                    span = ident.span(),
+                );
+                let builder = builder_ident(ty, ident);
+                let doc = format!(
+                    "Starts building a [`{}::{}`] error. Fields left unset are filled with their `Default`.",
+                    ty, ident,
+                );
+                let (source_param, source_init) = match required_source(variant) {
+                    Some(source) => {
+                        let member = &source.member;
+                        let source_ty = source.ty;
+                        (
+                            Some(quote!(#member: impl ::core::convert::Into<#source_ty>)),
+                            Some(quote!(#member: ::core::convert::Into::into(#member),)),
+                        )
+                    }
+                    None => (None, None),
+                };
+                let members = optional_fields(variant).map(|field| &field.member);
+                let deprecated = variant.deprecated();
+                quote! {
+                    #[doc = #doc]
+                    #deprecated
+                    pub fn #name(#source_param) -> #builder #ty_generics {
+                        #builder {
+                            #source_init
+                            #(#members: ::core::option::Option::None,)*
+                            __marker: ::core::marker::PhantomData,
+                        }
+                    }
+                }
+            })
+            .collect();
+        if methods.is_empty() {
+            return None;
+        }
+        Some(quote!(#(#methods)*))
+    }
+
+    pub(crate) fn builder_types(&self) -> Option<TokenStream> {
+        let ty = &self.ident;
+        let vis = &self.original.vis;
+        let generics = self.generics;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let types: Vec<TokenStream> = self
+            .variants
+            .iter()
+            .filter(|variant| variant.attrs.builder.is_some())
+            .map(|variant| {
+                let ident = &variant.ident;
+                let builder = builder_ident(ty, ident);
+                let doc = format!("Builder for [`{}::{}`].", ty, ident);
+                let source = required_source(variant).map(|source| {
+                    let member = &source.member;
+                    let source_ty = source.ty;
+                    (quote!(#member: #source_ty,), quote!(#member: self.#member,))
+                });
+                let (source_decl, source_init) = match source {
+                    Some((decl, init)) => (Some(decl), Some(init)),
+                    None => (None, None),
+                };
+                let optional: Vec<&Field> = optional_fields(variant).collect();
+                let decls = optional.iter().map(|field| {
+                    let member = &field.member;
+                    let field_ty = field.ty;
+                    quote!(#member: ::core::option::Option<#field_ty>,)
+                });
+                let setters = optional.iter().map(|field| {
+                    let member = &field.member;
+                    let field_ty = field.ty;
+                    let doc = format!("Sets the `{}` field.", quote!(#member));
+                    quote! {
+                        #[doc = #doc]
+                        pub fn #member(mut self, #member: impl ::core::convert::Into<#field_ty>) -> Self {
+                            self.#member = ::core::option::Option::Some(::core::convert::Into::into(#member));
+                            self
+                        }
+                    }
+                });
+                let inits = variant.fields.iter().filter_map(|field| {
+                    let member = &field.member;
+                    if let Some(captured) = captured(variant, field) {
+                        return Some(quote!(#member: #captured,));
+                    }
+                    if optional.iter().any(|optional| optional.member == field.member) {
+                        return Some(quote!(#member: self.#member.unwrap_or_default(),));
+                    }
+                    None
+                });
+                let traced = traced(
+                    &self.attrs,
+                    Some(&variant.attrs),
+                    variant.source_field().is_some(),
+                    quote!(#ty::#ident { #source_init #(#inits)* }),
+                );
+                let track_caller = if variant.fields.iter().any(Field::is_location) {
+                    Some(quote!(#[track_caller]))
+                } else {
+                    None
+                };
+                quote! {
+                    #[doc = #doc]
+                    #vis struct #builder #generics #where_clause {
+                        #source_decl
+                        #(#decls)*
+                        __marker: ::core::marker::PhantomData<fn() -> #ty #ty_generics>,
+                    }
+
+                    impl #impl_generics #builder #ty_generics #where_clause {
+                        #(#setters)*
+
+                        /// Creates the error from the fields set so far.
+                        #track_caller
+                        pub fn build(self) -> #ty #ty_generics {
+                            #traced
+                        }
+                    }
+                }
+            })
+            .collect();
+        if types.is_empty() {
+            return None;
+        }
+        Some(quote!(#(#types)*))
+    }
+}
+
+fn builder_ident(ty: &Ident, variant: &Ident) -> Ident {
+    format_ident!("{}{}Builder", ty, variant)
+}
+
+// The source is taken up front by the builder function, since an error has no
+// sensible default to fall back on.
+fn required_source<'a>(variant: &'a Variant) -> Option<&'a Field<'a>> {
+    variant
+        .source_field()
+        .filter(|source| captured(variant, source).is_none())
+}
+
+// Fields with a setter: everything other than the source and the captured
+// backtrace, span trace and location.
+fn optional_fields<'a>(variant: &'a Variant) -> impl Iterator<Item = &'a Field<'a>> {
+    let source = required_source(variant);
+    variant.fields.iter().filter(move |field| {
+        captured(variant, field).is_none()
+            && !matches!(source, Some(source) if source.member == field.member)
+    })
+}
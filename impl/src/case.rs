@@ -0,0 +1,20 @@
+// MyError -> my_error, HTTPError -> http_error.
+pub(crate) fn snake_case(ident: &str) -> String {
+    let ident = ident.trim_start_matches("r#");
+    let chars: Vec<char> = ident.chars().collect();
+    let mut snake = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(next) if next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    snake
+}
@@ -0,0 +1,128 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use crate::severity::severity;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn descriptors(&self) -> TokenStream {
+        let descriptor = descriptor(&self.ident, &self.attrs, None);
+        quote!(&[#descriptor])
+    }
+
+    pub(crate) fn catalog_const(&self) -> Option<TokenStream> {
+        self.attrs.catalog?;
+        Some(catalog_const(&self.descriptors()))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn descriptors(&self) -> TokenStream {
+        let descriptors = self
+            .variants
+            .iter()
+            .map(|variant| descriptor(&variant.ident, &variant.attrs, Some(&self.attrs)));
+        quote!(&[#(#descriptors),*])
+    }
+
+    pub(crate) fn catalog_const(&self) -> Option<TokenStream> {
+        self.attrs.catalog?;
+        Some(catalog_const(&self.descriptors()))
+    }
+}
+
+// For a variant, `attrs` are its own and `container` the enum's, which only
+// supplies the default severity.
+fn descriptor(ident: &Ident, attrs: &Attrs, container: Option<&Attrs>) -> TokenStream {
+    let name = ident.to_string();
+    let message = match &attrs.display {
+        Some(display) => {
+            let template = &display.template;
+            quote!(::core::option::Option::Some(#template))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+    let code = match &attrs.code {
+        Some(code) => quote!(::core::option::Option::Some(#code)),
+        None => quote!(::core::option::Option::None),
+    };
+    let severity = match container {
+        Some(container) => severity(container, Some(attrs)),
+        None => severity(attrs, None),
+    };
+    quote! {
+        thiserror::ErrorDescriptor {
+            name: #name,
+            message: #message,
+            code: #code,
+            severity: #severity,
+        }
+    }
+}
+
+fn catalog_const(descriptors: &TokenStream) -> TokenStream {
+    quote! {
+        /// Static description of every variant of this error, for building
+        /// documentation and support tooling.
+        pub const CATALOG: &'static [thiserror::ErrorDescriptor] = #descriptors;
+    }
+}
+
+// Registers the type before main on targets with static constructors, and
+// implements Registrable so that it can be registered by hand elsewhere.
+pub fn registration(
+    ident: &Ident,
+    generics: &Generics,
+    descriptors: &TokenStream,
+) -> Option<TokenStream> {
+    if !cfg!(feature = "registry") {
+        return None;
+    }
+
+    let type_name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        const _: () = {
+            static __THISERROR_REGISTERED: thiserror::RegisteredError =
+                thiserror::RegisteredError {
+                    type_name: #type_name,
+                    module_path: ::core::module_path!(),
+                    descriptors: #descriptors,
+                };
+            static __THISERROR_NODE: thiserror::private::RegistryNode =
+                thiserror::private::RegistryNode::new(&__THISERROR_REGISTERED);
+            #[used]
+            #[cfg_attr(
+                any(
+                    target_os = "linux",
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd",
+                    target_os = "dragonfly",
+                    target_os = "illumos",
+                ),
+                link_section = ".init_array"
+            )]
+            #[cfg_attr(
+                any(target_os = "macos", target_os = "ios"),
+                link_section = "__DATA,__mod_init_func"
+            )]
+            #[cfg_attr(windows, link_section = ".CRT$XCU")]
+            static __THISERROR_CTOR: extern "C" fn() = {
+                extern "C" fn __thiserror_register() {
+                    __THISERROR_NODE.register();
+                }
+                __thiserror_register
+            };
+            // Out of reach of allow_deprecated, which only sees top level impls.
+            #[allow(deprecated)]
+            impl #impl_generics thiserror::Registrable for #ident #ty_generics #where_clause {
+                fn node() -> &'static thiserror::private::RegistryNode {
+                    &__THISERROR_NODE
+                }
+            }
+        };
+    })
+}
@@ -0,0 +1,70 @@
+use crate::ast::{Enum, Struct};
+use crate::partial_eq::binding;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn clone_impl(&self) -> Option<TokenStream> {
+        self.attrs.clone?;
+        let ty = &self.ident;
+        let fields = self.fields.iter().map(|field| {
+            let member = &field.member;
+            quote!(#member: ::core::clone::Clone::clone(&self.#member),)
+        });
+        Some(clone_impl(ty, self.generics, quote!(#ty { #(#fields)* })))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn clone_impl(&self) -> Option<TokenStream> {
+        self.attrs.clone?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let members: Vec<_> = variant.fields.iter().map(|field| &field.member).collect();
+            let bindings: Vec<Ident> = members
+                .iter()
+                .map(|member| binding("self", member))
+                .collect();
+            quote! {
+                #ty::#ident { #(#members: #bindings,)* } => #ty::#ident {
+                    #(#members: ::core::clone::Clone::clone(#bindings),)*
+                },
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(clone_impl(
+            ty,
+            self.generics,
+            quote! {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            },
+        ))
+    }
+}
+
+fn clone_impl(ty: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut bounded = generics.clone();
+    let where_clause = bounded.make_where_clause();
+    for param in generics.type_params() {
+        let param = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::core::clone::Clone));
+    }
+    quote! {
+        impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::error_id::new_error_id;
+use crate::timestamp;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn parse_code_impl(&self) -> Option<TokenStream> {
+        self.attrs.from_str?;
+        let code = self.attrs.code.as_ref()?;
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let init = default_initializer(&self.fields);
+        Some(parse_code_impl(
+            quote!(impl #impl_generics ::core::str::FromStr for #ty #ty_generics #where_clause),
+            vec![quote!(#code => ::core::result::Result::Ok(#ty #init),)],
+        ))
+    }
+
+    pub(crate) fn code_methods(&self) -> Option<TokenStream> {
+        let code = self.attrs.code.as_ref()?;
+        let code_method = self.attrs.code_method.map(|_| code_method(quote!(#code)));
+        let codes_method = self.attrs.from_str.map(|_| codes_method(vec![code]));
+        if code_method.is_none() && codes_method.is_none() {
+            return None;
+        }
+        Some(quote! {
+            #code_method
+            #codes_method
+        })
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn parse_code_impl(&self) -> Option<TokenStream> {
+        self.attrs.from_str?;
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let arms = self
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let code = variant.attrs.code.as_ref()?;
+                let ident = &variant.ident;
+                let init = default_initializer(&variant.fields);
+                Some(quote!(#code => ::core::result::Result::Ok(#ty::#ident #init),))
+            })
+            .collect();
+        Some(parse_code_impl(
+            quote!(impl #impl_generics ::core::str::FromStr for #ty #ty_generics #where_clause),
+            arms,
+        ))
+    }
+
+    pub(crate) fn code_methods(&self) -> Option<TokenStream> {
+        let code_method = self.attrs.code_method.map(|_| {
+            let ty = &self.ident;
+            let arms = self.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let code = &variant.attrs.code;
+                quote!(#ty::#ident {..} => #code,)
+            });
+            let void_deref = if self.variants.is_empty() {
+                Some(quote!(*))
+            } else {
+                None
+            };
+            code_method(quote! {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            })
+        });
+        let codes_method = self.attrs.from_str.map(|_| {
+            let codes = self
+                .variants
+                .iter()
+                .filter_map(|variant| variant.attrs.code.as_ref())
+                .collect();
+            codes_method(codes)
+        });
+        if code_method.is_none() && codes_method.is_none() {
+            return None;
+        }
+        Some(quote! {
+            #code_method
+            #codes_method
+        })
+    }
+}
+
+pub(crate) fn default_initializer(fields: &[Field]) -> TokenStream {
+    let inits = fields.iter().map(|field| {
+        let member = &field.member;
+        if field.attrs.error_id.is_some() {
+            let error_id = new_error_id();
+            quote!(#member: #error_id,)
+        } else if field.attrs.timestamp.is_some() {
+            let now = timestamp::now(field);
+            quote!(#member: #now,)
+        } else {
+            quote!(#member: ::core::default::Default::default(),)
+        }
+    });
+    quote!({
+        #(#inits)*
+    })
+}
+
+fn parse_code_impl(header: TokenStream, arms: Vec<TokenStream>) -> TokenStream {
+    quote! {
+        #header {
+            type Err = thiserror::ParseCodeError;
+
+            fn from_str(code: &str) -> ::core::result::Result<Self, Self::Err> {
+                match code {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(thiserror::ParseCodeError::new(code)),
+                }
+            }
+        }
+    }
+}
+
+fn code_method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The stable `#[code("...")]` identifier of this error.
+        pub fn code(&self) -> &'static str {
+            #body
+        }
+    }
+}
+
+fn codes_method(codes: Vec<&syn::LitStr>) -> TokenStream {
+    quote! {
+        /// Every `#[code("...")]` identifier declared on this type, in
+        /// declaration order. Each one parses back through `FromStr`.
+        pub fn codes() -> &'static [&'static str] {
+            &[#(#codes),*]
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::ast::{Enum, Field, Variant};
+use crate::case::snake_case;
+use crate::error_id::new_error_id;
+use crate::expand::type_is_option;
+use crate::timestamp;
+use crate::trace::traced;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Ident, Member};
+
+impl Enum<'_> {
+    pub(crate) fn constructor_methods(&self) -> Option<TokenStream> {
+        self.attrs.constructors?;
+        let ty = &self.ident;
+        // The #[whatever] and #[error(other)] variants have
+        // constructors of their own.
+        let constructors = self
+            .variants
+            .iter()
+            .filter(|variant| variant.attrs.whatever.is_none() && variant.attrs.other.is_none())
+            .map(|variant| {
+                let ident = &variant.ident;
+                let name = constructor_ident(&snake_case(&ident.to_string()), ident.span());
+                let doc = format!("Creates a [`{}::{}`] error.", ty, ident);
+                let mut params = Vec::new();
+                let mut inits = Vec::new();
+                let mut track_caller = None;
+                for (i, field) in variant.fields.iter().enumerate() {
+                    let member = &field.member;
+                    if let Some(captured) = captured(variant, field) {
+                        inits.push(quote!(#member: #captured,));
+                        if field.is_location() {
+                            track_caller = Some(quote!(#[track_caller]));
+                        }
+                        continue;
+                    }
+                    let param = param_ident(field, i);
+                    let field_ty = field.ty;
+                    params.push(quote!(#param: impl ::core::convert::Into<#field_ty>));
+                    inits.push(quote!(#member: ::core::convert::Into::into(#param),));
+                }
+                let traced = traced(
+                    &self.attrs,
+                    Some(&variant.attrs),
+                    variant.source_field().is_some(),
+                    quote!(#ty::#ident { #(#inits)* }),
+                );
+                let deprecated = variant.deprecated();
+                quote! {
+                    #[doc = #doc]
+                    #deprecated
+                    #track_caller
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #name(#(#params),*) -> Self {
+                        #traced
+                    }
+                }
+            });
+        Some(quote!(#(#constructors)*))
+    }
+}
+
+// Fields that constructors fill in themselves rather than take as arguments:
+// a backtrace other than the source's own, a span trace, the location of the
+// caller, an #[error_id], and a #[timestamp].
+pub(crate) fn captured(variant: &Variant, field: &Field) -> Option<TokenStream> {
+    let is_field =
+        |other: Option<&Field>| matches!(other, Some(other) if other.member == field.member);
+    if is_field(variant.backtrace_field()) && !is_field(variant.source_field()) {
+        return Some(if type_is_option(field.ty) {
+            quote!(::core::option::Option::Some(
+                thiserror::private::std::backtrace::Backtrace::capture()
+            ))
+        } else {
+            quote!(thiserror::private::std::backtrace::Backtrace::capture())
+        });
+    }
+    if let (true, Some(span_trace)) = (
+        is_field(variant.span_trace_field()),
+        field.span_trace_type(),
+    ) {
+        return Some(if type_is_option(field.ty) {
+            quote!(::core::option::Option::Some(<#span_trace>::capture()))
+        } else {
+            quote!(<#span_trace>::capture())
+        });
+    }
+    if field.is_location() {
+        return Some(quote!(::core::panic::Location::caller()));
+    }
+    if field.attrs.error_id.is_some() {
+        return Some(new_error_id());
+    }
+    if field.attrs.timestamp.is_some() {
+        return Some(timestamp::now(field));
+    }
+    None
+}
+
+// The argument that sets a field: the field's own name, or _0 and so on for
+// tuple variants.
+pub(crate) fn param_ident(field: &Field, i: usize) -> Ident {
+    match &field.member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(_) => format_ident!("_{}", i),
+    }
+}
+
+// Variants named after keywords, like `Type`, get raw identifiers. The few
+// keywords that cannot be raw get a trailing underscore instead.
+fn constructor_ident(snake: &str, span: Span) -> Ident {
+    if syn::parse_str::<Ident>(snake).is_ok() {
+        Ident::new(snake, span)
+    } else if ["crate", "self", "super"].contains(&snake) {
+        format_ident!("{}_", snake, span = span)
+    } else {
+        Ident::new_raw(snake, span)
+    }
+}
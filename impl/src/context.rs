@@ -0,0 +1,95 @@
+use crate::ast::Enum;
+use crate::case::snake_case;
+use crate::constructors::{captured, param_ident};
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+impl Enum<'_> {
+    // An extension trait for Result with a method per variant that has a
+    // source, turning the error of the result into that variant along with
+    // the rest of its fields.
+    pub(crate) fn context_trait(&self) -> Option<TokenStream> {
+        self.attrs.context?;
+        let ty = &self.ident;
+        let vis = &self.original.vis;
+        let context = format_ident!("{}Context", ty);
+        let mut signatures = Vec::new();
+        let mut methods = Vec::new();
+        for variant in &self.variants {
+            let source = match variant.source_field() {
+                Some(source) => source,
+                None => continue,
+            };
+            let ident = &variant.ident;
+            let name = format_ident!(
+                "ctx_{}",
+                snake_case(&ident.to_string()),
+                span = ident.span(),
+            );
+            let doc = format!(
+                "Converts the error of this result into a [`{}::{}`] error.",
+                ty, ident,
+            );
+            let source_ty = source.ty;
+            let mut params = Vec::new();
+            let mut inits = Vec::new();
+            let mut track_caller = None;
+            for (i, field) in variant.fields.iter().enumerate() {
+                let member = &field.member;
+                if field.member == source.member {
+                    inits.push(quote!(#member: ::core::convert::Into::into(__source),));
+                } else if let Some(captured) = captured(variant, field) {
+                    inits.push(quote!(#member: #captured,));
+                    if field.is_location() {
+                        track_caller = Some(quote!(#[track_caller]));
+                    }
+                } else {
+                    let param = param_ident(field, i);
+                    let field_ty = field.ty;
+                    params.push(quote!(#param: impl ::core::convert::Into<#field_ty>));
+                    inits.push(quote!(#member: ::core::convert::Into::into(#param),));
+                }
+            }
+            let signature = quote! {
+                fn #name(self, #(#params),*) -> ::core::result::Result<__T, #ty>
+                where
+                    __E: ::core::convert::Into<#source_ty>
+            };
+            let traced = traced(
+                &self.attrs,
+                Some(&variant.attrs),
+                true,
+                quote!(#ty::#ident { #(#inits)* }),
+            );
+            signatures.push(quote! {
+                #[doc = #doc]
+                #[allow(clippy::too_many_arguments)]
+                #signature;
+            });
+            methods.push(quote! {
+                #track_caller
+                #signature {
+                    match self {
+                        ::core::result::Result::Ok(value) => ::core::result::Result::Ok(value),
+                        ::core::result::Result::Err(__source) => ::core::result::Result::Err(#traced),
+                    }
+                }
+            });
+        }
+        let doc = format!(
+            "Extension methods for `Result` that convert its error into a variant of [`{}`].",
+            ty,
+        );
+        Some(quote! {
+            #[doc = #doc]
+            #vis trait #context<__T, __E> {
+                #(#signatures)*
+            }
+
+            impl<__T, __E> #context<__T, __E> for ::core::result::Result<__T, __E> {
+                #(#methods)*
+            }
+        })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::type_is_option;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Generics, Ident, Path};
+
+impl Struct<'_> {
+    pub(crate) fn custom_trait_impl(&self) -> Option<TokenStream> {
+        let custom_trait = self.attrs.custom_trait.as_ref()?;
+        let body = if self.attrs.transparent.is_some() {
+            let only_field = &self.fields[0].member;
+            quote! {
+                thiserror::CustomError::<dyn #custom_trait + 'static>::custom_source(&self.#only_field)
+            }
+        } else if let Some(source_field) = self.static_source_field() {
+            let member = &source_field.member;
+            let source = source_as(custom_trait, source_field, quote!(source));
+            quote! {
+                let source = &self.#member;
+                ::core::option::Option::Some(#source)
+            }
+        } else {
+            quote!(::core::option::Option::None)
+        };
+        Some(custom_trait_impl(
+            &self.ident,
+            self.generics,
+            custom_trait,
+            self.generic_sources(),
+            body,
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn custom_trait_impl(&self) -> Option<TokenStream> {
+        let custom_trait = self.attrs.custom_trait.as_ref()?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            if variant.attrs.transparent.is_some() {
+                let only_field = &variant.fields[0].member;
+                quote! {
+                    #ty::#ident {#only_field: transparent} => {
+                        thiserror::CustomError::<dyn #custom_trait + 'static>::custom_source(transparent)
+                    }
+                }
+            } else if let Some(source_field) = variant.static_source_field() {
+                let member = &source_field.member;
+                let source = source_as(custom_trait, source_field, quote!(source));
+                quote! {
+                    #ty::#ident {#member: source, ..} => ::core::option::Option::Some(#source),
+                }
+            } else {
+                quote! {
+                    #ty::#ident {..} => ::core::option::Option::None,
+                }
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(custom_trait_impl(
+            ty,
+            self.generics,
+            custom_trait,
+            self.generic_sources(),
+            quote! {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            },
+        ))
+    }
+}
+
+// A reference to the source field, unsized into the framework's trait object.
+// An optional source returns early from the method when it is absent.
+fn source_as(custom_trait: &Path, source_field: &Field, source: TokenStream) -> TokenStream {
+    let span = source_field.member.span();
+    if type_is_option(source_field.ty) {
+        quote_spanned!(span=> #source.as_ref()? as &(dyn #custom_trait + 'static))
+    } else {
+        quote_spanned!(span=> #source as &(dyn #custom_trait + 'static))
+    }
+}
+
+fn custom_trait_impl(
+    ty: &Ident,
+    generics: &Generics,
+    custom_trait: &Path,
+    params: Vec<&Ident>,
+    body: TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut bounded = generics.clone();
+    let where_clause = bounded.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: #custom_trait + 'static));
+    }
+    let where_clause = &bounded.where_clause;
+    quote! {
+        impl #impl_generics thiserror::CustomError<dyn #custom_trait + 'static>
+            for #ty #ty_generics #where_clause
+        {
+            fn custom_source(&self) -> ::core::option::Option<&(dyn #custom_trait + 'static)> {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Display;
+use crate::expand::fields_pat;
+use crate::fmt::split_args;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote};
+use std::collections::BTreeMap as Map;
+use syn::{LitStr, Member};
+
+impl Struct<'_> {
+    pub(crate) fn format_impl(&self) -> Option<TokenStream> {
+        self.attrs.defmt?;
+        let body = if self.attrs.transparent.is_some() {
+            let only_field = &self.fields[0].member;
+            quote!(defmt::Format::format(&self.#only_field, __formatter))
+        } else {
+            let display = self.attrs.display.as_ref()?;
+            let pat = fields_pat(&self.fields);
+            let write = write(display);
+            quote! {
+                #[allow(unused_variables)]
+                let Self #pat = self;
+                #write
+            }
+        };
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        Some(quote! {
+            impl #impl_generics defmt::Format for #ty #ty_generics #where_clause {
+                fn format(&self, __formatter: defmt::Formatter) {
+                    #body
+                }
+            }
+        })
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn format_impl(&self) -> Option<TokenStream> {
+        self.attrs.defmt?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let write = match &variant.attrs.display {
+                Some(display) => write(display),
+                None => {
+                    let only_field = match &variant.fields[0].member {
+                        Member::Named(ident) => ident.clone(),
+                        Member::Unnamed(index) => format_ident!("_{}", index),
+                    };
+                    quote!(defmt::Format::format(#only_field, __formatter))
+                }
+            };
+            let ident = &variant.ident;
+            let pat = fields_pat(&variant.fields);
+            quote! {
+                #ty::#ident #pat => { #write }
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        Some(quote! {
+            impl #impl_generics defmt::Format for #ty #ty_generics #where_clause {
+                fn format(&self, __formatter: defmt::Formatter) {
+                    #[allow(unused_variables)]
+                    match #void_deref self {
+                        #(#arms)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+// The same format string and arguments as the Display impl, which defmt
+// interns instead of formatting on the device.
+fn write(display: &Display) -> TokenStream {
+    let use_as_display = if display.has_bonus_display {
+        Some(quote! {
+            #[allow(unused_imports)]
+            use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
+        })
+    } else {
+        None
+    };
+    let (fmt, args) = positional(display);
+    quote! {
+        #use_as_display
+        defmt::write!(__formatter, #fmt #(, #args)*)
+    }
+}
+
+// defmt takes no named arguments, so each placeholder of the Display format
+// string loses its name, as in `{value:?}` to `{:?}`, and the arguments are
+// passed in the order of the placeholders.
+fn positional(display: &Display) -> (LitStr, Vec<TokenStream>) {
+    let mut unnamed = Vec::new();
+    let mut named = Map::new();
+    for arg in split_args(&display.args).into_iter().skip(1) {
+        match arg.as_slice() {
+            [TokenTree::Ident(name), TokenTree::Punct(eq), value @ ..]
+                if eq.as_char() == '=' && eq.spacing() == Spacing::Alone =>
+            {
+                named.insert(name.to_string(), value.iter().cloned().collect());
+            }
+            _ => unnamed.push(arg.into_iter().collect::<TokenStream>()),
+        }
+    }
+
+    let fmt = display.fmt.value();
+    let mut read = fmt.as_str();
+    let mut out = String::new();
+    let mut args = Vec::new();
+    let mut next = 0;
+    while let Some(brace) = read.find('{') {
+        out += &read[..brace + 1];
+        read = &read[brace + 1..];
+        if read.starts_with('{') {
+            out.push('{');
+            read = &read[1..];
+            continue;
+        }
+        let end = read.find([':', '=', '}']).unwrap_or(read.len());
+        let name = &read[..end];
+        read = &read[end..];
+        let arg = if name.is_empty() {
+            next += 1;
+            unnamed.get(next - 1)
+        } else if let Ok(index) = name.parse::<usize>() {
+            unnamed.get(index)
+        } else {
+            named.get(name)
+        };
+        args.extend(arg.cloned());
+    }
+    out += read;
+    (LitStr::new(&out, display.fmt.span()), args)
+}
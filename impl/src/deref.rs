@@ -0,0 +1,37 @@
+use crate::ast::Struct;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    // Deref, DerefMut and AsRef to the error wrapped by a transparent struct,
+    // exposing its API through the wrapper.
+    pub(crate) fn deref_impls(&self) -> Option<TokenStream> {
+        self.attrs.deref?;
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let field = &self.fields[0];
+        let member = &field.member;
+        let inner = field.ty;
+        Some(quote! {
+            impl #impl_generics ::core::ops::Deref for #ty #ty_generics #where_clause {
+                type Target = #inner;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.#member
+                }
+            }
+
+            impl #impl_generics ::core::ops::DerefMut for #ty #ty_generics #where_clause {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.#member
+                }
+            }
+
+            impl #impl_generics ::core::convert::AsRef<#inner> for #ty #ty_generics #where_clause {
+                fn as_ref(&self) -> &#inner {
+                    &self.#member
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Lit, Meta};
+
+impl Struct<'_> {
+    pub(crate) fn describe_methods(&self) -> Option<TokenStream> {
+        self.attrs.describe?;
+        let description = description(&self.original.attrs);
+        Some(method(quote!(#description)))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn describe_methods(&self) -> Option<TokenStream> {
+        self.attrs.describe?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let description = description(&variant.original.attrs);
+            quote!(#ty::#ident {..} => #description,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// The text of the doc comments, with the space that follows `///` removed
+// from each line. Doc attributes that are not string literals, such as
+// `#[doc = include_str!("...")]`, are skipped.
+fn description(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        if let Ok(Meta::NameValue(meta)) = attr.parse_meta() {
+            if let Lit::Str(doc) = meta.lit {
+                for line in doc.value().split('\n') {
+                    let line = line.strip_prefix(' ').unwrap_or(line);
+                    lines.push(line.trim_end().to_owned());
+                }
+            }
+        }
+    }
+    lines.join("\n").trim_matches('\n').to_owned()
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The doc comment of the variant, or of the type for a struct, as a
+        /// longer explanation than the `Display` message.
+        pub fn describe(&self) -> &'static str {
+            #body
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::{Attrs, Display};
+use crate::expand::fields_pat;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn diagnostic_impl(&self) -> Option<TokenStream> {
+        let diagnostic = self.attrs.diagnostic.as_ref()?;
+        let pat = fields_pat(&self.fields);
+        let message = |display: &Display| {
+            let format = format(display);
+            quote! {
+                #[allow(unused_variables)]
+                let Self #pat = self;
+                #format
+            }
+        };
+        Some(diagnostic_impl(
+            &self.ident,
+            self.generics,
+            code(&self.attrs).map(|code| quote!(::core::option::Option::Some(#code))),
+            diagnostic.help.as_ref().map(message),
+            diagnostic.url.as_ref().map(message),
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn diagnostic_impl(&self) -> Option<TokenStream> {
+        if self.attrs.diagnostic.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.diagnostic.is_none())
+        {
+            return None;
+        }
+
+        let ty = &self.ident;
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+
+        let code = if self
+            .variants
+            .iter()
+            .any(|variant| code(&variant.attrs).is_some())
+        {
+            let arms = self.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                match code(&variant.attrs) {
+                    Some(code) => quote!(#ty::#ident {..} => ::core::option::Option::Some(#code),),
+                    None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+                }
+            });
+            Some(quote! {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            })
+        } else {
+            None
+        };
+
+        Some(diagnostic_impl(
+            ty,
+            self.generics,
+            code,
+            self.message(|attrs| attrs.diagnostic.as_ref()?.help.as_ref()),
+            self.message(|attrs| attrs.diagnostic.as_ref()?.url.as_ref()),
+        ))
+    }
+
+    fn message(
+        &self,
+        key: impl for<'x> Fn(&'x Attrs) -> Option<&'x Display<'x>>,
+    ) -> Option<TokenStream> {
+        if self
+            .variants
+            .iter()
+            .all(|variant| key(&variant.attrs).is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match key(&variant.attrs) {
+                Some(display) => {
+                    let pat = fields_pat(&variant.fields);
+                    let format = format(display);
+                    quote! {
+                        #[allow(unused_variables)]
+                        #ty::#ident #pat => { #format }
+                    }
+                }
+                None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        })
+    }
+}
+
+// The diagnostic code, either from #[diagnostic(code(...))] or falling back to
+// the plain #[code("...")] identifier.
+fn code(attrs: &Attrs) -> Option<TokenStream> {
+    let code = attrs
+        .diagnostic
+        .as_ref()
+        .and_then(|diagnostic| diagnostic.code.as_ref())
+        .or(attrs.code.as_ref())?;
+    Some(quote! {
+        thiserror::private::std::boxed::Box::new(#code) as thiserror::private::std::boxed::Box<dyn ::core::fmt::Display + '__a>
+    })
+}
+
+fn format(display: &Display) -> TokenStream {
+    let use_as_display = if display.has_bonus_display {
+        Some(quote! {
+            #[allow(unused_imports)]
+            use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
+        })
+    } else {
+        None
+    };
+    let fmt = &display.fmt;
+    let args = &display.args;
+    quote! {
+        #use_as_display
+        ::core::option::Option::Some(thiserror::private::std::boxed::Box::new(thiserror::private::std::format!(#fmt #args)))
+    }
+}
+
+fn diagnostic_impl(
+    ty: &Ident,
+    generics: &Generics,
+    code: Option<TokenStream>,
+    help: Option<TokenStream>,
+    url: Option<TokenStream>,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let code = code.map(|body| method("code", body));
+    let help = help.map(|body| method("help", body));
+    let url = url.map(|body| method("url", body));
+    quote! {
+        impl #impl_generics miette::Diagnostic for #ty #ty_generics #where_clause {
+            #code
+            #help
+            #url
+        }
+    }
+}
+
+fn method(name: &str, body: TokenStream) -> TokenStream {
+    let name = Ident::new(name, Span::call_site());
+    quote! {
+        fn #name<'__a>(
+            &'__a self,
+        ) -> ::core::option::Option<thiserror::private::std::boxed::Box<dyn ::core::fmt::Display + '__a>> {
+            #body
+        }
+    }
+}
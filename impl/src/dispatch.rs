@@ -0,0 +1,134 @@
+use crate::ast::{Enum, Variant};
+use crate::backtrace::source_backtrace;
+use crate::expand::{error_trait, field_local, fields_pat, type_is_option, variant_display};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+
+// With #[error(dispatch)], source(), backtrace_ref() and fmt() share a single
+// match over the variants. It hands the parts of the matched variant to a
+// callback, so that a huge enum is matched once instead of three times. The
+// parts are closures, so the caller only evaluates the one it asks for.
+impl Enum<'_> {
+    pub(crate) fn dispatch_method(&self) -> Option<TokenStream> {
+        self.attrs.dispatch?;
+        let ty = &self.ident;
+        let use_as_display = if self
+            .variants
+            .iter()
+            .any(|v| matches!(&v.attrs.display, Some(display) if display.has_bonus_display))
+        {
+            Some(quote! {
+                #[allow(unused_imports)]
+                use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
+            })
+        } else {
+            None
+        };
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let pat = fields_pat(&variant.fields);
+            let source = dispatch_source(variant);
+            let backtrace = if cfg!(feature = "std") {
+                let backtrace = dispatch_backtrace(variant);
+                Some(quote!(backtrace: &|| #backtrace,))
+            } else {
+                None
+            };
+            let display = variant_display(variant);
+            quote! {
+                #ty::#ident #pat => __visit(thiserror::private::Dispatch {
+                    source: &|| #source,
+                    #backtrace
+                    display: &|__formatter: &mut ::core::fmt::Formatter| -> ::core::fmt::Result {
+                        #display
+                    },
+                }),
+            }
+        });
+        Some(quote! {
+            #[doc(hidden)]
+            fn __thiserror_dispatch<'__a>(
+                &'__a self,
+                __visit: &mut dyn ::core::ops::FnMut(thiserror::private::Dispatch<'__a, '_>),
+            ) {
+                #use_as_display
+                #[allow(unused_imports)]
+                use thiserror::AsDynError;
+                #[allow(unused_variables)]
+                match #void_deref self {
+                    #(#arms)*
+                }
+            }
+        })
+    }
+
+    pub(crate) fn dispatch_source_method(&self) -> TokenStream {
+        let error_trait = error_trait();
+        quote! {
+            fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+                let mut source = ::core::option::Option::None;
+                self.__thiserror_dispatch(&mut |dispatch| source = (dispatch.source)());
+                source
+            }
+        }
+    }
+
+    pub(crate) fn dispatch_display_body(&self) -> TokenStream {
+        quote! {
+            let mut result = ::core::result::Result::Ok(());
+            self.__thiserror_dispatch(&mut |dispatch| result = (dispatch.display)(__formatter));
+            result
+        }
+    }
+}
+
+fn dispatch_source(variant: &Variant) -> TokenStream {
+    let error_trait = error_trait();
+    if variant.attrs.transparent.is_some() {
+        let only_field = &variant.fields[0];
+        let local = field_local(&only_field.member);
+        if only_field.is_borrowed() {
+            quote!(#error_trait::source(#local))
+        } else {
+            quote!(#error_trait::source(#local.as_dyn_error()))
+        }
+    } else if let Some(source_field) = variant.static_source_field() {
+        let source = field_local(&source_field.member);
+        if type_is_option(source_field.ty) {
+            quote_spanned! {source.span()=>
+                #source.as_ref().map(|source| source.as_dyn_error())
+            }
+        } else {
+            quote_spanned! {source.span()=>
+                ::core::option::Option::Some(#source.as_dyn_error())
+            }
+        }
+    } else {
+        quote!(::core::option::Option::None)
+    }
+}
+
+fn dispatch_backtrace(variant: &Variant) -> TokenStream {
+    let backtrace_field = match variant.backtrace_field() {
+        Some(backtrace_field) => backtrace_field,
+        None => return quote!(::core::option::Option::None),
+    };
+    let backtrace = field_local(&backtrace_field.member);
+    let own_backtrace = if type_is_option(backtrace_field.ty) {
+        quote!(#backtrace.as_ref())
+    } else {
+        quote!(::core::option::Option::Some(#backtrace))
+    };
+    let source_field = match variant.static_source_field() {
+        Some(source_field) if backtrace_field.member != source_field.member => source_field,
+        _ => return own_backtrace,
+    };
+    let source = field_local(&source_field.member);
+    let source_backtrace = source_backtrace(source_field, quote!(#source));
+    quote!(#source_backtrace.or(#own_backtrace))
+}
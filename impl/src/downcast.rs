@@ -0,0 +1,62 @@
+use crate::ast::Enum;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Enum<'_> {
+    // From<anyhow::Error> or From<eyre::Report>, which have the same
+    // downcast method.
+    pub(crate) fn downcast_conversion_impl(&self) -> Option<TokenStream> {
+        let dynamic = if self.attrs.from_anyhow.is_some() {
+            quote!(anyhow::Error)
+        } else if self.attrs.from_eyre.is_some() {
+            quote!(eyre::Report)
+        } else {
+            return None;
+        };
+        // The #[error(fallback)] variant takes the error as is; without one,
+        // the #[error(other)] variant takes it boxed.
+        let fallback = self
+            .variants
+            .iter()
+            .find(|variant| variant.attrs.fallback.is_some())
+            .or(self.other_variant())?;
+
+        // Each attempt hands the error back on failure, so the order of the
+        // variants decides which type wins when several could match.
+        let attempts = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.from_field())
+            .flat_map(|from_field| from_field.from_types())
+            .map(|from| {
+                quote! {
+                    let error = match error.downcast::<#from>() {
+                        ::core::result::Result::Ok(source) => {
+                            return <Self as ::core::convert::From<#from>>::from(source);
+                        }
+                        ::core::result::Result::Err(error) => error,
+                    };
+                }
+            });
+
+        let ty = &self.ident;
+        let ident = &fallback.ident;
+        let member = &fallback.fields[0].member;
+        let traced = traced(
+            &self.attrs,
+            Some(&fallback.attrs),
+            false,
+            quote!(#ty::#ident { #member: ::core::convert::From::from(error) }),
+        );
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<#dynamic> for #ty #ty_generics #where_clause {
+                fn from(error: #dynamic) -> Self {
+                    #(#attempts)*
+                    #traced
+                }
+            }
+        })
+    }
+}
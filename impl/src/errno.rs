@@ -0,0 +1,105 @@
+use crate::ast::{Enum, Struct, Variant};
+use crate::code::default_initializer;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn errno_methods(&self) -> Option<TokenStream> {
+        let value = self.attrs.errno.as_ref()?.value.as_ref()?;
+        Some(method(quote!(::core::option::Option::Some(#value))))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn errno_methods(&self) -> Option<TokenStream> {
+        if self
+            .variants
+            .iter()
+            .all(|variant| variant.attrs.errno.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.attrs.errno {
+                Some(errno) => match &errno.value {
+                    Some(value) => {
+                        quote!(#ty::#ident {..} => ::core::option::Option::Some(#value),)
+                    }
+                    None => {
+                        let member = &variant.fields[0].member;
+                        quote!(#ty::#ident { #member: __errno } => ::core::option::Option::Some(*__errno),)
+                    }
+                },
+                None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+
+    pub(crate) fn errno_conversion_impl(&self) -> Option<TokenStream> {
+        let other = self.errno_other_variant()?;
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let known = self.variants.iter().filter_map(|variant| {
+            let value = variant.attrs.errno.as_ref()?.value.as_ref()?;
+            let ident = &variant.ident;
+            let init = default_initializer(&variant.fields);
+            let traced = traced(
+                &self.attrs,
+                Some(&variant.attrs),
+                false,
+                quote!(#ty::#ident #init),
+            );
+            Some(quote! {
+                if errno == #value {
+                    #traced
+                } else
+            })
+        });
+        let ident = &other.ident;
+        let member = &other.fields[0].member;
+        let other = traced(
+            &self.attrs,
+            Some(&other.attrs),
+            false,
+            quote!(#ty::#ident { #member: errno }),
+        );
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<i32> for #ty #ty_generics #where_clause {
+                fn from(errno: i32) -> Self {
+                    #(#known)* {
+                        #other
+                    }
+                }
+            }
+        })
+    }
+
+    pub(crate) fn errno_other_variant(&self) -> Option<&Variant<'_>> {
+        self.variants
+            .iter()
+            .find(|variant| matches!(&variant.attrs.errno, Some(errno) if errno.value.is_none()))
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The OS error code corresponding to this error, as in
+        /// `std::io::Error::raw_os_error`.
+        pub fn raw_os_error(&self) -> ::core::option::Option<i32> {
+            #body
+        }
+    }
+}
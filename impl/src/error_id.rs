@@ -0,0 +1,58 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// The value that generated From impls and constructors put in an #[error_id]
+// field: a fresh thiserror::ErrorId, converted into the field's type.
+pub(crate) fn new_error_id() -> TokenStream {
+    quote!(::core::convert::From::from(thiserror::ErrorId::next()))
+}
+
+impl Struct<'_> {
+    pub(crate) fn error_id_methods(&self) -> Option<TokenStream> {
+        let error_id_field = self.error_id_field()?;
+        let member = &error_id_field.member;
+        let ty = error_id_field.ty;
+        Some(quote! {
+            /// The identifier this error was assigned when it was created.
+            pub fn error_id(&self) -> &#ty {
+                &self.#member
+            }
+        })
+    }
+}
+
+impl Enum<'_> {
+    // Validation makes sure that every #[error_id] field has the same type.
+    pub(crate) fn error_id_methods(&self) -> Option<TokenStream> {
+        let ty = self
+            .variants
+            .iter()
+            .find_map(|variant| variant.error_id_field())?
+            .ty;
+        let enum_ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match variant.error_id_field() {
+                Some(error_id_field) => {
+                    let member = &error_id_field.member;
+                    quote! {
+                        #enum_ty::#ident { #member: error_id, .. } => {
+                            ::core::option::Option::Some(error_id)
+                        }
+                    }
+                }
+                None => quote!(#enum_ty::#ident {..} => ::core::option::Option::None,),
+            }
+        });
+        Some(quote! {
+            /// The identifier this error was assigned when it was created, if
+            /// the variant carries one.
+            pub fn error_id(&self) -> ::core::option::Option<&#ty> {
+                match self {
+                    #(#arms)*
+                }
+            }
+        })
+    }
+}
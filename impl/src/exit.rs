@@ -0,0 +1,87 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn exit_methods(&self) -> Option<TokenStream> {
+        let code = exit_code(&self.attrs, None)?;
+        Some(method(code))
+    }
+
+    pub(crate) fn exit_status_impl(&self) -> Option<TokenStream> {
+        self.attrs.exit_code.as_ref()?;
+        Some(exit_status_impl(&self.ident, self.generics))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn exit_methods(&self) -> Option<TokenStream> {
+        if !self.has_exit_code() {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let code = exit_code(&self.attrs, Some(&variant.attrs)).unwrap_or(quote!(1u8));
+            quote!(#ty::#ident {..} => #code,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+
+    pub(crate) fn exit_status_impl(&self) -> Option<TokenStream> {
+        if !self.has_exit_code() {
+            return None;
+        }
+        Some(exit_status_impl(&self.ident, self.generics))
+    }
+
+    fn has_exit_code(&self) -> bool {
+        self.attrs.exit_code.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.exit_code.is_some())
+    }
+}
+
+// The variant's exit code, else the container's. Variants of an enum with
+// neither exit with status 1.
+fn exit_code(container: &Attrs, variant: Option<&Attrs>) -> Option<TokenStream> {
+    let code = variant
+        .and_then(|variant| variant.exit_code.as_ref())
+        .or(container.exit_code.as_ref())?;
+    let value = code.base10_parse::<u8>().unwrap();
+    Some(quote!(#value))
+}
+
+fn method(code: TokenStream) -> TokenStream {
+    quote! {
+        /// The status with which the process exits when `main` fails with
+        /// this error.
+        pub fn exit_code(&self) -> thiserror::private::std::process::ExitCode {
+            thiserror::private::std::process::ExitCode::from(#code)
+        }
+    }
+}
+
+fn exit_status_impl(ty: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics thiserror::ExitStatus for #ty #ty_generics #where_clause {
+            fn exit_code(&self) -> thiserror::private::std::process::ExitCode {
+                Self::exit_code(self)
+            }
+        }
+    }
+}
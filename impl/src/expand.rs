@@ -1,28 +1,125 @@
-use crate::ast::{Enum, Field, Input, Struct};
-use proc_macro2::TokenStream;
+use crate::ast::{Enum, Field, Input, Struct, Variant};
+use crate::attr::{Attrs, Bound, From, FromWrap, IntoMapping, Trait};
+use crate::backtrace::source_backtrace;
+use crate::catalog;
+use crate::error_id::new_error_id;
+use crate::timestamp;
+use crate::trace::traced;
+use proc_macro2::{Group, Spacing, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::iter;
 use syn::spanned::Spanned;
-use syn::{DeriveInput, Member, PathArguments, Result, Type};
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Generics, Ident, Index, Member, Path, PathArguments,
+    Result, Type, WhereClause,
+};
 
 pub fn derive(node: &DeriveInput) -> Result<TokenStream> {
     let input = Input::from_syn(node)?;
     input.validate()?;
-    Ok(match input {
-        Input::Struct(input) => impl_struct(input),
-        Input::Enum(input) => impl_enum(input),
+    let crate_path = match &input {
+        Input::Struct(input) => input.attrs.crate_path.clone(),
+        Input::Enum(input) => input.attrs.crate_path.clone(),
+    };
+    let expanded = match input {
+        Input::Struct(input) => impl_struct(input)?,
+        Input::Enum(input) => impl_enum(input)?,
+    };
+    let expanded = allow_deprecated(node, expanded);
+    Ok(match crate_path {
+        Some(crate_path) => replace_crate_path(expanded, &crate_path),
+        None => expanded,
     })
 }
 
-fn impl_struct(input: Struct) -> TokenStream {
+// The expansion refers to the runtime crate as `thiserror`, which only
+// resolves if it is a direct dependency. With #[error(crate = path)], every
+// path starting with `thiserror::` starts with the given path instead, so
+// that a facade crate re-exporting the derive can point it at its own
+// re-export of thiserror.
+fn replace_crate_path(expanded: TokenStream, crate_path: &Path) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    let mut iter = expanded.into_iter().peekable();
+    // Whether the previous tokens were a `::`, after which `thiserror` is a
+    // segment in the middle of some other path. A single `:`, as in a field
+    // or a type ascription, does not count.
+    let mut after_path_sep = false;
+    let mut after_joint_colon = false;
+    while let Some(tt) = iter.next() {
+        let (is_colon, is_joint) = match &tt {
+            TokenTree::Punct(punct) => (punct.as_char() == ':', punct.spacing() == Spacing::Joint),
+            _ => (false, false),
+        };
+        match tt {
+            TokenTree::Ident(ident)
+                if ident == "thiserror"
+                    && !after_path_sep
+                    && matches!(iter.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == ':') =>
+            {
+                tokens.extend(crate_path.to_token_stream());
+            }
+            TokenTree::Group(group) => {
+                let stream = replace_crate_path(group.stream(), crate_path);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                tokens.extend(iter::once(TokenTree::Group(replaced)));
+            }
+            tt => tokens.extend(iter::once(tt)),
+        }
+        after_path_sep = is_colon && after_joint_colon;
+        after_joint_colon = is_colon && is_joint;
+    }
+    tokens
+}
+
+// The generated impls necessarily refer to a #[deprecated] type or variant,
+// which would warn in the user's crate. Each of them opts out, while code
+// outside the expansion keeps warning.
+fn allow_deprecated(node: &DeriveInput, expanded: TokenStream) -> TokenStream {
+    let is_deprecated =
+        |attrs: &[Attribute]| attrs.iter().any(|attr| attr.path.is_ident("deprecated"));
+    let any_deprecated = is_deprecated(&node.attrs)
+        || match &node.data {
+            Data::Enum(data) => data
+                .variants
+                .iter()
+                .any(|variant| is_deprecated(&variant.attrs)),
+            Data::Struct(_) | Data::Union(_) => false,
+        };
+    if !any_deprecated {
+        return expanded;
+    }
+    let mut tokens = TokenStream::new();
+    for tt in expanded {
+        if let TokenTree::Ident(ident) = &tt {
+            if ident == "impl" {
+                tokens.extend(quote!(#[allow(deprecated)]));
+            }
+        }
+        tokens.extend(iter::once(tt));
+    }
+    tokens
+}
+
+fn impl_struct(input: Struct) -> Result<TokenStream> {
     let ty = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let hint = method_hint(&input.attrs);
+    let error_trait = error_trait();
 
     let source_body = if input.attrs.transparent.is_some() {
-        let only_field = &input.fields[0].member;
-        Some(quote! {
-            std::error::Error::source(self.#only_field.as_dyn_error())
-        })
-    } else if let Some(source_field) = input.source_field() {
+        let only_field = &input.fields[0];
+        let member = &only_field.member;
+        if only_field.is_borrowed() {
+            Some(quote! {
+                #error_trait::source(&self.#member)
+            })
+        } else {
+            Some(quote! {
+                #error_trait::source(self.#member.as_dyn_error())
+            })
+        }
+    } else if let Some(source_field) = input.static_source_field() {
         let source = &source_field.member;
         let asref = if type_is_option(source_field.ty) {
             Some(quote_spanned!(source.span()=> .as_ref()?))
@@ -31,57 +128,15 @@ fn impl_struct(input: Struct) -> TokenStream {
         };
         let dyn_error = quote_spanned!(source.span()=> self.#source #asref.as_dyn_error());
         Some(quote! {
-            std::option::Option::Some(#dyn_error)
+            ::core::option::Option::Some(#dyn_error)
         })
     } else {
         None
     };
     let source_method = source_body.map(|body| {
         quote! {
-            fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
-                use thiserror::private::AsDynError;
-                #body
-            }
-        }
-    });
-
-    let backtrace_method = input.backtrace_field().map(|backtrace_field| {
-        let backtrace = &backtrace_field.member;
-        let body = if let Some(source_field) = input.source_field() {
-            let source = &source_field.member;
-            let source_backtrace = if type_is_option(source_field.ty) {
-                quote_spanned! {source.span()=>
-                    self.#source.as_ref().and_then(|source| source.as_dyn_error().backtrace())
-                }
-            } else {
-                quote_spanned! {source.span()=>
-                    self.#source.as_dyn_error().backtrace()
-                }
-            };
-            let combinator = if type_is_option(backtrace_field.ty) {
-                quote! {
-                    #source_backtrace.or(self.#backtrace.as_ref())
-                }
-            } else {
-                quote! {
-                    std::option::Option::Some(#source_backtrace.unwrap_or(&self.#backtrace))
-                }
-            };
-            quote! {
-                use thiserror::private::AsDynError;
-                #combinator
-            }
-        } else if type_is_option(backtrace_field.ty) {
-            quote! {
-                self.#backtrace.as_ref()
-            }
-        } else {
-            quote! {
-                std::option::Option::Some(&self.#backtrace)
-            }
-        };
-        quote! {
-            fn backtrace(&self) -> std::option::Option<&std::backtrace::Backtrace> {
+            fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+                use thiserror::AsDynError;
                 #body
             }
         }
@@ -90,13 +145,13 @@ fn impl_struct(input: Struct) -> TokenStream {
     let display_body = if input.attrs.transparent.is_some() {
         let only_field = &input.fields[0].member;
         Some(quote! {
-            std::fmt::Display::fmt(&self.#only_field, __formatter)
+            ::core::fmt::Display::fmt(&self.#only_field, __formatter)
         })
     } else if let Some(display) = &input.attrs.display {
         let use_as_display = if display.has_bonus_display {
             Some(quote! {
                 #[allow(unused_imports)]
-                use thiserror::private::{DisplayAsDisplay, PathAsDisplay};
+                use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
             })
         } else {
             None
@@ -111,53 +166,165 @@ fn impl_struct(input: Struct) -> TokenStream {
     } else {
         None
     };
+    let display_where_clause = display_where_clause(
+        input.generics,
+        input.attrs.bound.as_ref(),
+        input.display_bounds(),
+    );
     let display_impl = display_body.map(|body| {
         quote! {
-            impl #impl_generics std::fmt::Display for #ty #ty_generics #where_clause {
-                fn fmt(&self, __formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            impl #impl_generics ::core::fmt::Display for #ty #ty_generics #display_where_clause {
+                #hint
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                     #body
                 }
             }
         }
     });
 
-    let from_impl = input.from_field().map(|from_field| {
+    let from_impls = input.from_field().map_or_else(Vec::new, |from_field| {
         let backtrace_field = input.backtrace_field();
-        let from = from_field.ty;
-        let body = from_initializer(from_field, backtrace_field);
+        let span_trace_field = input.span_trace_field();
+        let body = from_initializer(&input.fields, from_field, backtrace_field, span_trace_field);
+        let traced = traced(&input.attrs, None, true, quote!(#ty #body));
+        from_field
+            .from_types()
+            .into_iter()
+            .map(|from| {
+                quote! {
+                    impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
+                        #hint
+                        fn from(source: #from) -> Self {
+                            #traced
+                        }
+                    }
+                }
+            })
+            .collect()
+    });
+
+    let into_impl = input.attrs.into.as_ref().map(|into| {
+        let target = into.target.as_ref().unwrap();
+        let conversion = into_conversion(target, into.mapping.as_ref().unwrap());
         quote! {
-            impl #impl_generics std::convert::From<#from> for #ty #ty_generics #where_clause {
-                fn from(source: #from) -> Self {
-                    #ty #body
+            impl #impl_generics ::core::convert::From<#ty #ty_generics> for #target #where_clause {
+                #hint
+                fn from(error: #ty #ty_generics) -> Self {
+                    #conversion
                 }
             }
         }
     });
 
-    quote! {
-        impl #impl_generics std::error::Error for #ty #ty_generics #where_clause {
+    let error_where_clause = error_where_clause(
+        input.generics,
+        input.attrs.bound.as_ref(),
+        input.generic_sources(),
+    );
+
+    let methods = [
+        chain_methods(&input.attrs),
+        input.http_methods(),
+        input.code_methods(),
+        input.help_methods(),
+        input.jsonrpc_methods(),
+        input.exit_methods(),
+        input.retry_methods(),
+        input.errno_methods(),
+        input.hresult_methods(),
+        input.repr_code_methods(),
+        input.report_methods(),
+        input.sentry_methods(),
+        input.meta_methods(),
+        input.error_id_methods(),
+        input.severity_methods(),
+        input.log_methods(),
+        input.name_methods(),
+        input.describe_methods(),
+        input.catalog_const(),
+        input.source_ref_method(),
+    ];
+    let inherent_impl = inherent_impl(ty, input.generics, &methods);
+    let parse_code_impl = input.parse_code_impl();
+    let serialize_impl = input.serialize_impl();
+    let json_schema_impl = input.json_schema_impl();
+    let deserialize_impl = input.deserialize_impl();
+    let diagnostic_impl = input.diagnostic_impl();
+    let response_impls = input.response_impls();
+    let openapi_impls = input.openapi_impls();
+    let exit_status_impl = input.exit_status_impl();
+    let retryable_impl = input.retryable_impl();
+    let backtraced_impl = input.backtraced_impl();
+    let partial_eq_impl = input.partial_eq_impl();
+    let clone_impl = input.clone_impl();
+    let custom_trait_impl = input.custom_trait_impl();
+    let deref_impls = input.deref_impls();
+    let py_err_impl = input.py_err_impl();
+    let js_value_impl = input.js_value_impl();
+    let format_impl = input.format_impl();
+    let metrics_impl = input.metrics_impl();
+    let extern_fns = input.extern_fns();
+
+    let registration = catalog::registration(ty, input.generics, &input.descriptors());
+    let owned_twin = input.owned_twin()?;
+    let guard_macros = input.guard_macros();
+
+    Ok(quote! {
+        impl #impl_generics #error_trait for #ty #ty_generics #error_where_clause {
             #source_method
-            #backtrace_method
         }
         #display_impl
-        #from_impl
-    }
+        #(#from_impls)*
+        #into_impl
+        #parse_code_impl
+        #serialize_impl
+        #json_schema_impl
+        #deserialize_impl
+        #diagnostic_impl
+        #response_impls
+        #openapi_impls
+        #exit_status_impl
+        #retryable_impl
+        #backtraced_impl
+        #partial_eq_impl
+        #clone_impl
+        #custom_trait_impl
+        #deref_impls
+        #py_err_impl
+        #js_value_impl
+        #format_impl
+        #metrics_impl
+        #inherent_impl
+        #extern_fns
+        #registration
+        #owned_twin
+        #guard_macros
+    })
 }
 
-fn impl_enum(input: Enum) -> TokenStream {
+fn impl_enum(input: Enum) -> Result<TokenStream> {
     let ty = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let hint = method_hint(&input.attrs);
+    let error_trait = error_trait();
 
-    let source_method = if input.has_source() {
+    let source_method = if input.attrs.dispatch.is_some() && input.has_source() {
+        Some(input.dispatch_source_method())
+    } else if input.has_source() {
         let arms = input.variants.iter().map(|variant| {
             let ident = &variant.ident;
             if variant.attrs.transparent.is_some() {
-                let only_field = &variant.fields[0].member;
-                let source = quote!(std::error::Error::source(transparent.as_dyn_error()));
+                let only_field = &variant.fields[0];
+                let member = &only_field.member;
+                let source = if only_field.is_borrowed() {
+                    quote!(#error_trait::source(transparent))
+                } else {
+                    quote!(#error_trait::source(transparent.as_dyn_error()))
+                };
                 quote! {
-                    #ty::#ident {#only_field: transparent} => #source,
+                    #ty::#ident {#member: transparent} => #source,
                 }
-            } else if let Some(source_field) = variant.source_field() {
+            } else if let Some(source_field) = variant.static_source_field() {
                 let source = &source_field.member;
                 let asref = if type_is_option(source_field.ty) {
                     Some(quote_spanned!(source.span()=> .as_ref()?))
@@ -166,17 +333,17 @@ fn impl_enum(input: Enum) -> TokenStream {
                 };
                 let dyn_error = quote_spanned!(source.span()=> source #asref.as_dyn_error());
                 quote! {
-                    #ty::#ident {#source: source, ..} => std::option::Option::Some(#dyn_error),
+                    #ty::#ident {#source: source, ..} => ::core::option::Option::Some(#dyn_error),
                 }
             } else {
                 quote! {
-                    #ty::#ident {..} => std::option::Option::None,
+                    #ty::#ident {..} => ::core::option::Option::None,
                 }
             }
         });
         Some(quote! {
-            fn source(&self) -> std::option::Option<&(dyn std::error::Error + 'static)> {
-                use thiserror::private::AsDynError;
+            fn source(&self) -> ::core::option::Option<&(dyn #error_trait + 'static)> {
+                use thiserror::AsDynError;
                 match self {
                     #(#arms)*
                 }
@@ -186,72 +353,17 @@ fn impl_enum(input: Enum) -> TokenStream {
         None
     };
 
-    let backtrace_method = if input.has_backtrace() {
-        let arms = input.variants.iter().map(|variant| {
-            let ident = &variant.ident;
-            match (variant.backtrace_field(), variant.source_field()) {
-                (Some(backtrace_field), Some(source_field))
-                    if backtrace_field.attrs.backtrace.is_none() =>
-                {
-                    let backtrace = &backtrace_field.member;
-                    let source = &source_field.member;
-                    let source_backtrace = if type_is_option(source_field.ty) {
-                        quote_spanned! {source.span()=>
-                            source.as_ref().and_then(|source| source.as_dyn_error().backtrace())
-                        }
-                    } else {
-                        quote_spanned! {source.span()=>
-                            source.as_dyn_error().backtrace()
-                        }
-                    };
-                    let combinator = if type_is_option(backtrace_field.ty) {
-                        quote! {
-                            #source_backtrace.or(backtrace.as_ref())
-                        }
-                    } else {
-                        quote! {
-                            std::option::Option::Some(#source_backtrace.unwrap_or(backtrace))
-                        }
-                    };
-                    quote! {
-                        #ty::#ident {
-                            #backtrace: backtrace,
-                            #source: source,
-                            ..
-                        } => {
-                            use thiserror::private::AsDynError;
-                            #combinator
-                        }
-                    }
-                }
-                (Some(backtrace_field), _) => {
-                    let backtrace = &backtrace_field.member;
-                    let body = if type_is_option(backtrace_field.ty) {
-                        quote!(backtrace.as_ref())
-                    } else {
-                        quote!(std::option::Option::Some(backtrace))
-                    };
-                    quote! {
-                        #ty::#ident {#backtrace: backtrace, ..} => #body,
-                    }
-                }
-                (None, _) => quote! {
-                    #ty::#ident {..} => std::option::Option::None,
-                },
-            }
-        });
+    let display_impl = if input.attrs.dispatch.is_some() {
+        let body = input.dispatch_display_body();
         Some(quote! {
-            fn backtrace(&self) -> std::option::Option<&std::backtrace::Backtrace> {
-                match self {
-                    #(#arms)*
+            impl #impl_generics ::core::fmt::Display for #ty #ty_generics #where_clause {
+                #hint
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    #body
                 }
             }
         })
-    } else {
-        None
-    };
-
-    let display_impl = if input.has_display() {
+    } else if input.has_display() {
         let use_as_display = if input.variants.iter().any(|v| {
             v.attrs
                 .display
@@ -260,7 +372,7 @@ fn impl_enum(input: Enum) -> TokenStream {
         }) {
             Some(quote! {
                 #[allow(unused_imports)]
-                use thiserror::private::{DisplayAsDisplay, PathAsDisplay};
+                use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
             })
         } else {
             None
@@ -271,25 +383,22 @@ fn impl_enum(input: Enum) -> TokenStream {
             None
         };
         let arms = input.variants.iter().map(|variant| {
-            let display = match &variant.attrs.display {
-                Some(display) => display.to_token_stream(),
-                None => {
-                    let only_field = match &variant.fields[0].member {
-                        Member::Named(ident) => ident.clone(),
-                        Member::Unnamed(index) => format_ident!("_{}", index),
-                    };
-                    quote!(std::fmt::Display::fmt(#only_field, __formatter))
-                }
-            };
+            let display = variant_display(variant);
             let ident = &variant.ident;
             let pat = fields_pat(&variant.fields);
             quote! {
                 #ty::#ident #pat => #display
             }
         });
+        let display_where_clause = display_where_clause(
+            input.generics,
+            input.attrs.bound.as_ref(),
+            input.display_bounds(),
+        );
         Some(quote! {
-            impl #impl_generics std::fmt::Display for #ty #ty_generics #where_clause {
-                fn fmt(&self, __formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            impl #impl_generics ::core::fmt::Display for #ty #ty_generics #display_where_clause {
+                #hint
+                fn fmt(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
                     #use_as_display
                     #[allow(unused_variables)]
                     match #void_deref self {
@@ -302,32 +411,497 @@ fn impl_enum(input: Enum) -> TokenStream {
         None
     };
 
-    let from_impls = input.variants.iter().filter_map(|variant| {
-        let from_field = variant.from_field()?;
+    let from_impls = input.variants.iter().flat_map(|variant| {
+        let from_field = match variant.from_field() {
+            Some(from_field) => from_field,
+            None => return Vec::new(),
+        };
         let backtrace_field = variant.backtrace_field();
+        let span_trace_field = variant.span_trace_field();
+        let body = from_initializer(&variant.fields, from_field, backtrace_field, span_trace_field);
+        let ident = &variant.ident;
+        let traced = traced(
+            &input.attrs,
+            Some(&variant.attrs),
+            true,
+            quote!(#ty::#ident #body),
+        );
+        from_field
+            .from_types()
+            .into_iter()
+            .map(|from| {
+                quote! {
+                    impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
+                        #hint
+                        fn from(source: #from) -> Self {
+                            #traced
+                        }
+                    }
+                }
+            })
+            .collect()
+    });
+
+    let mut from_match_types = Vec::new();
+    for variant in &input.variants {
+        if let Some(from_match) = &variant.attrs.from_match {
+            let repr = from_match.ty.to_token_stream().to_string();
+            if !from_match_types.iter().any(|(seen, _)| *seen == repr) {
+                from_match_types.push((repr, &from_match.ty));
+            }
+        }
+    }
+    let from_match_impls = from_match_types.iter().map(|(repr, from)| {
+        let arms = input.variants.iter().filter_map(|variant| {
+            let from_match = variant.attrs.from_match.as_ref()?;
+            if from_match.ty.to_token_stream().to_string() != *repr {
+                return None;
+            }
+            let source_field = variant.source_field()?;
+            let backtrace_field = variant.backtrace_field();
+            let span_trace_field = variant.span_trace_field();
+            let body = from_initializer(
+                &variant.fields,
+                source_field,
+                backtrace_field,
+                span_trace_field,
+            );
+            let arm = &from_match.arm;
+            let ident = &variant.ident;
+            let traced = traced(
+                &input.attrs,
+                Some(&variant.attrs),
+                true,
+                quote!(#ty::#ident #body),
+            );
+            Some(quote! {
+                #arm => #traced,
+            })
+        });
+        // Errors matching none of the arms are boxed into the #[error(other)]
+        // variant, if there is one.
+        let other_arm = input.other_variant().map(|variant| {
+            let ident = &variant.ident;
+            let member = &variant.fields[0].member;
+            let traced = traced(
+                &input.attrs,
+                Some(&variant.attrs),
+                true,
+                quote!(#ty::#ident { #member: ::core::convert::From::from(source) }),
+            );
+            quote! {
+                #[allow(unreachable_patterns)]
+                _ => #traced,
+            }
+        });
+        quote! {
+            impl #impl_generics ::core::convert::From<#from> for #ty #ty_generics #where_clause {
+                #hint
+                fn from(source: #from) -> Self {
+                    match &source {
+                        #(#arms)*
+                        #other_arm
+                    }
+                }
+            }
+        }
+    });
+
+    let from_parts_impls = input.variants.iter().filter_map(|variant| {
+        variant.attrs.from_parts?;
+        let types = variant.fields.iter().map(|field| field.ty);
+        let parts = quote!((#(#types),*));
+        let members = variant.fields.iter().map(|field| &field.member);
+        let indices = (0..variant.fields.len()).map(Index::from);
+        let ident = &variant.ident;
+        let traced = traced(
+            &input.attrs,
+            Some(&variant.attrs),
+            variant.source_field().is_some(),
+            quote! {
+                #ty::#ident {
+                    #(#members: parts.#indices,)*
+                }
+            },
+        );
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<#parts> for #ty #ty_generics #where_clause {
+                #hint
+                fn from(parts: #parts) -> Self {
+                    #traced
+                }
+            }
+        })
+    });
+
+    let message_impls = input.message_variant().map(|variant| {
+        let member = &variant.fields[0].member;
+        let ident = &variant.ident;
+        let traced = traced(
+            &input.attrs,
+            Some(&variant.attrs),
+            false,
+            quote!(#ty::#ident { #member: message }),
+        );
+        quote! {
+            impl #impl_generics ::core::convert::From<thiserror::private::std::string::String>
+                for #ty #ty_generics #where_clause
+            {
+                #hint
+                fn from(message: thiserror::private::std::string::String) -> Self {
+                    #traced
+                }
+            }
+
+            impl #impl_generics ::core::convert::From<&str> for #ty #ty_generics #where_clause {
+                #hint
+                fn from(message: &str) -> Self {
+                    let message = thiserror::private::std::string::ToString::to_string(message);
+                    #traced
+                }
+            }
+        }
+    });
+
+    let try_from_impls = input.variants.iter().filter_map(|variant| {
+        variant.attrs.try_into?;
+        let field = variant.try_into_field()?;
+        let field_ty = field.ty;
+        let member = &field.member;
         let variant = &variant.ident;
-        let from = from_field.ty;
-        let body = from_initializer(from_field, backtrace_field);
         Some(quote! {
-            impl #impl_generics std::convert::From<#from> for #ty #ty_generics #where_clause {
-                fn from(source: #from) -> Self {
-                    #ty::#variant #body
+            impl #impl_generics ::core::convert::TryFrom<#ty #ty_generics> for #field_ty #where_clause {
+                type Error = #ty #ty_generics;
+
+                fn try_from(
+                    error: #ty #ty_generics,
+                ) -> ::core::result::Result<Self, #ty #ty_generics> {
+                    #[allow(unreachable_patterns)]
+                    match error {
+                        #ty::#variant { #member: field, .. } => ::core::result::Result::Ok(field),
+                        error => ::core::result::Result::Err(error),
+                    }
                 }
             }
         })
     });
 
-    quote! {
-        impl #impl_generics std::error::Error for #ty #ty_generics #where_clause {
+    let into_impl = input.attrs.into.as_ref().map(|into| {
+        let target = into.target.as_ref().unwrap();
+        let arms = input.variants.iter().map(|variant| {
+            let mapping = variant
+                .attrs
+                .into
+                .as_ref()
+                .and_then(|into| into.mapping.as_ref())
+                .or(into.mapping.as_ref())
+                .unwrap();
+            let conversion = into_conversion(target, mapping);
+            let ident = &variant.ident;
+            quote! {
+                #ty::#ident {..} => #conversion,
+            }
+        });
+        let void_deref = if input.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        quote! {
+            impl #impl_generics ::core::convert::From<#ty #ty_generics> for #target #where_clause {
+                #hint
+                fn from(error: #ty #ty_generics) -> Self {
+                    match #void_deref &error {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+    });
+
+    let error_where_clause = error_where_clause(
+        input.generics,
+        input.attrs.bound.as_ref(),
+        input.generic_sources(),
+    );
+
+    let methods = [
+        chain_methods(&input.attrs),
+        input.http_methods(),
+        input.code_methods(),
+        input.help_methods(),
+        input.jsonrpc_methods(),
+        input.exit_methods(),
+        input.retry_methods(),
+        input.errno_methods(),
+        input.hresult_methods(),
+        input.repr_code_methods(),
+        input.report_methods(),
+        input.sentry_methods(),
+        input.meta_methods(),
+        input.error_id_methods(),
+        input.severity_methods(),
+        input.log_methods(),
+        input.name_methods(),
+        input.describe_methods(),
+        input.catalog_const(),
+        input.source_ref_method(),
+        input.accessor_methods(),
+        input.kind_methods(),
+        input.constructor_methods(),
+        input.builder_methods(),
+        message_constructor(&input),
+        input.whatever_constructors(),
+        input.other_constructor(),
+        input.dispatch_method(),
+    ];
+    let inherent_impl = inherent_impl(ty, input.generics, &methods);
+    let parse_code_impl = input.parse_code_impl();
+    let serialize_impl = input.serialize_impl();
+    let json_schema_impl = input.json_schema_impl();
+    let deserialize_impl = input.deserialize_impl();
+    let diagnostic_impl = input.diagnostic_impl();
+    let response_impls = input.response_impls();
+    let openapi_impls = input.openapi_impls();
+    let errno_conversion_impl = input.errno_conversion_impl();
+    let hresult_conversion_impl = input.hresult_conversion_impl();
+    let downcast_conversion_impl = input.downcast_conversion_impl();
+    let exit_status_impl = input.exit_status_impl();
+    let retryable_impl = input.retryable_impl();
+    let backtraced_impl = input.backtraced_impl();
+    let partial_eq_impl = input.partial_eq_impl();
+    let clone_impl = input.clone_impl();
+    let custom_trait_impl = input.custom_trait_impl();
+    let py_err_impl = input.py_err_impl();
+    let js_value_impl = input.js_value_impl();
+    let format_impl = input.format_impl();
+    let metrics_impl = input.metrics_impl();
+    let extern_fns = input.extern_fns();
+
+    let registration = catalog::registration(ty, input.generics, &input.descriptors());
+    let owned_twin = input.owned_twin()?;
+    let guard_macros = input.guard_macros();
+    let whatever_macro = input.whatever_macro();
+    let kind_enum = input.kind_enum();
+    let builder_types = input.builder_types();
+    let context_trait = input.context_trait();
+
+    Ok(quote! {
+        impl #impl_generics #error_trait for #ty #ty_generics #error_where_clause {
             #source_method
-            #backtrace_method
         }
         #display_impl
         #(#from_impls)*
+        #(#from_match_impls)*
+        #(#from_parts_impls)*
+        #message_impls
+        #(#try_from_impls)*
+        #into_impl
+        #parse_code_impl
+        #serialize_impl
+        #json_schema_impl
+        #deserialize_impl
+        #diagnostic_impl
+        #response_impls
+        #openapi_impls
+        #errno_conversion_impl
+        #hresult_conversion_impl
+        #downcast_conversion_impl
+        #exit_status_impl
+        #retryable_impl
+        #backtraced_impl
+        #partial_eq_impl
+        #clone_impl
+        #custom_trait_impl
+        #py_err_impl
+        #js_value_impl
+        #format_impl
+        #metrics_impl
+        #inherent_impl
+        #extern_fns
+        #registration
+        #owned_twin
+        #guard_macros
+        #whatever_macro
+        #kind_enum
+        #builder_types
+        #context_trait
+    })
+}
+
+// The body formatting one variant into `__formatter`, given its fields bound
+// by `fields_pat`.
+pub(crate) fn variant_display(variant: &Variant) -> TokenStream {
+    match &variant.attrs.display {
+        Some(display) => display.to_token_stream(),
+        None => {
+            let field = variant
+                .whatever_message_field()
+                .unwrap_or(&variant.fields[0]);
+            let only_field = field_local(&field.member);
+            quote!(::core::fmt::Display::fmt(#only_field, __formatter))
+        }
+    }
+}
+
+// The name that `fields_pat` binds a field to.
+pub(crate) fn field_local(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index),
+    }
+}
+
+fn inherent_impl(
+    ty: &Ident,
+    generics: &Generics,
+    methods: &[Option<TokenStream>],
+) -> Option<TokenStream> {
+    if methods.iter().all(Option::is_none) {
+        return None;
     }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let methods = methods.iter().flatten();
+    Some(quote! {
+        impl #impl_generics #ty #ty_generics #where_clause {
+            #(#methods)*
+        }
+    })
 }
 
-fn fields_pat(fields: &[Field]) -> TokenStream {
+fn message_constructor(input: &Enum) -> Option<TokenStream> {
+    let variant = input.message_variant()?;
+    let ty = &input.ident;
+    let member = &variant.fields[0].member;
+    let ident = &variant.ident;
+    let traced = traced(
+        &input.attrs,
+        Some(&variant.attrs),
+        false,
+        quote!(#ty::#ident { #member: message }),
+    );
+    Some(quote! {
+        /// Creates the catch-all message variant from anything that can be
+        /// displayed.
+        pub fn msg(message: impl ::core::fmt::Display) -> Self {
+            let message = thiserror::private::std::string::ToString::to_string(&message);
+            #traced
+        }
+    })
+}
+
+fn chain_methods(attrs: &Attrs) -> Option<TokenStream> {
+    attrs.chain?;
+    let error_trait = error_trait();
+    Some(quote! {
+        /// Iterates over this error followed by each of its lower level
+        /// sources, as returned by `source()`.
+        pub fn chain(
+            &self,
+        ) -> impl ::core::iter::Iterator<Item = &(dyn #error_trait + 'static)> + '_
+        where
+            Self: 'static,
+        {
+            thiserror::private::Chain::new(self)
+        }
+
+        /// Returns the lowest level source of this error, which is the last
+        /// error in `chain()`. Returns `self` if there is no source.
+        pub fn root_cause(&self) -> &(dyn #error_trait + 'static)
+        where
+            Self: 'static,
+        {
+            let mut cause: &(dyn #error_trait + 'static) = self;
+            while let ::core::option::Option::Some(source) = cause.source() {
+                cause = source;
+            }
+            cause
+        }
+    })
+}
+
+// Error paths are cold by definition, but only the user knows whether keeping
+// them out of line or inlining the conversion suits their hot loops.
+fn method_hint(attrs: &Attrs) -> Option<TokenStream> {
+    if attrs.inline.is_some() {
+        Some(quote!(#[inline]))
+    } else if attrs.cold.is_some() {
+        Some(quote!(#[cold] #[inline(never)]))
+    } else {
+        None
+    }
+}
+
+// The Error trait has only been in core since Rust 1.81, so crates using std
+// keep naming it through std. Which one is re-exported is up to the features
+// of the runtime crate; the derived crate itself may be no_std either way.
+pub(crate) fn error_trait() -> TokenStream {
+    quote!(thiserror::private::Error)
+}
+
+// Source fields whose type is a bare type parameter need that parameter to be
+// an Error, which the definition itself is not required to spell out. Nor does
+// it need to bound the parameters for Debug and Display, which the Error impl
+// then asks of Self instead. #[error(bound = "...")] replaces the former.
+fn error_where_clause(
+    generics: &Generics,
+    bound: Option<&Bound>,
+    params: Vec<&Ident>,
+) -> Option<WhereClause> {
+    if generics.type_params().next().is_none() && bound.is_none() {
+        return generics.where_clause.clone();
+    }
+    let error_trait = error_trait();
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(parse_quote!(Self: ::core::fmt::Debug + ::core::fmt::Display));
+    match bound {
+        Some(bound) => where_clause.predicates.extend(bound.predicates.clone()),
+        None => {
+            for param in params {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#param: #error_trait + 'static));
+            }
+        }
+    }
+    generics.where_clause
+}
+
+// Fields whose type involves a type parameter need the formatting trait their
+// placeholder uses, as in `T: Display` for a `{0}` of type T, unless
+// #[error(bound = "...")] says otherwise.
+fn display_where_clause(
+    generics: &Generics,
+    bound: Option<&Bound>,
+    bounds: Vec<(&Type, Trait)>,
+) -> Option<WhereClause> {
+    if bounds.is_empty() && bound.is_none() {
+        return generics.where_clause.clone();
+    }
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    match bound {
+        Some(bound) => where_clause.predicates.extend(bound.predicates.clone()),
+        None => {
+            for (ty, bound) in bounds {
+                where_clause.predicates.push(parse_quote!(#ty: #bound));
+            }
+        }
+    }
+    generics.where_clause
+}
+
+fn into_conversion(target: &Type, mapping: &IntoMapping) -> TokenStream {
+    match mapping {
+        IntoMapping::Kind(kind) => quote!(<#target>::new(#kind, error)),
+        IntoMapping::With(with) => quote!(#with(error)),
+    }
+}
+
+pub(crate) fn fields_pat(fields: &[Field]) -> TokenStream {
     let mut members = fields.iter().map(|field| &field.member).peekable();
     match members.peek() {
         Some(Member::Named(_)) => quote!({ #(#members),* }),
@@ -342,27 +916,113 @@ fn fields_pat(fields: &[Field]) -> TokenStream {
     }
 }
 
-fn from_initializer(from_field: &Field, backtrace_field: Option<&Field>) -> TokenStream {
+fn from_initializer(
+    fields: &[Field],
+    from_field: &Field,
+    backtrace_field: Option<&Field>,
+    span_trace_field: Option<&Field>,
+) -> TokenStream {
     let from_member = &from_field.member;
     let backtrace = backtrace_field.map(|backtrace_field| {
         let backtrace_member = &backtrace_field.member;
-        if type_is_option(backtrace_field.ty) {
-            quote! {
-                #backtrace_member: std::option::Option::Some(std::backtrace::Backtrace::capture()),
-            }
+        // Initialized ahead of the source so that it can look at the source
+        // before it is moved, and skip capturing if the source has its own.
+        let source_backtrace = from_source_backtrace(from_field);
+        match (type_is_option(backtrace_field.ty), source_backtrace) {
+            (true, None) => quote! {
+                #backtrace_member: ::core::option::Option::Some(thiserror::private::std::backtrace::Backtrace::capture()),
+            },
+            (false, None) => quote! {
+                #backtrace_member: thiserror::private::std::backtrace::Backtrace::capture(),
+            },
+            (true, Some(source_backtrace)) => quote! {
+                #backtrace_member: if #source_backtrace.is_some() {
+                    ::core::option::Option::None
+                } else {
+                    ::core::option::Option::Some(thiserror::private::std::backtrace::Backtrace::capture())
+                },
+            },
+            (false, Some(source_backtrace)) => quote! {
+                #backtrace_member: if #source_backtrace.is_some() {
+                    thiserror::private::std::backtrace::Backtrace::disabled()
+                } else {
+                    thiserror::private::std::backtrace::Backtrace::capture()
+                },
+            },
+        }
+    });
+    let source = match &from_field.attrs.from {
+        Some(From {
+            with: Some(with), ..
+        }) => quote!(#with(source)),
+        Some(from) if !from.types.is_empty() => quote!(::core::convert::Into::into(source)),
+        Some(From {
+            wrap: Some(FromWrap::Arc),
+            ..
+        }) => quote!(thiserror::private::std::sync::Arc::new(source)),
+        Some(From {
+            wrap: Some(FromWrap::Box),
+            ..
+        }) => quote!(thiserror::private::std::boxed::Box::new(source)),
+        _ => quote!(source),
+    };
+    let span_trace = span_trace_field.and_then(|span_trace_field| {
+        let span_trace_member = &span_trace_field.member;
+        let span_trace = span_trace_field.span_trace_type()?;
+        if type_is_option(span_trace_field.ty) {
+            Some(quote! {
+                #span_trace_member: ::core::option::Option::Some(<#span_trace>::capture()),
+            })
         } else {
-            quote! {
-                #backtrace_member: std::backtrace::Backtrace::capture(),
-            }
+            Some(quote! {
+                #span_trace_member: <#span_trace>::capture(),
+            })
+        }
+    });
+    let defaults = fields.iter().filter_map(|field| {
+        if field.member == from_field.member
+            || Some(&field.member) == backtrace_field.map(|backtrace_field| &backtrace_field.member)
+            || Some(&field.member)
+                == span_trace_field.map(|span_trace_field| &span_trace_field.member)
+        {
+            return None;
+        }
+        let member = &field.member;
+        if field.attrs.error_id.is_some() {
+            let error_id = new_error_id();
+            return Some(quote! {
+                #member: #error_id,
+            });
         }
+        if field.attrs.timestamp.is_some() {
+            let now = timestamp::now(field);
+            return Some(quote! {
+                #member: #now,
+            });
+        }
+        Some(quote! {
+            #member: ::core::default::Default::default(),
+        })
     });
     quote!({
-        #from_member: source,
         #backtrace
+        #from_member: #source,
+        #span_trace
+        #(#defaults)*
     })
 }
 
-fn type_is_option(ty: &Type) -> bool {
+// The backtrace of the incoming source, if it is an error whose backtrace can
+// be asked for before conversion. Sources converted through `Into` or a `with`
+// function may not be errors themselves.
+fn from_source_backtrace(from_field: &Field) -> Option<TokenStream> {
+    match &from_field.attrs.from {
+        Some(from) if !from.types.is_empty() || from.with.is_some() => None,
+        _ => Some(source_backtrace(from_field, quote!(&source))),
+    }
+}
+
+pub(crate) fn type_is_option(ty: &Type) -> bool {
     let path = match ty {
         Type::Path(ty) => &ty.path,
         _ => return false,
@@ -0,0 +1,114 @@
+use crate::ast::{Enum, Struct};
+use crate::case::snake_case;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Struct<'_> {
+    pub(crate) fn repr_code_methods(&self) -> Option<TokenStream> {
+        let repr_code = self.attrs.repr_code.as_ref()?;
+        let code = repr_code.value.unwrap_or(1);
+        Some(method(quote!(#code)))
+    }
+
+    pub(crate) fn extern_fns(&self) -> Option<TokenStream> {
+        self.attrs.repr_code.as_ref()?;
+        Some(extern_fns(&self.ident))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn repr_code_methods(&self) -> Option<TokenStream> {
+        self.attrs.repr_code.as_ref()?;
+        let ty = &self.ident;
+        let arms = self
+            .variants
+            .iter()
+            .zip(self.repr_codes())
+            .map(|(variant, code)| {
+                let ident = &variant.ident;
+                quote!(#ty::#ident {..} => #code,)
+            });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+
+    pub(crate) fn extern_fns(&self) -> Option<TokenStream> {
+        self.attrs.repr_code.as_ref()?;
+        Some(extern_fns(&self.ident))
+    }
+
+    // Like the discriminants of a C enum: each variant without an explicit
+    // #[repr_code(...)] takes the code after that of the previous variant,
+    // starting from 1 so that 0 remains free to mean success.
+    pub(crate) fn repr_codes(&self) -> Vec<i32> {
+        let mut next = 1i32;
+        self.variants
+            .iter()
+            .map(|variant| {
+                let code = variant
+                    .attrs
+                    .repr_code
+                    .as_ref()
+                    .and_then(|repr_code| repr_code.value)
+                    .unwrap_or(next);
+                next = code.wrapping_add(1);
+                code
+            })
+            .collect()
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The stable numeric code with which this error is reported across
+        /// the C API.
+        pub fn repr_code(&self) -> i32 {
+            #body
+        }
+    }
+}
+
+fn extern_fns(ty: &Ident) -> TokenStream {
+    let prefix = snake_case(&ty.to_string());
+    let code_fn = format_ident!("{}_code", prefix);
+    let message_fn = format_ident!("{}_message", prefix);
+    let code_doc = format!("Returns the numeric code of a `{}`.", ty);
+    let message_doc = format!(
+        "Writes the message of a `{}` into `buf` as a NUL terminated string, \
+         truncated to fit in `len` bytes, and returns the length of the full \
+         message excluding the terminator.",
+        ty,
+    );
+    quote! {
+        #[doc = #code_doc]
+        #[no_mangle]
+        #[allow(improper_ctypes_definitions, deprecated)]
+        pub extern "C" fn #code_fn(error: &#ty) -> i32 {
+            #ty::repr_code(error)
+        }
+
+        #[doc = #message_doc]
+        ///
+        /// # Safety
+        ///
+        /// `buf` must be null or valid for writes of `len` bytes.
+        #[no_mangle]
+        #[allow(improper_ctypes_definitions, deprecated)]
+        pub unsafe extern "C" fn #message_fn(
+            error: &#ty,
+            buf: *mut thiserror::private::std::os::raw::c_char,
+            len: usize,
+        ) -> usize {
+            thiserror::private::write_c_message(&thiserror::private::std::string::ToString::to_string(error), buf, len)
+        }
+    }
+}
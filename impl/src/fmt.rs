@@ -1,26 +1,61 @@
 use crate::ast::Field;
-use crate::attr::Display;
-use proc_macro2::TokenTree;
-use quote::{format_ident, quote_spanned};
-use std::collections::HashSet as Set;
+use crate::attr::{Adapter, Diagnostic, Display, Reveal, Trait};
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::{HashMap as Map, HashSet as Set};
 use syn::ext::IdentExt;
 use syn::parse::{ParseStream, Parser};
-use syn::{Ident, Index, LitStr, Member, Result, Token};
+use syn::{Error, Ident, Index, LitStr, Member, Result, Token};
+
+impl Diagnostic<'_> {
+    pub fn expand_shorthand(&mut self, fields: &[Field]) -> Result<()> {
+        if let Some(help) = &mut self.help {
+            help.expand_shorthand(fields, false)?;
+        }
+        if let Some(url) = &mut self.url {
+            url.expand_shorthand(fields, false)?;
+        }
+        Ok(())
+    }
+}
 
 impl Display<'_> {
     // Transform `"error {var}"` to `"error {}", var`.
-    pub fn expand_shorthand(&mut self, fields: &[Field]) {
+    //
+    // With `sanitize`, fields are additionally wrapped so that control
+    // characters are escaped and overly long values are truncated, also where
+    // they are passed as explicit arguments. Fields marked #[sensitive] are
+    // redacted regardless, a std #[timestamp] is rendered as an RFC 3339 date
+    // and time, and fields with #[display(with = path)] are written by that
+    // function.
+    pub fn expand_shorthand(&mut self, fields: &[Field], sanitize: bool) -> Result<()> {
         let raw_args = self.args.clone();
         let mut named_args = explicit_named_args.parse2(raw_args).unwrap();
+        let sensitive: Map<Member, Reveal> = fields
+            .iter()
+            .filter_map(|f| Some((f.member.clone(), f.attrs.sensitive.as_ref()?.reveal)))
+            .collect();
+        let display_with: Map<Member, &Adapter> = fields
+            .iter()
+            .filter_map(|f| Some((f.member.clone(), &f.attrs.display_with.as_ref()?.adapter)))
+            .collect();
+        let timestamps: Set<Member> = fields
+            .iter()
+            .filter(|f| f.is_std_timestamp())
+            .map(|f| f.member.clone())
+            .collect();
         let fields: Set<Member> = fields.iter().map(|f| f.member.clone()).collect();
 
         let span = self.fmt.span();
         let fmt = self.fmt.value();
         let mut read = fmt.as_str();
         let mut out = String::new();
-        let mut args = self.args.clone();
+        let mut args = explicit_field_args(&self.args, &fields, &sensitive, sanitize)?;
         let mut has_bonus_display = false;
+        let mut implied_bounds = Set::new();
+        let explicit_named_args = named_args.clone();
 
+        let mut only_arg = None;
         let mut has_trailing_comma = false;
         if let Some(TokenTree::Punct(punct)) = args.clone().into_iter().last() {
             if punct.as_char() == ',' {
@@ -38,14 +73,14 @@ impl Display<'_> {
             }
             let next = match read.chars().next() {
                 Some(next) => next,
-                None => return,
+                None => return Ok(()),
             };
             let member = match next {
                 '0'..='9' => {
                     let int = take_int(&mut read);
                     let member = match int.parse::<u32>() {
                         Ok(index) => Member::Unnamed(Index { index, span }),
-                        Err(_) => return,
+                        Err(_) => return Ok(()),
                     };
                     if !fields.contains(&member) {
                         out += &int;
@@ -70,6 +105,16 @@ impl Display<'_> {
                 formatvar = format_ident!("field_{}", formatvar);
             }
             out += &formatvar.to_string();
+            let is_field = fields.contains(&member);
+            let reveal = sensitive.get(&member).filter(|_| is_field);
+            let with = display_with.get(&member).filter(|_| is_field);
+            if is_field
+                && !explicit_named_args.contains(&formatvar)
+                && !matches!(reveal, Some(Reveal::Nothing))
+                && with.is_none()
+            {
+                implied_bounds.insert((member.clone(), format_trait(read)));
+            }
             if !named_args.insert(formatvar.clone()) {
                 // Already specified in the format argument list.
                 continue;
@@ -78,17 +123,165 @@ impl Display<'_> {
                 args.extend(quote_spanned!(span=> ,));
                 has_trailing_comma = false;
             }
-            args.extend(quote_spanned!(span=> #formatvar = #local));
-            if read.starts_with('}') && fields.contains(&member) {
+            let mut arg = quote_spanned!(span=> #local);
+            if let Some(with) = with {
+                let with = match with {
+                    Adapter::With(path) => quote!(#path),
+                    Adapter::Humantime => quote!(thiserror::private::humantime),
+                };
+                arg = quote_spanned!(span=> thiserror::private::DisplayWith(#arg, #with));
+            } else if read.starts_with('}') && timestamps.contains(&member) {
+                arg = quote_spanned!(span=> thiserror::private::DisplayTimestamp(#arg));
+            } else if read.starts_with('}') && is_field && !matches!(reveal, Some(Reveal::Nothing))
+            {
                 has_bonus_display = true;
-                args.extend(quote_spanned!(span=> .as_display()));
+                arg = quote_spanned!(span=> #arg.as_display());
             }
+            if let Some(reveal) = reveal {
+                arg = redact(arg, *reveal, span);
+            }
+            if sanitize && is_field {
+                arg = quote_spanned!(span=> thiserror::private::Sanitized(#arg));
+            }
+            if self.args.is_empty() && out == format!("{{{}", formatvar) && read == "}" {
+                only_arg = Some(arg.clone());
+            }
+            args.extend(quote_spanned!(span=> #formatvar = #arg));
         }
 
         out += read;
         self.fmt = LitStr::new(&out, self.fmt.span());
         self.args = args;
         self.has_bonus_display = has_bonus_display;
+        self.implied_bounds = implied_bounds;
+        self.only_arg = only_arg;
+        Ok(())
+    }
+}
+
+fn redact(arg: TokenStream, reveal: Reveal, span: Span) -> TokenStream {
+    match reveal {
+        Reveal::Nothing => quote_spanned!(span=> thiserror::private::Redacted),
+        Reveal::Hash => quote_spanned!(span=> thiserror::private::RedactedHash(#arg)),
+        Reveal::Last4 => quote_spanned!(span=> thiserror::private::RedactedLast4(#arg)),
+    }
+}
+
+// Explicit arguments, as in `"token {}", .token`, that consist of a field by
+// itself are sanitized and redacted the same way as its `{token}` shorthand.
+// Any other expression involving a #[sensitive] field could give the value
+// away, so is rejected.
+fn explicit_field_args(
+    args: &TokenStream,
+    fields: &Set<Member>,
+    sensitive: &Map<Member, Reveal>,
+    sanitize: bool,
+) -> Result<TokenStream> {
+    if sensitive.is_empty() && !sanitize {
+        return Ok(args.clone());
+    }
+    let fields: Set<String> = fields.iter().map(local_name).collect();
+    let sensitive: Map<String, Reveal> = sensitive
+        .iter()
+        .map(|(member, reveal)| (local_name(member), *reveal))
+        .collect();
+    let mut expanded = TokenStream::new();
+    for (i, arg) in split_args(args).into_iter().enumerate() {
+        if i > 0 {
+            expanded.extend(quote!(,));
+        }
+        let value_start = match (arg.first(), arg.get(1)) {
+            (Some(TokenTree::Ident(_)), Some(TokenTree::Punct(punct)))
+                if punct.as_char() == '=' && punct.spacing() == Spacing::Alone =>
+            {
+                2
+            }
+            _ => 0,
+        };
+        let (name, value) = arg.split_at(value_start);
+        expanded.extend(name.iter().cloned());
+        if let [TokenTree::Ident(ident)] = value {
+            let local = ident.unraw().to_string();
+            if fields.contains(&local) {
+                let span = ident.span();
+                let mut arg = quote!(#ident);
+                if let Some(reveal) = sensitive.get(&local) {
+                    arg = redact(arg, *reveal, span);
+                }
+                if sanitize {
+                    arg = quote_spanned!(span=> thiserror::private::Sanitized(#arg));
+                }
+                expanded.extend(arg);
+                continue;
+            }
+        }
+        let value: TokenStream = value.iter().cloned().collect();
+        if let Some(ident) = find_ident(value.clone(), &sensitive) {
+            return Err(Error::new(
+                ident.span(),
+                "a #[sensitive] field can only be passed to the message by itself, as in `.field`, so that it stays redacted",
+            ));
+        }
+        expanded.extend(value);
+    }
+    Ok(expanded)
+}
+
+// The name a field is bound to while formatting, see `field_local`.
+fn local_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.unraw().to_string(),
+        Member::Unnamed(index) => format!("_{}", index.index),
+    }
+}
+
+// Splits the tokens following the format string at its top level commas. The
+// leading comma gives an empty first argument.
+pub(crate) fn split_args(args: &TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut split = vec![Vec::new()];
+    for token in args.clone() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => split.push(Vec::new()),
+            _ => split.last_mut().unwrap().push(token),
+        }
+    }
+    split
+}
+
+fn find_ident(tokens: TokenStream, names: &Map<String, Reveal>) -> Option<Ident> {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) if names.contains_key(&ident.unraw().to_string()) => {
+                return Some(ident);
+            }
+            TokenTree::Group(group) => {
+                if let Some(ident) = find_ident(group.stream(), names) {
+                    return Some(ident);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// The trait a placeholder formats with, given the format string following the
+// placeholder's argument, as in `:>8x}` for `{field:>8x}`.
+fn format_trait(read: &str) -> Trait {
+    let spec = match read.find('}') {
+        Some(end) if read.starts_with(':') => &read[1..end],
+        _ => return Trait::Display,
+    };
+    match spec.chars().last() {
+        Some('?') => Trait::Debug,
+        Some('o') => Trait::Octal,
+        Some('x') => Trait::LowerHex,
+        Some('X') => Trait::UpperHex,
+        Some('p') => Trait::Pointer,
+        Some('b') => Trait::Binary,
+        Some('e') => Trait::LowerExp,
+        Some('E') => Trait::UpperExp,
+        _ => Trait::Display,
     }
 }
 
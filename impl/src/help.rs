@@ -0,0 +1,81 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Display;
+use crate::expand::fields_pat;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn help_methods(&self) -> Option<TokenStream> {
+        let help = self.attrs.help.as_ref()?;
+        let pat = fields_pat(&self.fields);
+        let format = format(help);
+        Some(method(quote! {
+            #[allow(unused_variables)]
+            let Self #pat = self;
+            #format
+        }))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn help_methods(&self) -> Option<TokenStream> {
+        if self
+            .variants
+            .iter()
+            .all(|variant| variant.attrs.help.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.attrs.help {
+                Some(help) => {
+                    let pat = fields_pat(&variant.fields);
+                    let format = format(help);
+                    quote! {
+                        #[allow(unused_variables)]
+                        #ty::#ident #pat => { #format }
+                    }
+                }
+                None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+fn format(help: &Display) -> TokenStream {
+    let use_as_display = if help.has_bonus_display {
+        Some(quote! {
+            #[allow(unused_imports)]
+            use thiserror::private::{DisplayAsDisplay, LossyAsDisplay, PathAsDisplay};
+        })
+    } else {
+        None
+    };
+    let fmt = &help.fmt;
+    let args = &help.args;
+    quote! {
+        #use_as_display
+        ::core::option::Option::Some(thiserror::private::std::format!(#fmt #args))
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// A hint for resolving this error, to show under the message.
+        pub fn help(&self) -> ::core::option::Option<thiserror::private::std::string::String> {
+            #body
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::ast::{Enum, Struct, Variant};
+use crate::attr::{Attrs, HResultValue};
+use crate::code::default_initializer;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn hresult_methods(&self) -> Option<TokenStream> {
+        let value = self.attrs.hresult.as_ref()?.value.as_ref()?;
+        Some(method(hresult(value)))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn hresult_methods(&self) -> Option<TokenStream> {
+        if self.attrs.hresult.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.hresult.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match variant_hresult(&self.attrs, &variant.attrs) {
+                Some(value) => quote!(#ty::#ident {..} => #value,),
+                None => {
+                    let member = &variant.fields[0].member;
+                    quote!(#ty::#ident { #member: __error } => __error.code().0,)
+                }
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+
+    pub(crate) fn hresult_conversion_impl(&self) -> Option<TokenStream> {
+        let other = self.hresult_other_variant()?;
+        let ty = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let known = self.variants.iter().filter_map(|variant| {
+            let value = variant.attrs.hresult.as_ref()?.value.as_ref()?;
+            let value = hresult(value);
+            let ident = &variant.ident;
+            let init = default_initializer(&variant.fields);
+            let traced = traced(
+                &self.attrs,
+                Some(&variant.attrs),
+                false,
+                quote!(#ty::#ident #init),
+            );
+            Some(quote! {
+                if code == #value {
+                    #traced
+                } else
+            })
+        });
+        let ident = &other.ident;
+        let member = &other.fields[0].member;
+        let other = traced(
+            &self.attrs,
+            Some(&other.attrs),
+            false,
+            quote!(#ty::#ident { #member: error }),
+        );
+        Some(quote! {
+            impl #impl_generics ::core::convert::From<windows::core::Error> for #ty #ty_generics #where_clause {
+                fn from(error: windows::core::Error) -> Self {
+                    let code = error.code().0;
+                    #(#known)* {
+                        #other
+                    }
+                }
+            }
+        })
+    }
+
+    pub(crate) fn hresult_other_variant(&self) -> Option<&Variant<'_>> {
+        self.variants.iter().find(
+            |variant| matches!(&variant.attrs.hresult, Some(hresult) if hresult.value.is_none()),
+        )
+    }
+}
+
+// The variant's HRESULT, else the enum's, else E_FAIL. None for the
+// #[hresult(other)] variant, which reports the code of the error it holds.
+fn variant_hresult(container: &Attrs, variant: &Attrs) -> Option<TokenStream> {
+    match &variant.hresult {
+        Some(attr) => attr.value.as_ref().map(hresult),
+        None => match container
+            .hresult
+            .as_ref()
+            .and_then(|attr| attr.value.as_ref())
+        {
+            Some(value) => Some(hresult(value)),
+            None => {
+                let e_fail = 0x8000_4005u32 as i32;
+                Some(quote!(#e_fail))
+            }
+        },
+    }
+}
+
+// HRESULTs are written as their unsigned bit pattern, as in 0x8007_0005, but
+// reported as i32 like windows::core::HRESULT.
+fn hresult(value: &HResultValue) -> TokenStream {
+    match value {
+        HResultValue::Code(code) => {
+            let value = code.base10_parse::<u32>().unwrap() as i32;
+            quote!(#value)
+        }
+        HResultValue::Const(path) => quote!(#path.0),
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The HRESULT with which this error is reported to COM callers.
+        pub fn hresult(&self) -> i32 {
+            #body
+        }
+    }
+}
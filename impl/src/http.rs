@@ -0,0 +1,231 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::{Attrs, Response, Status};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn http_methods(&self) -> Option<TokenStream> {
+        if !has_http_attrs(&self.attrs) {
+            return None;
+        }
+
+        let status = status(&self.attrs, None);
+        let public_message = public_message(&self.attrs, None, &status);
+        Some(methods(quote!(#status), quote!(#public_message)))
+    }
+
+    pub(crate) fn response_impls(&self) -> TokenStream {
+        let code = code(&self.attrs);
+        response_impls(&self.attrs, &self.ident, self.generics, code)
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn http_methods(&self) -> Option<TokenStream> {
+        if !has_http_attrs(&self.attrs)
+            && !self
+                .variants
+                .iter()
+                .any(|variant| has_http_attrs(&variant.attrs))
+        {
+            return None;
+        }
+
+        let ty = &self.ident;
+        let mut status_arms = Vec::new();
+        let mut message_arms = Vec::new();
+        for variant in &self.variants {
+            let ident = &variant.ident;
+            let status = status(&self.attrs, Some(&variant.attrs));
+            let public_message = public_message(&self.attrs, Some(&variant.attrs), &status);
+            status_arms.push(quote!(#ty::#ident {..} => #status,));
+            message_arms.push(quote!(#ty::#ident {..} => #public_message,));
+        }
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(methods(
+            quote! {
+                match #void_deref self {
+                    #(#status_arms)*
+                }
+            },
+            quote! {
+                match #void_deref self {
+                    #(#message_arms)*
+                }
+            },
+        ))
+    }
+
+    pub(crate) fn response_impls(&self) -> TokenStream {
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let code = code(&variant.attrs);
+            quote!(#ty::#ident {..} => #code,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        let code = quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        };
+        response_impls(&self.attrs, ty, self.generics, code)
+    }
+}
+
+fn has_http_attrs(attrs: &Attrs) -> bool {
+    attrs.status.is_some()
+        || attrs.expose.is_some()
+        || attrs.into_response.is_some()
+        || attrs.response_error.is_some()
+}
+
+fn code(attrs: &Attrs) -> TokenStream {
+    match &attrs.code {
+        Some(code) => quote!(::core::option::Option::Some(#code)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+pub(crate) fn status(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let status = variant
+        .and_then(|variant| variant.status.as_ref())
+        .or(container.status.as_ref());
+    match status {
+        Some(Status::Code(status)) => {
+            let value = status.base10_parse::<u16>().unwrap();
+            quote!(#value)
+        }
+        Some(Status::Const(status)) => quote!(#status.as_u16()),
+        None => quote!(500u16),
+    }
+}
+
+pub(crate) fn is_exposed(container: &Attrs, variant: Option<&Attrs>) -> bool {
+    let expose = variant
+        .and_then(|variant| variant.expose.as_ref())
+        .or(container.expose.as_ref());
+    match expose {
+        Some(expose) => expose.value,
+        None => true,
+    }
+}
+
+fn public_message(container: &Attrs, variant: Option<&Attrs>, status: &TokenStream) -> TokenStream {
+    if is_exposed(container, variant) {
+        quote!(thiserror::private::std::string::ToString::to_string(self))
+    } else {
+        quote!(thiserror::private::std::string::String::from(thiserror::private::canonical_reason(#status)))
+    }
+}
+
+fn methods(status_body: TokenStream, public_message_body: TokenStream) -> TokenStream {
+    quote! {
+        /// The HTTP status code with which this error is reported.
+        pub fn status_code(&self) -> u16 {
+            #status_body
+        }
+
+        /// The message that is safe to show to HTTP clients. Variants marked
+        /// `expose = false` render the canonical reason phrase of their status
+        /// instead of their `Display` message.
+        pub fn public_message(&self) -> thiserror::private::std::string::String {
+            #public_message_body
+        }
+    }
+}
+
+fn response_impls(
+    attrs: &Attrs,
+    ty: &Ident,
+    generics: &Generics,
+    code: TokenStream,
+) -> TokenStream {
+    let into_response_impl = attrs
+        .into_response
+        .as_ref()
+        .map(|response| into_response_impl(ty, generics, response, &code));
+    let response_error_impl = attrs
+        .response_error
+        .as_ref()
+        .map(|response| response_error_impl(ty, generics, response, &code));
+    quote! {
+        #into_response_impl
+        #response_error_impl
+    }
+}
+
+fn into_response_impl(
+    ty: &Ident,
+    generics: &Generics,
+    response: &Response,
+    code: &TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = if response.json.is_some() {
+        quote! {
+            axum::Json(thiserror::private::ResponseBody {
+                code: #code,
+                message: self.public_message(),
+            })
+        }
+    } else {
+        quote!(self.public_message())
+    };
+    quote! {
+        impl #impl_generics axum::response::IntoResponse for #ty #ty_generics #where_clause {
+            fn into_response(self) -> axum::response::Response {
+                let status = axum::http::StatusCode::from_u16(self.status_code())
+                    .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+                axum::response::IntoResponse::into_response((status, #body))
+            }
+        }
+    }
+}
+
+fn response_error_impl(
+    ty: &Ident,
+    generics: &Generics,
+    response: &Response,
+    code: &TokenStream,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = if let Some(with) = &response.with {
+        quote!(#with(self, &mut __builder))
+    } else if response.json.is_some() {
+        quote! {
+            __builder.json(thiserror::private::ResponseBody {
+                code: #code,
+                message: self.public_message(),
+            })
+        }
+    } else {
+        quote!(__builder.body(self.public_message()))
+    };
+    // The inherent status_code takes precedence over the trait method of the
+    // same name inside the impl.
+    quote! {
+        impl #impl_generics actix_web::ResponseError for #ty #ty_generics #where_clause {
+            fn status_code(&self) -> actix_web::http::StatusCode {
+                actix_web::http::StatusCode::from_u16(Self::status_code(self))
+                    .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+
+            fn error_response(&self) -> actix_web::HttpResponse {
+                let mut __builder = actix_web::HttpResponse::build(
+                    actix_web::ResponseError::status_code(self),
+                );
+                #body
+            }
+        }
+    }
+}
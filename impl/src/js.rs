@@ -0,0 +1,69 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn js_value_impl(&self) -> Option<TokenStream> {
+        self.attrs.js_value?;
+        let code = self
+            .attrs
+            .code
+            .as_ref()
+            .map(|code| quote!(::core::option::Option::Some(#code)));
+        Some(js_value_impl(&self.ident, self.generics, code))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn js_value_impl(&self) -> Option<TokenStream> {
+        self.attrs.js_value?;
+        let ty = &self.ident;
+        let code = if self
+            .variants
+            .iter()
+            .any(|variant| variant.attrs.code.is_some())
+        {
+            let arms = self.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                match &variant.attrs.code {
+                    Some(code) => quote!(#ty::#ident {..} => ::core::option::Option::Some(#code),),
+                    None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+                }
+            });
+            Some(quote! {
+                match &error {
+                    #(#arms)*
+                }
+            })
+        } else {
+            None
+        };
+        Some(js_value_impl(ty, self.generics, code))
+    }
+}
+
+fn js_value_impl(ty: &Ident, generics: &Generics, code: Option<TokenStream>) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let set_code = code.map(|code| {
+        quote! {
+            let code: ::core::option::Option<&'static str> = #code;
+            if let ::core::option::Option::Some(code) = code {
+                let _ = js_sys::Reflect::set(
+                    &js_error,
+                    &wasm_bindgen::JsValue::from_str("code"),
+                    &wasm_bindgen::JsValue::from_str(code),
+                );
+            }
+        }
+    });
+    quote! {
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for wasm_bindgen::JsValue #where_clause {
+            fn from(error: #ty #ty_generics) -> Self {
+                let js_error = js_sys::Error::new(&thiserror::private::std::string::ToString::to_string(&error));
+                #set_code
+                wasm_bindgen::JsValue::from(js_error)
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::JsonRpc;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn jsonrpc_methods(&self) -> Option<TokenStream> {
+        let jsonrpc = self.attrs.jsonrpc.as_ref()?;
+        let code = code(Some(jsonrpc), None);
+        let data = match &jsonrpc.data {
+            Some(member) => data(quote!(&self.#member)),
+            None => quote!(::core::option::Option::None),
+        };
+        Some(method(quote!((#code, #data))))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn jsonrpc_methods(&self) -> Option<TokenStream> {
+        if self.attrs.jsonrpc.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.jsonrpc.is_none())
+        {
+            return None;
+        }
+
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let jsonrpc = variant.attrs.jsonrpc.as_ref();
+            let code = code(self.attrs.jsonrpc.as_ref(), jsonrpc);
+            match jsonrpc.and_then(|jsonrpc| jsonrpc.data.as_ref()) {
+                Some(member) => {
+                    let data = data(quote!(__data));
+                    quote!(#ty::#ident { #member: __data, .. } => (#code, #data),)
+                }
+                None => quote!(#ty::#ident {..} => (#code, ::core::option::Option::None),),
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// The variant's code, else the container's, else -32603 which JSON-RPC 2.0
+// reserves for internal errors.
+fn code(container: Option<&JsonRpc>, variant: Option<&JsonRpc>) -> TokenStream {
+    let code = variant
+        .and_then(|variant| variant.code.as_ref())
+        .or_else(|| container.and_then(|container| container.code.as_ref()));
+    match code {
+        Some(code) => {
+            let value = code.base10_parse::<i64>().unwrap();
+            quote!(#value)
+        }
+        None => quote!(-32603i64),
+    }
+}
+
+fn data(value: TokenStream) -> TokenStream {
+    quote!(thiserror::private::serde_json::to_value(#value).ok())
+}
+
+fn method(code_and_data: TokenStream) -> TokenStream {
+    quote! {
+        /// The JSON-RPC 2.0 error object reporting this error, with the
+        /// `Display` message as its message.
+        pub fn to_jsonrpc_error(&self) -> thiserror::JsonRpcError {
+            let (code, data) = #code_and_data;
+            thiserror::JsonRpcError {
+                code,
+                message: thiserror::private::std::string::ToString::to_string(self),
+                data,
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::ast::Enum;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Enum<'_> {
+    pub(crate) fn kind_methods(&self) -> Option<TokenStream> {
+        self.attrs.kind?;
+        let ty = &self.ident;
+        let kind = kind_ident(ty);
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            quote!(#ty::#ident {..} => #kind::#ident,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(quote! {
+            /// The kind of this error, without the data it carries.
+            pub fn kind(&self) -> #kind {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            }
+        })
+    }
+
+    // One fieldless variant per variant of the error, so kinds can be compared
+    // and used as map keys without cloning payloads.
+    pub(crate) fn kind_enum(&self) -> Option<TokenStream> {
+        self.attrs.kind?;
+        let ty = &self.ident;
+        let kind = kind_ident(ty);
+        let vis = &self.original.vis;
+        let variants = self.variants.iter().map(|variant| {
+            let docs = variant
+                .original
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("doc"));
+            let ident = &variant.ident;
+            quote! {
+                #(#docs)*
+                #ident
+            }
+        });
+        // Downstream crates must not match exhaustively on the kinds of an
+        // error they cannot match exhaustively on either.
+        let non_exhaustive = self
+            .original
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("non_exhaustive"));
+        let doc = format!("Fieldless counterpart of [`{}`].", ty);
+        Some(quote! {
+            #[doc = #doc]
+            #(#non_exhaustive)*
+            #[derive(
+                ::core::fmt::Debug,
+                ::core::clone::Clone,
+                ::core::marker::Copy,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::hash::Hash,
+            )]
+            #vis enum #kind {
+                #(#variants,)*
+            }
+        })
+    }
+}
+
+fn kind_ident(ident: &Ident) -> Ident {
+    format_ident!("{}Kind", ident)
+}
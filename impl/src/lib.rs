@@ -1,18 +1,74 @@
-#![allow(clippy::block_in_if_condition_stmt, clippy::range_plus_one)]
+#![allow(
+    clippy::block_in_if_condition_stmt,
+    clippy::range_plus_one,
+    clippy::wrong_self_convention
+)]
 
 extern crate proc_macro;
 
+mod accessors;
 mod ast;
 mod attr;
+mod backtrace;
+mod builder;
+mod case;
+mod catalog;
+mod clone;
+mod code;
+mod constructors;
+mod context;
+mod custom;
+mod defmt;
+mod deref;
+mod describe;
+mod diagnostic;
+mod dispatch;
+mod downcast;
+mod errno;
+mod error_id;
+mod exit;
 mod expand;
+mod ffi;
 mod fmt;
+mod help;
+mod hresult;
+mod http;
+mod js;
+mod jsonrpc;
+mod kind;
+mod macros;
+mod meta;
+mod metrics;
+mod name;
+mod openapi;
+mod other;
+mod owned;
+mod partial_eq;
 mod prop;
+mod py;
+mod report;
+mod retry;
+mod schema;
+mod sentry;
+mod serialize;
+mod severity;
+mod source_ref;
+mod timestamp;
+mod trace;
 mod valid;
+mod whatever;
 
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(Error, attributes(backtrace, error, from, source))]
+#[proc_macro_derive(
+    Error,
+    attributes(
+        backtrace, code, diagnostic, display, errno, error, error_id, exit_code, from, from_match,
+        from_parts, help, hresult, into, jsonrpc, level, meta, py, repr_code, retryable, sensitive,
+        sentry, severity, source, status, timestamp, try_into, whatever
+    )
+)]
 pub fn derive_error(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     expand::derive(&input)
@@ -0,0 +1,46 @@
+use crate::ast::{Enum, Struct};
+use crate::case::snake_case;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+impl Struct<'_> {
+    pub(crate) fn guard_macros(&self) -> Option<TokenStream> {
+        self.attrs.macros?;
+        let ty = &self.ident;
+        Some(guard_macros(ty, quote!(#ty)))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn guard_macros(&self) -> Option<TokenStream> {
+        self.attrs.macros?;
+        let ty = &self.ident;
+        Some(guard_macros(ty, quote!(#ty::)))
+    }
+}
+
+// The macros are not exported, so they are in scope after the type in the
+// same module and its children, the same as any macro_rules macro.
+fn guard_macros(ty: &Ident, prefix: TokenStream) -> TokenStream {
+    let snake = snake_case(&ty.to_string());
+    let bail = format_ident!("{}_bail", snake);
+    let ensure = format_ident!("{}_ensure", snake);
+    quote! {
+        #[allow(unused_macros)]
+        macro_rules! #bail {
+            ($($error:tt)*) => {
+                return ::core::result::Result::Err(::core::convert::From::from(#prefix $($error)*))
+            };
+        }
+
+        #[allow(unused_macros)]
+        macro_rules! #ensure {
+            ($cond:expr, $($error:tt)*) => {
+                if !$cond {
+                    return ::core::result::Result::Err(::core::convert::From::from(#prefix $($error)*));
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::{Attrs, MetaValue, Reveal};
+use crate::expand::field_local;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{LitStr, Member};
+
+impl Struct<'_> {
+    pub(crate) fn meta_methods(&self) -> Option<TokenStream> {
+        let meta = self.attrs.meta.as_ref()?;
+        let pushes = meta.entries.iter().map(|(key, value)| {
+            let value = value_expr(value, &self.fields, |member| quote!(&self.#member));
+            quote!(__metadata.push((#key, #value));)
+        });
+        Some(method(quote! {
+            #(#pushes)*
+        }))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn meta_methods(&self) -> Option<TokenStream> {
+        if self.attrs.meta.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.meta.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let entries = entries(&self.attrs, &variant.attrs);
+            let mut members = Vec::new();
+            for (_, value) in &entries {
+                if let MetaValue::Field(member) = value {
+                    if !members.contains(&member) && !fully_redacted(&variant.fields, member) {
+                        members.push(member);
+                    }
+                }
+            }
+            let bindings = members.iter().map(|member| match member {
+                Member::Named(ident) => quote!(#ident),
+                Member::Unnamed(_) => {
+                    let local = field_local(member);
+                    quote!(#member: #local)
+                }
+            });
+            let pushes = entries.iter().map(|(key, value)| {
+                let value = value_expr(value, &variant.fields, |member| {
+                    let local = field_local(member);
+                    quote!(#local)
+                });
+                quote!(__metadata.push((#key, #value));)
+            });
+            quote! {
+                #ty::#ident { #(#bindings,)* .. } => {
+                    #(#pushes)*
+                }
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// The enum's entries together with the variant's, which take precedence.
+fn entries<'a>(container: &'a Attrs, variant: &'a Attrs) -> Vec<&'a (LitStr, MetaValue)> {
+    let variant_entries = variant.meta.as_ref().map_or(&[][..], |meta| &meta.entries);
+    let container_entries = container
+        .meta
+        .as_ref()
+        .map_or(&[][..], |meta| &meta.entries)
+        .iter()
+        .filter(|(key, _)| {
+            !variant_entries
+                .iter()
+                .any(|(variant_key, _)| variant_key.value() == key.value())
+        });
+    container_entries.chain(variant_entries).collect()
+}
+
+// A field of the error is rendered with Display, and stays redacted if it is
+// #[sensitive].
+fn value_expr(
+    value: &MetaValue,
+    fields: &[Field],
+    access: impl Fn(&Member) -> TokenStream,
+) -> TokenStream {
+    let member = match value {
+        MetaValue::Lit(lit) => {
+            return quote!(thiserror::private::std::string::ToString::to_string(&#lit))
+        }
+        MetaValue::Field(member) => member,
+    };
+    let access = access(member);
+    let value = match reveal(fields, member) {
+        None => access,
+        Some(Reveal::Nothing) => quote!(thiserror::private::Redacted),
+        Some(Reveal::Hash) => quote!(thiserror::private::RedactedHash(#access)),
+        Some(Reveal::Last4) => quote!(thiserror::private::RedactedLast4(#access)),
+    };
+    quote!(thiserror::private::std::string::ToString::to_string(&#value))
+}
+
+fn reveal(fields: &[Field], member: &Member) -> Option<Reveal> {
+    let field = fields.iter().find(|field| field.member == *member).unwrap();
+    field
+        .attrs
+        .sensitive
+        .as_ref()
+        .map(|sensitive| sensitive.reveal)
+}
+
+// A field shown as a bare `<redacted>` is never read, so is not bound.
+fn fully_redacted(fields: &[Field], member: &Member) -> bool {
+    matches!(reveal(fields, member), Some(Reveal::Nothing))
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The key-value context attached to this error by `#[meta(...)]`.
+        pub fn metadata(
+            &self,
+        ) -> impl ::core::iter::Iterator<Item = (&'static str, thiserror::private::std::string::String)> {
+            #[allow(unused_mut)]
+            let mut __metadata: thiserror::private::std::vec::Vec<(&'static str, thiserror::private::std::string::String)> =
+                thiserror::private::std::vec::Vec::new();
+            #body
+            __metadata.into_iter()
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{Generics, Ident};
+
+// With #[error(metrics)], an impl of thiserror's hidden Counted trait
+// incrementing the `errors_total` counter of the metrics crate, labeled with
+// the name of the type and, for enums, of the variant. Generated From impls
+// and constructors call it on every error they create.
+impl Struct<'_> {
+    pub(crate) fn metrics_impl(&self) -> Option<TokenStream> {
+        self.attrs.metrics?;
+        let ty = self.ident.unraw().to_string();
+        Some(counted_impl(
+            &self.ident,
+            self.generics,
+            quote! {
+                metrics::counter!("errors_total", "type" => #ty).increment(1);
+            },
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn metrics_impl(&self) -> Option<TokenStream> {
+        self.attrs.metrics?;
+        let ty = &self.ident;
+        let type_name = ty.unraw().to_string();
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = ident.unraw().to_string();
+            quote!(#ty::#ident {..} => #name,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(counted_impl(
+            ty,
+            self.generics,
+            quote! {
+                let variant = match #void_deref self {
+                    #(#arms)*
+                };
+                metrics::counter!("errors_total", "type" => #type_name, "variant" => variant).increment(1);
+            },
+        ))
+    }
+}
+
+fn counted_impl(ty: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics thiserror::private::Counted for #ty #ty_generics #where_clause {
+            fn count(&self) {
+                #body
+            }
+        }
+    }
+}
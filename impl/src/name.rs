@@ -0,0 +1,44 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+impl Struct<'_> {
+    pub(crate) fn name_methods(&self) -> Option<TokenStream> {
+        self.attrs.name?;
+        let name = self.ident.unraw().to_string();
+        Some(method(quote!(#name)))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn name_methods(&self) -> Option<TokenStream> {
+        self.attrs.name?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = ident.unraw().to_string();
+            quote!(#ty::#ident {..} => #name,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// The name of the variant, or of the type for a struct, as written
+        /// in the source.
+        pub fn name(&self) -> &'static str {
+            #body
+        }
+    }
+}
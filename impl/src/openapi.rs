@@ -0,0 +1,147 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use crate::http::{is_exposed, status};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn openapi_impls(&self) -> Option<TokenStream> {
+        self.attrs.into_responses?;
+        let response = response(&self.attrs, None, &self.ident);
+        Some(openapi_impls(
+            &self.attrs,
+            &self.ident,
+            self.generics,
+            vec![response],
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn openapi_impls(&self) -> Option<TokenStream> {
+        self.attrs.into_responses?;
+        let responses = self
+            .variants
+            .iter()
+            .map(|variant| response(&self.attrs, Some(&variant.attrs), &variant.ident))
+            .collect();
+        Some(openapi_impls(
+            &self.attrs,
+            &self.ident,
+            self.generics,
+            responses,
+        ))
+    }
+}
+
+// The status of a struct or variant along with the description of its
+// response: the message template as written, or for errors whose message is
+// not exposed, the reason phrase that clients receive instead.
+fn response(container: &Attrs, variant: Option<&Attrs>, ident: &Ident) -> TokenStream {
+    let attrs = variant.unwrap_or(container);
+    let status = status(container, variant);
+    let description = if !is_exposed(container, variant) {
+        quote!(thiserror::private::canonical_reason(#status))
+    } else if let Some(display) = &attrs.display {
+        let template = &display.template;
+        quote!(#template)
+    } else {
+        let name = ident.to_string();
+        quote!(#name)
+    };
+    quote!((#status, #description))
+}
+
+fn openapi_impls(
+    attrs: &Attrs,
+    ty: &Ident,
+    generics: &Generics,
+    responses: Vec<TokenStream>,
+) -> TokenStream {
+    let name = ty.to_string();
+    // The body written by the IntoResponse or ResponseError impls: a JSON
+    // object with the `json` option, otherwise the message as plain text.
+    let json = attrs
+        .into_response
+        .iter()
+        .chain(&attrs.response_error)
+        .any(|response| response.json.is_some());
+    let (content_type, schema) = if json {
+        (
+            "application/json",
+            quote! {
+                utoipa::openapi::ObjectBuilder::new()
+                    .property(
+                        "code",
+                        utoipa::openapi::ObjectBuilder::new()
+                            .schema_type(utoipa::openapi::schema::Type::String),
+                    )
+                    .property(
+                        "message",
+                        utoipa::openapi::ObjectBuilder::new()
+                            .schema_type(utoipa::openapi::schema::Type::String),
+                    )
+                    .required("message")
+                    .into()
+            },
+        )
+    } else {
+        (
+            "text/plain",
+            quote! {
+                utoipa::openapi::ObjectBuilder::new()
+                    .schema_type(utoipa::openapi::schema::Type::String)
+                    .into()
+            },
+        )
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics utoipa::PartialSchema for #ty #ty_generics #where_clause {
+            fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+                #schema
+            }
+        }
+
+        impl #impl_generics utoipa::ToSchema for #ty #ty_generics #where_clause {
+            fn name() -> thiserror::private::std::borrow::Cow<'static, str> {
+                thiserror::private::std::borrow::Cow::Borrowed(#name)
+            }
+        }
+
+        impl #impl_generics utoipa::IntoResponses for #ty #ty_generics #where_clause {
+            fn responses() -> thiserror::private::std::collections::BTreeMap<
+                thiserror::private::std::string::String,
+                utoipa::openapi::RefOr<utoipa::openapi::response::Response>,
+            > {
+                // Variants sharing a status are documented as one response.
+                let __responses: &[(u16, &'static str)] = &[#(#responses),*];
+                let mut __descriptions = thiserror::private::std::collections::BTreeMap::<u16, thiserror::private::std::vec::Vec<&'static str>>::new();
+                for &(__status, __description) in __responses {
+                    let __entry = __descriptions.entry(__status).or_default();
+                    if !__entry.contains(&__description) {
+                        __entry.push(__description);
+                    }
+                }
+                __descriptions
+                    .into_iter()
+                    .map(|(__status, __descriptions)| {
+                        let __response = utoipa::openapi::ResponseBuilder::new()
+                            .description(__descriptions.join("\n"))
+                            .content(
+                                #content_type,
+                                utoipa::openapi::ContentBuilder::new()
+                                    .schema(::core::option::Option::Some(
+                                        utoipa::openapi::Ref::from_schema_name(#name),
+                                    ))
+                                    .build(),
+                            )
+                            .build();
+                        (thiserror::private::std::string::ToString::to_string(&__status), __response.into())
+                    })
+                    .collect()
+            }
+        }
+    }
+}
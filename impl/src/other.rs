@@ -0,0 +1,29 @@
+use crate::ast::Enum;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Enum<'_> {
+    pub(crate) fn other_constructor(&self) -> Option<TokenStream> {
+        let variant = self.other_variant()?;
+        let ty = &self.ident;
+        let ident = &variant.ident;
+        let member = &variant.fields[0].member;
+        let doc = format!("Wraps any other error in [`{}::{}`].", ty, ident);
+        let traced = traced(
+            &self.attrs,
+            Some(&variant.attrs),
+            true,
+            quote!(#ty::#ident { #member: ::core::convert::From::from(error) }),
+        );
+        Some(quote! {
+            #[doc = #doc]
+            pub fn other<__E>(error: __E) -> Self
+            where
+                __E: thiserror::private::std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+            {
+                #traced
+            }
+        })
+    }
+}
@@ -0,0 +1,311 @@
+use crate::ast::{Enum, Field, Struct};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::ParseStream;
+use syn::{
+    Attribute, Error, Fields, GenericArgument, GenericParam, Generics, Ident, Lifetime,
+    PathArguments, Result, Type, WherePredicate,
+};
+
+impl Struct<'_> {
+    pub(crate) fn owned_twin(&self) -> Result<Option<TokenStream>> {
+        let owned = match self.attrs.owned {
+            Some(owned) => owned,
+            None => return Ok(None),
+        };
+        let lifetimes = lifetimes(self.generics, owned)?;
+        let ty = &self.ident;
+        let twin = twin_ident(ty);
+        let generics = twin_generics(self.generics, &lifetimes);
+        let (_, twin_generics, where_clause) = generics.split_for_impl();
+        let (impl_generics, ty_generics, original_where_clause) = self.generics.split_for_impl();
+
+        let container_attrs = container_attrs(&self.original.attrs, owned)?;
+        let vis = &self.original.vis;
+        let fields = twin_fields(&self.fields, &lifetimes)?;
+        let body = match &self.fields.first().map(|field| &field.original.ident) {
+            Some(Some(_)) => quote!(#where_clause { #(#fields),* }),
+            Some(None) => quote!(( #(#fields),* ) #where_clause;),
+            None => quote!(#where_clause;),
+        };
+        let (pat, init) = conversion(&self.fields, &lifetimes)?;
+        let doc = format!("Owned counterpart of [`{}`].", ty);
+
+        Ok(Some(quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, thiserror::Error)]
+            #(#container_attrs)*
+            #vis struct #twin #generics #body
+
+            impl #impl_generics #ty #ty_generics #original_where_clause {
+                /// Converts this error into its owned counterpart, which
+                /// does not borrow from its input.
+                pub fn into_owned(self) -> #twin #twin_generics {
+                    let #ty #pat = self;
+                    #twin #init
+                }
+            }
+        }))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn owned_twin(&self) -> Result<Option<TokenStream>> {
+        let owned = match self.attrs.owned {
+            Some(owned) => owned,
+            None => return Ok(None),
+        };
+        let lifetimes = lifetimes(self.generics, owned)?;
+        let ty = &self.ident;
+        let twin = twin_ident(ty);
+        let generics = twin_generics(self.generics, &lifetimes);
+        let (_, twin_generics, where_clause) = generics.split_for_impl();
+        let (impl_generics, ty_generics, original_where_clause) = self.generics.split_for_impl();
+
+        let container_attrs = container_attrs(&self.original.attrs, owned)?;
+        let vis = &self.original.vis;
+        let mut variants = Vec::new();
+        let mut arms = Vec::new();
+        for variant in &self.variants {
+            let attrs = variant.original.attrs.iter().filter(|attr| is_copied(attr));
+            let ident = &variant.ident;
+            let fields = twin_fields(&variant.fields, &lifetimes)?;
+            let fields = match &variant.original.fields {
+                Fields::Named(_) => quote!({ #(#fields),* }),
+                Fields::Unnamed(_) => quote!(( #(#fields),* )),
+                Fields::Unit => TokenStream::new(),
+            };
+            variants.push(quote! {
+                #(#attrs)*
+                #ident #fields
+            });
+            let (pat, init) = conversion(&variant.fields, &lifetimes)?;
+            arms.push(quote! {
+                #ty::#ident #pat => #twin::#ident #init,
+            });
+        }
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        let doc = format!("Owned counterpart of [`{}`].", ty);
+
+        Ok(Some(quote! {
+            #[doc = #doc]
+            #[derive(::core::fmt::Debug, thiserror::Error)]
+            #(#container_attrs)*
+            #vis enum #twin #generics #where_clause {
+                #(#variants,)*
+            }
+
+            impl #impl_generics #ty #ty_generics #original_where_clause {
+                /// Converts this error into its owned counterpart, which
+                /// does not borrow from its input.
+                pub fn into_owned(self) -> #twin #twin_generics {
+                    match #void_deref self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }))
+    }
+}
+
+fn twin_ident(ident: &Ident) -> Ident {
+    format_ident!("{}Owned", ident)
+}
+
+fn lifetimes(generics: &Generics, owned: &Attribute) -> Result<Vec<Lifetime>> {
+    let lifetimes: Vec<Lifetime> = generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .collect();
+    if lifetimes.is_empty() {
+        return Err(Error::new_spanned(
+            owned,
+            "#[error(owned)] requires a type with a lifetime parameter",
+        ));
+    }
+    Ok(lifetimes)
+}
+
+fn twin_generics(generics: &Generics, lifetimes: &[Lifetime]) -> Generics {
+    let mut generics = generics.clone();
+    generics.params = generics
+        .params
+        .into_iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(_) => None,
+            param => Some(param),
+        })
+        .collect();
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Lifetime(_) => false,
+                _ => !mentions_lifetime(predicate.to_token_stream(), lifetimes),
+            })
+            .cloned()
+            .collect();
+    }
+    generics
+}
+
+// Keeps #[error(...)] attributes other than the one requesting the owned twin,
+// so that the twin renders and converts the same way as the original.
+fn container_attrs<'a>(attrs: &'a [Attribute], owned: &Attribute) -> Result<Vec<&'a Attribute>> {
+    owned.parse_args_with(|input: ParseStream| {
+        input.parse::<Ident>()?;
+        if !input.is_empty() {
+            return Err(Error::new_spanned(
+                owned,
+                "#[error(owned)] must be written as a separate attribute",
+            ));
+        }
+        Ok(())
+    })?;
+    Ok(attrs
+        .iter()
+        .filter(|attr| {
+            attr.path.is_ident("error") && !core::ptr::eq(*attr, owned)
+                || attr.path.is_ident("non_exhaustive")
+        })
+        .collect())
+}
+
+fn is_copied(attr: &Attribute) -> bool {
+    [
+        "doc",
+        "error",
+        "source",
+        "from",
+        "backtrace",
+        "code",
+        "display",
+        "error_id",
+        "meta",
+        "non_exhaustive",
+        "sensitive",
+        "timestamp",
+    ]
+    .iter()
+    .any(|name| attr.path.is_ident(name))
+}
+
+fn twin_fields(fields: &[Field], lifetimes: &[Lifetime]) -> Result<Vec<TokenStream>> {
+    fields
+        .iter()
+        .map(|field| {
+            let attrs = field.original.attrs.iter().filter(|attr| is_copied(attr));
+            let vis = &field.original.vis;
+            let colon = field.original.ident.as_ref().map(|ident| quote!(#ident:));
+            let (ty, _) = owned_type(field.ty, lifetimes)?;
+            Ok(quote! {
+                #(#attrs)*
+                #vis #colon #ty
+            })
+        })
+        .collect()
+}
+
+enum Convert {
+    Move,
+    ToOwned,
+    Cow,
+}
+
+// Pattern destructuring the original value, e.g. `{ 0: __field0 }`, and the
+// initializer constructing the twin from the bindings of that pattern.
+fn conversion(fields: &[Field], lifetimes: &[Lifetime]) -> Result<(TokenStream, TokenStream)> {
+    let mut pats = Vec::new();
+    let mut inits = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let member = &field.member;
+        let binding = format_ident!("__field{}", i);
+        let value = match owned_type(field.ty, lifetimes)?.1 {
+            Convert::Move => quote!(#binding),
+            Convert::ToOwned => {
+                quote!(thiserror::private::std::borrow::ToOwned::to_owned(#binding))
+            }
+            Convert::Cow => quote! {
+                thiserror::private::std::borrow::Cow::Owned(thiserror::private::std::borrow::Cow::into_owned(#binding))
+            },
+        };
+        pats.push(quote!(#member: #binding));
+        inits.push(quote!(#member: #value));
+    }
+    Ok((quote!({ #(#pats),* }), quote!({ #(#inits),* })))
+}
+
+fn owned_type(ty: &Type, lifetimes: &[Lifetime]) -> Result<(TokenStream, Convert)> {
+    if !mentions_lifetime(ty.to_token_stream(), lifetimes) {
+        return Ok((ty.to_token_stream(), Convert::Move));
+    }
+
+    if let Type::Reference(reference) = ty {
+        if reference.mutability.is_none() {
+            let elem = &reference.elem;
+            let owned = match &**elem {
+                Type::Path(path) if path.path.is_ident("str") => {
+                    quote!(thiserror::private::std::string::String)
+                }
+                Type::Slice(slice) => {
+                    let elem = &slice.elem;
+                    quote!(thiserror::private::std::vec::Vec<#elem>)
+                }
+                elem => quote!(<#elem as thiserror::private::std::borrow::ToOwned>::Owned),
+            };
+            return Ok((owned, Convert::ToOwned));
+        }
+    }
+
+    if let Type::Path(path) = ty {
+        let last = path.path.segments.last().unwrap();
+        if last.ident == "Cow" {
+            if let PathArguments::AngleBracketed(bracketed) = &last.arguments {
+                let mut args = bracketed.args.iter();
+                if let (
+                    Some(GenericArgument::Lifetime(_)),
+                    Some(GenericArgument::Type(inner)),
+                    None,
+                ) = (args.next(), args.next(), args.next())
+                {
+                    if !mentions_lifetime(inner.to_token_stream(), lifetimes) {
+                        return Ok((
+                            quote!(thiserror::private::std::borrow::Cow<'static, #inner>),
+                            Convert::Cow,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Err(Error::new_spanned(
+        ty,
+        "#[error(owned)] does not know how to make an owned copy of this field; supported borrowed types are `&'a T` where T: ToOwned, and `Cow<'a, T>`",
+    ))
+}
+
+fn mentions_lifetime(tokens: TokenStream, lifetimes: &[Lifetime]) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+                    if lifetimes.iter().any(|lifetime| lifetime.ident == *ident) {
+                        return true;
+                    }
+                }
+            }
+            TokenTree::Group(group) if mentions_lifetime(group.stream(), lifetimes) => {
+                return true;
+            }
+            _ => {}
+        }
+    }
+    false
+}
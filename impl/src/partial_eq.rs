@@ -0,0 +1,120 @@
+use crate::ast::{Enum, Field, Struct};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Generics, Ident, Member};
+
+impl Struct<'_> {
+    pub(crate) fn partial_eq_impl(&self) -> Option<TokenStream> {
+        self.attrs.partial_eq?;
+        let comparisons =
+            compared(&self.fields, self.backtrace_field(), self.source_field()).map(|field| {
+                let member = &field.member;
+                quote!(self.#member == other.#member)
+            });
+        let body = all(comparisons.collect());
+        Some(partial_eq_impl(&self.ident, self.generics, body))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn partial_eq_impl(&self) -> Option<TokenStream> {
+        self.attrs.partial_eq?;
+        let ty = &self.ident;
+        if self.variants.is_empty() {
+            return Some(partial_eq_impl(ty, self.generics, quote!(match *self {})));
+        }
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let fields: Vec<&Field> = compared(
+                &variant.fields,
+                variant.backtrace_field(),
+                variant.source_field(),
+            )
+            .collect();
+            let members: Vec<&Member> = fields.iter().map(|field| &field.member).collect();
+            let ours: Vec<Ident> = members
+                .iter()
+                .map(|member| binding("self", member))
+                .collect();
+            let theirs: Vec<Ident> = members
+                .iter()
+                .map(|member| binding("other", member))
+                .collect();
+            let comparisons = ours
+                .iter()
+                .zip(&theirs)
+                .map(|(ours, theirs)| quote!(#ours == #theirs))
+                .collect();
+            let body = all(comparisons);
+            quote! {
+                (
+                    #ty::#ident { #(#members: #ours,)* .. },
+                    #ty::#ident { #(#members: #theirs,)* .. },
+                ) => #body,
+            }
+        });
+        Some(partial_eq_impl(
+            ty,
+            self.generics,
+            quote! {
+                match (self, other) {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => false,
+                }
+            },
+        ))
+    }
+}
+
+// Every field other than a captured backtrace, span trace, #[error_id] or
+// #[timestamp], which differ between two otherwise equal errors. A source
+// that provides the backtrace is still compared.
+fn compared<'a, 'b>(
+    fields: &'a [Field<'b>],
+    backtrace_field: Option<&'a Field<'b>>,
+    source_field: Option<&'a Field<'b>>,
+) -> impl Iterator<Item = &'a Field<'b>> {
+    let is = |other: Option<&Field>, field: &Field| matches!(other, Some(other) if other.member == field.member);
+    fields.iter().filter(move |field| {
+        let is_backtrace = is(backtrace_field, field) && !is(source_field, field);
+        !is_backtrace
+            && field.span_trace_type().is_none()
+            && field.attrs.error_id.is_none()
+            && field.attrs.timestamp.is_none()
+    })
+}
+
+pub(crate) fn binding(side: &str, member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => format_ident!("__{}_{}", side, ident),
+        Member::Unnamed(index) => format_ident!("__{}_{}", side, index.index),
+    }
+}
+
+fn all(comparisons: Vec<TokenStream>) -> TokenStream {
+    if comparisons.is_empty() {
+        quote!(true)
+    } else {
+        quote!(#(#comparisons)&&*)
+    }
+}
+
+fn partial_eq_impl(ty: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mut bounded = generics.clone();
+    let where_clause = bounded.make_where_clause();
+    for param in generics.type_params() {
+        let param = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::core::cmp::PartialEq));
+    }
+    quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                #body
+            }
+        }
+    }
+}
@@ -1,18 +1,63 @@
 use crate::ast::{Enum, Field, Struct, Variant};
-use syn::{Member, Type};
+use crate::attr::{Display, FromWrap, Trait};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{Attribute, GenericArgument, Generics, Ident, Member, PathArguments, Type};
 
 impl Struct<'_> {
-    pub(crate) fn from_field(&self) -> Option<&Field> {
+    pub(crate) fn from_field(&self) -> Option<&Field<'_>> {
         from_field(&self.fields)
     }
 
-    pub(crate) fn source_field(&self) -> Option<&Field> {
+    pub(crate) fn source_field(&self) -> Option<&Field<'_>> {
         source_field(&self.fields)
     }
 
-    pub(crate) fn backtrace_field(&self) -> Option<&Field> {
+    // The source field, if Error::source can hand it out as 'static.
+    pub(crate) fn static_source_field(&self) -> Option<&Field<'_>> {
+        self.source_field().filter(|field| !field.is_borrowed())
+    }
+
+    pub(crate) fn backtrace_field(&self) -> Option<&Field<'_>> {
         backtrace_field(&self.fields)
     }
+
+    pub(crate) fn span_trace_field(&self) -> Option<&Field<'_>> {
+        span_trace_field(&self.fields)
+    }
+
+    pub(crate) fn error_id_field(&self) -> Option<&Field<'_>> {
+        error_id_field(&self.fields)
+    }
+
+    pub(crate) fn timestamp_field(&self) -> Option<&Field<'_>> {
+        timestamp_field(&self.fields)
+    }
+
+    // Type parameters that source() hands out as an Error, either as the
+    // source field or as the only field of a transparent struct.
+    pub(crate) fn generic_sources(&self) -> Vec<&Ident> {
+        let source_field = match self.attrs.transparent {
+            Some(_) => self.fields.first(),
+            None => self.source_field(),
+        };
+        source_field
+            .and_then(|source_field| source_field.type_param(self.generics))
+            .into_iter()
+            .collect()
+    }
+
+    // The formatting traits that the Display impl needs of generic fields.
+    pub(crate) fn display_bounds(&self) -> Vec<(&Type, Trait)> {
+        let bounds = if self.attrs.transparent.is_some() {
+            vec![(self.fields[0].ty, Trait::Display)]
+        } else if let Some(display) = &self.attrs.display {
+            implied_bounds(display, &self.fields)
+        } else {
+            Vec::new()
+        };
+        generic_bounds(bounds, self.generics)
+    }
 }
 
 impl Enum<'_> {
@@ -22,6 +67,40 @@ impl Enum<'_> {
             .any(|variant| variant.source_field().is_some() || variant.attrs.transparent.is_some())
     }
 
+    pub(crate) fn generic_sources(&self) -> Vec<&Ident> {
+        let mut params = Vec::new();
+        for variant in &self.variants {
+            let source_field = match variant.attrs.transparent {
+                Some(_) => variant.fields.first(),
+                None => variant.source_field(),
+            };
+            if let Some(param) =
+                source_field.and_then(|source_field| source_field.type_param(self.generics))
+            {
+                if !params.contains(&param) {
+                    params.push(param);
+                }
+            }
+        }
+        params
+    }
+
+    pub(crate) fn display_bounds(&self) -> Vec<(&Type, Trait)> {
+        let mut bounds = Vec::new();
+        for variant in &self.variants {
+            match &variant.attrs.display {
+                Some(display) => bounds.extend(implied_bounds(display, &variant.fields)),
+                None => {
+                    let field = variant
+                        .whatever_message_field()
+                        .or_else(|| variant.fields.first());
+                    bounds.extend(field.map(|field| (field.ty, Trait::Display)));
+                }
+            }
+        }
+        generic_bounds(bounds, self.generics)
+    }
+
     pub(crate) fn has_backtrace(&self) -> bool {
         self.variants
             .iter()
@@ -35,31 +114,169 @@ impl Enum<'_> {
                 .variants
                 .iter()
                 .any(|variant| variant.attrs.display.is_some())
-            || self
-                .variants
-                .iter()
-                .all(|variant| variant.attrs.transparent.is_some())
+            || self.variants.iter().all(|variant| {
+                variant.attrs.transparent.is_some()
+                    || variant.attrs.message.is_some()
+                    || variant.attrs.whatever.is_some()
+            })
+    }
+
+    pub(crate) fn message_variant(&self) -> Option<&Variant<'_>> {
+        self.variants
+            .iter()
+            .find(|variant| variant.attrs.message.is_some())
+    }
+
+    pub(crate) fn whatever_variant(&self) -> Option<&Variant<'_>> {
+        self.variants
+            .iter()
+            .find(|variant| variant.attrs.whatever.is_some())
+    }
+
+    pub(crate) fn other_variant(&self) -> Option<&Variant<'_>> {
+        self.variants
+            .iter()
+            .find(|variant| variant.attrs.other.is_some())
     }
 }
 
 impl Variant<'_> {
-    pub(crate) fn from_field(&self) -> Option<&Field> {
+    pub(crate) fn from_field(&self) -> Option<&Field<'_>> {
         from_field(&self.fields)
     }
 
-    pub(crate) fn source_field(&self) -> Option<&Field> {
-        source_field(&self.fields)
+    pub(crate) fn static_source_field(&self) -> Option<&Field<'_>> {
+        self.source_field().filter(|field| !field.is_borrowed())
+    }
+
+    // The boxed error of an #[error(other)] variant is its source even when
+    // not marked as such.
+    pub(crate) fn source_field(&self) -> Option<&Field<'_>> {
+        source_field(&self.fields).or(match self.attrs.other {
+            Some(_) => self.fields.first(),
+            None => None,
+        })
     }
 
-    pub(crate) fn backtrace_field(&self) -> Option<&Field> {
+    pub(crate) fn backtrace_field(&self) -> Option<&Field<'_>> {
         backtrace_field(&self.fields)
     }
+
+    pub(crate) fn span_trace_field(&self) -> Option<&Field<'_>> {
+        span_trace_field(&self.fields)
+    }
+
+    pub(crate) fn error_id_field(&self) -> Option<&Field<'_>> {
+        error_id_field(&self.fields)
+    }
+
+    pub(crate) fn timestamp_field(&self) -> Option<&Field<'_>> {
+        timestamp_field(&self.fields)
+    }
+
+    // The `message` field of an #[whatever] variant.
+    pub(crate) fn whatever_message_field(&self) -> Option<&Field<'_>> {
+        self.attrs.whatever?;
+        self.fields
+            .iter()
+            .find(|field| matches!(&field.member, Member::Named(ident) if ident == "message"))
+    }
+
+    // Generated functions standing in for the variant's constructor carry
+    // the same #[deprecated], so that callers are warned either way.
+    pub(crate) fn deprecated(&self) -> Option<&Attribute> {
+        self.original
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("deprecated"))
+    }
+
+    pub(crate) fn try_into_field(&self) -> Option<&Field<'_>> {
+        match self.source_field() {
+            Some(source_field) => Some(source_field),
+            None if self.fields.len() == 1 => Some(&self.fields[0]),
+            None => None,
+        }
+    }
 }
 
 impl Field<'_> {
     pub(crate) fn is_backtrace(&self) -> bool {
         type_is_backtrace(self.ty)
     }
+
+    // Whether the field's type borrows with some lifetime other than 'static,
+    // which rules it out of the `dyn Error + 'static` returned by source().
+    pub(crate) fn is_borrowed(&self) -> bool {
+        mentions_lifetime(self.ty.to_token_stream())
+    }
+
+    // A `&'static Location<'static>`, which constructors fill in with their
+    // caller.
+    pub(crate) fn is_location(&self) -> bool {
+        match self.ty {
+            Type::Reference(ty) => ty.mutability.is_none() && type_is_location(&ty.elem),
+            _ => false,
+        }
+    }
+
+    // The type of an #[timestamp] field's `now()`, either the field's own type
+    // or T in Option<T>.
+    pub(crate) fn timestamp_type(&self) -> Option<&Type> {
+        self.attrs.timestamp?;
+        Some(type_parameter_of(self.ty, "Option").unwrap_or(self.ty))
+    }
+
+    // A timestamp of std's SystemTime or Instant, which do not implement
+    // Display themselves.
+    pub(crate) fn is_std_timestamp(&self) -> bool {
+        let path = match self.timestamp_type() {
+            Some(Type::Path(ty)) => &ty.path,
+            _ => return false,
+        };
+        let last = path.segments.last().unwrap();
+        (last.ident == "SystemTime" || last.ident == "Instant") && last.arguments.is_empty()
+    }
+
+    // The SpanTrace type, either the field's own type or T in Option<T>.
+    pub(crate) fn span_trace_type(&self) -> Option<&Type> {
+        let ty = type_parameter_of(self.ty, "Option").unwrap_or(self.ty);
+        if type_is_span_trace(ty) {
+            Some(ty)
+        } else {
+            None
+        }
+    }
+
+    // The type parameter that makes up this field's type, either the field's
+    // own type or T in Option<T>.
+    pub(crate) fn type_param<'g>(&self, generics: &'g Generics) -> Option<&'g Ident> {
+        let ty = type_parameter_of(self.ty, "Option").unwrap_or(self.ty);
+        generics
+            .type_params()
+            .map(|param| &param.ident)
+            .find(|ident| type_is_ident(ty, ident))
+    }
+
+    pub(crate) fn from_types(&self) -> Vec<&Type> {
+        match &self.attrs.from {
+            Some(from) if !from.types.is_empty() => from.types.iter().collect(),
+            Some(from) => match from.wrap.and_then(|wrap| self.wrapped_type(wrap)) {
+                Some(inner) => vec![inner],
+                None => vec![self.ty],
+            },
+            None => vec![self.ty],
+        }
+    }
+
+    pub(crate) fn wrapped_type(&self, wrap: FromWrap) -> Option<&Type> {
+        let wrapper = match wrap {
+            FromWrap::Arc => "Arc",
+            FromWrap::Box => "Box",
+            FromWrap::Raw => return None,
+        };
+        type_parameter_of(self.ty, wrapper)
+    }
 }
 
 fn from_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
@@ -100,6 +317,118 @@ fn backtrace_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
     None
 }
 
+fn error_id_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    fields.iter().find(|field| field.attrs.error_id.is_some())
+}
+
+fn timestamp_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    fields.iter().find(|field| field.attrs.timestamp.is_some())
+}
+
+fn span_trace_field<'a, 'b>(fields: &'a [Field<'b>]) -> Option<&'a Field<'b>> {
+    fields
+        .iter()
+        .find(|field| field.span_trace_type().is_some())
+}
+
+pub(crate) fn type_parameter_of<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return None,
+    };
+
+    let last = path.segments.last().unwrap();
+    if last.ident != wrapper {
+        return None;
+    }
+
+    match &last.arguments {
+        PathArguments::AngleBracketed(bracketed) if bracketed.args.len() == 1 => {
+            match &bracketed.args[0] {
+                GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn implied_bounds<'a>(display: &Display, fields: &'a [Field]) -> Vec<(&'a Type, Trait)> {
+    fields
+        .iter()
+        .flat_map(|field| {
+            display
+                .implied_bounds
+                .iter()
+                .filter(move |(member, _)| *member == field.member)
+                .map(move |(_, bound)| (field.ty, *bound))
+        })
+        .collect()
+}
+
+// Keeps the bounds on field types that involve a type parameter, which are the
+// ones the definition may not have spelled out, without repeating any.
+fn generic_bounds<'a>(
+    bounds: Vec<(&'a Type, Trait)>,
+    generics: &Generics,
+) -> Vec<(&'a Type, Trait)> {
+    let params: Vec<&Ident> = generics.type_params().map(|param| &param.ident).collect();
+    let mut seen = Vec::new();
+    let mut generic_bounds = Vec::new();
+    for (ty, bound) in bounds {
+        let tokens = ty.to_token_stream();
+        let key = (tokens.to_string(), bound);
+        if !seen.contains(&key) && tokens_mention(tokens, &params) {
+            seen.push(key);
+            generic_bounds.push((ty, bound));
+        }
+    }
+    generic_bounds
+}
+
+fn mentions_lifetime(tokens: TokenStream) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+                    if ident != "static" {
+                        return true;
+                    }
+                }
+            }
+            TokenTree::Group(group) if mentions_lifetime(group.stream()) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn tokens_mention(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.iter().any(|param| ident == **param),
+        TokenTree::Group(group) => tokens_mention(group.stream(), idents),
+        _ => false,
+    })
+}
+
+pub(crate) fn type_is_ident(ty: &Type, ident: &Ident) -> bool {
+    match ty {
+        Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+pub(crate) fn type_is_string(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    let last = path.segments.last().unwrap();
+    last.ident == "String" && last.arguments.is_empty()
+}
+
 fn type_is_backtrace(ty: &Type) -> bool {
     let path = match ty {
         Type::Path(ty) => &ty.path,
@@ -109,3 +438,23 @@ fn type_is_backtrace(ty: &Type) -> bool {
     let last = path.segments.last().unwrap();
     last.ident == "Backtrace" && last.arguments.is_empty()
 }
+
+fn type_is_span_trace(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    let last = path.segments.last().unwrap();
+    last.ident == "SpanTrace" && last.arguments.is_empty()
+}
+
+fn type_is_location(ty: &Type) -> bool {
+    let path = match ty {
+        Type::Path(ty) => &ty.path,
+        _ => return false,
+    };
+
+    let last = path.segments.last().unwrap();
+    last.ident == "Location"
+}
@@ -0,0 +1,71 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn py_err_impl(&self) -> Option<TokenStream> {
+        self.attrs.py.as_ref()?;
+        let exception = exception(&self.attrs, None);
+        Some(py_err_impl(
+            &self.ident,
+            self.generics,
+            quote!(#exception::new_err(message)),
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn py_err_impl(&self) -> Option<TokenStream> {
+        if self.attrs.py.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.py.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let exception = exception(&self.attrs, Some(&variant.attrs));
+            quote!(#ty::#ident {..} => #exception::new_err(message),)
+        });
+        Some(py_err_impl(
+            ty,
+            self.generics,
+            quote! {
+                match error {
+                    #(#arms)*
+                }
+            },
+        ))
+    }
+}
+
+// The variant's exception type, else the container's, else RuntimeError.
+fn exception(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let py = variant
+        .and_then(|variant| variant.py.as_ref())
+        .or(container.py.as_ref());
+    match py {
+        Some(py) => {
+            let exception = &py.exception;
+            quote!(<#exception>)
+        }
+        None => quote!(pyo3::exceptions::PyRuntimeError),
+    }
+}
+
+fn py_err_impl(ty: &Ident, generics: &Generics, body: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for pyo3::PyErr #where_clause {
+            fn from(error: #ty #ty_generics) -> Self {
+                let message = thiserror::private::std::string::ToString::to_string(&error);
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn report_methods(&self) -> Option<TokenStream> {
+        self.attrs.into_report?;
+        let code = self
+            .attrs
+            .code
+            .as_ref()
+            .map(|code| quote!(::core::option::Option::Some(#code)));
+        Some(method(code))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn report_methods(&self) -> Option<TokenStream> {
+        self.attrs.into_report?;
+        if self
+            .variants
+            .iter()
+            .all(|variant| variant.attrs.code.is_none())
+        {
+            return Some(method(None));
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match &variant.attrs.code {
+                Some(code) => quote!(#ty::#ident {..} => ::core::option::Option::Some(#code),),
+                None => quote!(#ty::#ident {..} => ::core::option::Option::None,),
+            }
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(Some(quote! {
+            match #void_deref &self {
+                #(#arms)*
+            }
+        })))
+    }
+}
+
+// Sections are a color-eyre extension, so the crate only needs a dependency
+// on color-eyre if some variant has a #[code(...)] to attach.
+fn method(code: Option<TokenStream>) -> TokenStream {
+    let body = match code {
+        Some(code) => quote! {
+            let code: ::core::option::Option<&'static str> = #code;
+            let report = eyre::Report::new(self);
+            match code {
+                ::core::option::Option::Some(code) => {
+                    color_eyre::Section::section(report, thiserror::private::std::format!("Code: {}", code))
+                }
+                ::core::option::Option::None => report,
+            }
+        },
+        None => quote!(eyre::Report::new(self)),
+    };
+    quote! {
+        /// Converts this error into an `eyre::Report`, which keeps the chain
+        /// of sources and carries the error code as a section.
+        pub fn into_report(self) -> eyre::Report
+        where
+            Self: thiserror::private::std::error::Error + ::core::marker::Send + ::core::marker::Sync + 'static,
+        {
+            #body
+        }
+    }
+}
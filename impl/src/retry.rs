@@ -0,0 +1,110 @@
+use crate::ast::{Enum, Struct, Variant};
+use crate::attr::{Attrs, Retryable};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn retry_methods(&self) -> Option<TokenStream> {
+        let retryable = self.attrs.retryable.as_ref()?;
+        Some(method(retryable_expr(retryable)))
+    }
+
+    pub(crate) fn retryable_impl(&self) -> Option<TokenStream> {
+        self.attrs.retryable.as_ref()?;
+        Some(retryable_impl(&self.ident, self.generics))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn retry_methods(&self) -> Option<TokenStream> {
+        if !self.has_retryable() {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant_arm(ty, &self.attrs, variant));
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+
+    pub(crate) fn retryable_impl(&self) -> Option<TokenStream> {
+        if !self.has_retryable() {
+            return None;
+        }
+        Some(retryable_impl(&self.ident, self.generics))
+    }
+
+    fn has_retryable(&self) -> bool {
+        self.attrs.retryable.is_some()
+            || self
+                .variants
+                .iter()
+                .any(|variant| variant.attrs.retryable.is_some())
+    }
+}
+
+// The variant's #[retryable], else a transparent variant asks its source,
+// else the enum's #[retryable], else not retryable.
+fn variant_arm(ty: &Ident, container: &Attrs, variant: &Variant) -> TokenStream {
+    let ident = &variant.ident;
+    if let Some(retryable) = &variant.attrs.retryable {
+        let expr = retryable_expr(retryable);
+        return quote!(#ty::#ident {..} => #expr,);
+    }
+    if variant.attrs.transparent.is_some() {
+        let member = &variant.fields[0].member;
+        return quote! {
+            #ty::#ident { #member: __source, .. } => {
+                #[allow(unused_imports)]
+                use thiserror::private::{RetryableDelegate, RetryableFallback};
+                (&thiserror::private::Retry(__source)).is_retryable()
+            }
+        };
+    }
+    match &container.retryable {
+        Some(retryable) => {
+            let expr = retryable_expr(retryable);
+            quote!(#ty::#ident {..} => #expr,)
+        }
+        None => quote!(#ty::#ident {..} => false,),
+    }
+}
+
+fn retryable_expr(retryable: &Retryable) -> TokenStream {
+    match &retryable.condition {
+        Some(condition) => quote!(#condition(self)),
+        None => quote!(true),
+    }
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// Whether the operation that failed with this error is worth
+        /// retrying.
+        pub fn is_retryable(&self) -> bool {
+            #body
+        }
+    }
+}
+
+fn retryable_impl(ty: &Ident, generics: &Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics thiserror::Retryable for #ty #ty_generics #where_clause {
+            fn is_retryable(&self) -> bool {
+                Self::is_retryable(self)
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::ast::{Enum, Struct};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, LitStr};
+
+impl Struct<'_> {
+    pub(crate) fn json_schema_impl(&self) -> Option<TokenStream> {
+        self.attrs.json_schema?;
+        let variant = self.ident.to_string();
+        let codes: Vec<&LitStr> = self.attrs.code.iter().collect();
+        // A struct with a code always writes it.
+        let code_required = !codes.is_empty();
+        Some(json_schema_impl(
+            &self.ident,
+            self.generics,
+            &[variant],
+            &codes,
+            code_required,
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn json_schema_impl(&self) -> Option<TokenStream> {
+        self.attrs.json_schema?;
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|variant| variant.ident.to_string())
+            .collect();
+        let codes: Vec<&LitStr> = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.code.as_ref())
+            .collect();
+        let code_required = !self.variants.is_empty()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.code.is_some());
+        Some(json_schema_impl(
+            &self.ident,
+            self.generics,
+            &variants,
+            &codes,
+            code_required,
+        ))
+    }
+}
+
+// Describes the object written by thiserror::private::serialize_error: the
+// variant name, the message, the code if there is one, and the messages of
+// the chain of sources.
+fn json_schema_impl(
+    ty: &Ident,
+    generics: &Generics,
+    variants: &[String],
+    codes: &[&LitStr],
+    code_required: bool,
+) -> TokenStream {
+    let name = ty.to_string();
+    let code_property = if codes.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            "code": { "type": "string", "enum": [#(#codes),*] },
+        })
+    };
+    let code_required = if code_required {
+        Some(quote!("code",))
+    } else {
+        None
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics schemars::JsonSchema for #ty #ty_generics #where_clause {
+            fn schema_name() -> thiserror::private::std::borrow::Cow<'static, str> {
+                thiserror::private::std::borrow::Cow::Borrowed(#name)
+            }
+
+            fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                schemars::json_schema!({
+                    "type": "object",
+                    "properties": {
+                        "variant": { "type": "string", "enum": [#(#variants),*] },
+                        "message": { "type": "string" },
+                        #code_property
+                        "chain": { "type": "array", "items": { "type": "string" } },
+                    },
+                    "required": ["variant", "message", #code_required "chain"],
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+impl Struct<'_> {
+    pub(crate) fn sentry_methods(&self) -> Option<TokenStream> {
+        self.attrs.sentry.as_ref()?;
+        Some(methods(
+            fingerprint(&self.attrs, None),
+            tags(&self.attrs, None),
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn sentry_methods(&self) -> Option<TokenStream> {
+        if self.attrs.sentry.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.sentry.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let mut fingerprint_arms = Vec::new();
+        let mut tags_arms = Vec::new();
+        for variant in &self.variants {
+            let ident = &variant.ident;
+            let fingerprint = fingerprint(&self.attrs, Some(&variant.attrs));
+            let tags = tags(&self.attrs, Some(&variant.attrs));
+            fingerprint_arms.push(quote!(#ty::#ident {..} => #fingerprint,));
+            tags_arms.push(quote!(#ty::#ident {..} => #tags,));
+        }
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(methods(
+            quote! {
+                match #void_deref self {
+                    #(#fingerprint_arms)*
+                }
+            },
+            quote! {
+                match #void_deref self {
+                    #(#tags_arms)*
+                }
+            },
+        ))
+    }
+}
+
+// The variant's fingerprint, else the enum's, else Sentry's default grouping.
+fn fingerprint(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let fingerprint = variant
+        .and_then(|variant| variant.sentry.as_ref())
+        .filter(|sentry| !sentry.fingerprint.is_empty())
+        .or(container.sentry.as_ref())
+        .map(|sentry| &sentry.fingerprint)
+        .filter(|fingerprint| !fingerprint.is_empty());
+    match fingerprint {
+        Some(fingerprint) => quote!(thiserror::private::std::vec![#(#fingerprint),*]),
+        None => quote!(thiserror::private::std::vec!["{{ default }}"]),
+    }
+}
+
+// The enum's tags together with the variant's, which take precedence.
+fn tags(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let variant_tags = variant
+        .and_then(|variant| variant.sentry.as_ref())
+        .map_or(&[][..], |sentry| &sentry.tags);
+    let container_tags = container
+        .sentry
+        .as_ref()
+        .map_or(&[][..], |sentry| &sentry.tags)
+        .iter()
+        .filter(|(key, _)| {
+            !variant_tags
+                .iter()
+                .any(|(variant_key, _)| variant_key.value() == key.value())
+        });
+    let inserts = container_tags
+        .chain(variant_tags)
+        .map(|(key, value)| quote!(__tags.insert(#key, #value);));
+    quote!({
+        #[allow(unused_mut)]
+        let mut __tags = thiserror::private::std::collections::BTreeMap::new();
+        #(#inserts)*
+        __tags
+    })
+}
+
+fn methods(fingerprint_body: TokenStream, tags_body: TokenStream) -> TokenStream {
+    quote! {
+        /// The fingerprint by which Sentry groups occurrences of this error.
+        pub fn sentry_fingerprint(&self) -> thiserror::private::std::vec::Vec<&'static str> {
+            #fingerprint_body
+        }
+
+        /// The tags with which this error is reported to Sentry.
+        pub fn sentry_tags(
+            &self,
+        ) -> thiserror::private::std::collections::BTreeMap<&'static str, &'static str> {
+            #tags_body
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::attr::Attrs;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Attribute, Data, Fields, Generics, Ident};
+
+impl Struct<'_> {
+    pub(crate) fn serialize_impl(&self) -> Option<TokenStream> {
+        self.attrs.serialize?;
+        let variant = self.ident.to_string();
+        let code = code(&self.attrs);
+        Some(serialize_impl(
+            &self.ident,
+            self.generics,
+            quote!((#variant, #code)),
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn serialize_impl(&self) -> Option<TokenStream> {
+        self.attrs.serialize?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = ident.to_string();
+            let code = code(&variant.attrs);
+            quote!(#ty::#ident {..} => (#name, #code),)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(serialize_impl(
+            ty,
+            self.generics,
+            quote! {
+                match #void_deref self {
+                    #(#arms)*
+                }
+            },
+        ))
+    }
+}
+
+fn code(attrs: &Attrs) -> TokenStream {
+    match &attrs.code {
+        Some(code) => quote!(::core::option::Option::Some(#code)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn serialize_impl(ty: &Ident, generics: &Generics, variant_and_code: TokenStream) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics thiserror::private::serde::Serialize for #ty #ty_generics #where_clause {
+            fn serialize<__S>(
+                &self,
+                __serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: thiserror::private::serde::Serializer,
+            {
+                let (variant, code) = #variant_and_code;
+                thiserror::private::serialize_error(self, variant, code, __serializer)
+            }
+        }
+    }
+}
+
+impl Struct<'_> {
+    pub(crate) fn deserialize_impl(&self) -> Option<TokenStream> {
+        self.attrs.deserialize?;
+        let ty = &self.ident;
+        let shadow = shadow_ident(ty);
+        let generics = self.generics;
+        let where_clause = &generics.where_clause;
+        let fields = shadow_fields(&self.fields);
+        let definition = match &self.original.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(_) => quote!(struct #shadow #generics #where_clause { #(#fields),* }),
+                Fields::Unnamed(_) => {
+                    quote!(struct #shadow #generics (#(#fields),*) #where_clause;)
+                }
+                Fields::Unit => quote!(struct #shadow #generics #where_clause;),
+            },
+            _ => unreachable!(),
+        };
+        let (pat, init) = conversion(&self.fields);
+        Some(deserialize_impl(
+            &self.original.attrs,
+            "struct",
+            ty,
+            generics,
+            definition,
+            quote!(match __shadow {
+                #shadow #pat => #ty #init,
+            }),
+        ))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn deserialize_impl(&self) -> Option<TokenStream> {
+        self.attrs.deserialize?;
+        let ty = &self.ident;
+        let shadow = shadow_ident(ty);
+        let generics = self.generics;
+        let where_clause = &generics.where_clause;
+        let mut variants = Vec::new();
+        let mut arms = Vec::new();
+        for variant in &self.variants {
+            let attrs = serde_attrs(&variant.original.attrs);
+            let ident = &variant.ident;
+            let fields = shadow_fields(&variant.fields);
+            let fields = match &variant.original.fields {
+                Fields::Named(_) => quote!({ #(#fields),* }),
+                Fields::Unnamed(_) => quote!((#(#fields),*)),
+                Fields::Unit => TokenStream::new(),
+            };
+            variants.push(quote! {
+                #(#attrs)*
+                #ident #fields
+            });
+            let (pat, init) = conversion(&variant.fields);
+            arms.push(quote! {
+                #shadow::#ident #pat => #ty::#ident #init,
+            });
+        }
+        Some(deserialize_impl(
+            &self.original.attrs,
+            "enum",
+            ty,
+            generics,
+            quote!(enum #shadow #generics #where_clause { #(#variants),* }),
+            quote!(match __shadow {
+                #(#arms)*
+            }),
+        ))
+    }
+}
+
+// The shadow type mirrors the fields of the error and derives Deserialize
+// through serde_derive; the result is then moved into the error field by
+// field.
+fn shadow_ident(ty: &Ident) -> Ident {
+    format_ident!("__Deserialize{}", ty)
+}
+
+fn shadow_fields(fields: &[Field]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| {
+            let attrs = serde_attrs(&field.original.attrs);
+            let colon = field.original.ident.as_ref().map(|ident| quote!(#ident:));
+            let ty = field.ty;
+            quote! {
+                #(#attrs)*
+                #colon #ty
+            }
+        })
+        .collect()
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> Vec<&Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .collect()
+}
+
+fn conversion(fields: &[Field]) -> (TokenStream, TokenStream) {
+    let members: Vec<_> = fields.iter().map(|field| &field.member).collect();
+    let vars: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    (
+        quote!({ #(#members: #vars),* }),
+        quote!({ #(#members: #vars),* }),
+    )
+}
+
+fn deserialize_impl(
+    container_attrs: &[Attribute],
+    kind: &str,
+    ty: &Ident,
+    generics: &Generics,
+    shadow_definition: TokenStream,
+    conversion: TokenStream,
+) -> TokenStream {
+    let shadow = shadow_ident(ty);
+    let serde_attrs = serde_attrs(container_attrs);
+    // Report the error's own name in messages from serde, such as "expected
+    // struct MyError", unless the container already configures serde itself.
+    let rename = if serde_attrs.is_empty() {
+        let name = ty.to_string();
+        let expecting = format!("{} {}", kind, ty);
+        Some(quote!(#[serde(rename = #name, expecting = #expecting)]))
+    } else {
+        None
+    };
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let mut impl_generics = generics.clone();
+    impl_generics.params.insert(0, parse_quote!('de));
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#shadow #ty_generics: thiserror::private::serde::Deserialize<'de>));
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            #[derive(thiserror::private::serde::Deserialize)]
+            #[serde(crate = "thiserror::private::serde")]
+            #rename
+            #(#serde_attrs)*
+            #shadow_definition
+
+            impl #impl_generics thiserror::private::serde::Deserialize<'de>
+                for #ty #ty_generics #where_clause
+            {
+                fn deserialize<__D>(
+                    __deserializer: __D,
+                ) -> ::core::result::Result<Self, __D::Error>
+                where
+                    __D: thiserror::private::serde::Deserializer<'de>,
+                {
+                    let __shadow =
+                        <#shadow #ty_generics as thiserror::private::serde::Deserialize<'de>>::deserialize(
+                            __deserializer,
+                        )?;
+                    ::core::result::Result::Ok(#conversion)
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,59 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+impl Struct<'_> {
+    pub(crate) fn severity_methods(&self) -> Option<TokenStream> {
+        self.attrs.severity.as_ref()?;
+        Some(method(severity(&self.attrs, None)))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn severity_methods(&self) -> Option<TokenStream> {
+        if self.attrs.severity.is_none()
+            && self
+                .variants
+                .iter()
+                .all(|variant| variant.attrs.severity.is_none())
+        {
+            return None;
+        }
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let severity = severity(&self.attrs, Some(&variant.attrs));
+            quote!(#ty::#ident {..} => #severity,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// The variant's severity, else the type's, else Error.
+pub(crate) fn severity(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let severity = variant
+        .and_then(|variant| variant.severity.clone())
+        .or_else(|| container.severity.clone())
+        .unwrap_or_else(|| Ident::new("Error", Span::call_site()));
+    quote!(thiserror::Severity::#severity)
+}
+
+fn method(body: TokenStream) -> TokenStream {
+    quote! {
+        /// How serious this error is, for logging and alerting to act on.
+        pub fn severity(&self) -> thiserror::Severity {
+            #body
+        }
+    }
+}
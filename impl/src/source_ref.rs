@@ -0,0 +1,92 @@
+use crate::ast::{Enum, Field, Struct};
+use crate::expand::{error_trait, type_is_option};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+impl Struct<'_> {
+    pub(crate) fn source_ref_method(&self) -> Option<TokenStream> {
+        let source_field = self.source_field().filter(|field| field.is_borrowed())?;
+        let member = &source_field.member;
+        let source = borrowed_source(source_field, quote!(source));
+        Some(source_ref_method(quote! {
+            let source = &self.#member;
+            ::core::option::Option::Some(#source)
+        }))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn source_ref_method(&self) -> Option<TokenStream> {
+        let borrows = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.source_field())
+            .any(|source_field| source_field.is_borrowed());
+        if !borrows {
+            return None;
+        }
+        let error_trait = error_trait();
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            if variant.attrs.transparent.is_some() {
+                let only_field = &variant.fields[0];
+                let member = &only_field.member;
+                let source = if only_field.is_borrowed() {
+                    quote!(#error_trait::source(transparent))
+                } else {
+                    quote!(#error_trait::source(transparent.as_dyn_error()))
+                };
+                quote! {
+                    #ty::#ident {#member: transparent} => #source,
+                }
+            } else if let Some(source_field) = variant.source_field() {
+                let member = &source_field.member;
+                let source = if source_field.is_borrowed() {
+                    borrowed_source(source_field, quote!(source))
+                } else if type_is_option(source_field.ty) {
+                    quote_spanned!(member.span()=> source.as_ref()?.as_dyn_error())
+                } else {
+                    quote_spanned!(member.span()=> source.as_dyn_error())
+                };
+                quote! {
+                    #ty::#ident {#member: source, ..} => ::core::option::Option::Some(#source),
+                }
+            } else {
+                quote! {
+                    #ty::#ident {..} => ::core::option::Option::None,
+                }
+            }
+        });
+        Some(source_ref_method(quote! {
+            match self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// A borrowed source unsized into `dyn Error` for as long as self is borrowed.
+fn borrowed_source(source_field: &Field, source: TokenStream) -> TokenStream {
+    let error_trait = error_trait();
+    let span = source_field.member.span();
+    if type_is_option(source_field.ty) {
+        quote_spanned!(span=> #source.as_ref()? as &dyn #error_trait)
+    } else {
+        quote_spanned!(span=> #source as &dyn #error_trait)
+    }
+}
+
+fn source_ref_method(body: TokenStream) -> TokenStream {
+    let error_trait = error_trait();
+    quote! {
+        /// The source of this error, including one that borrows data and so
+        /// cannot be returned by `Error::source`.
+        pub fn source_ref(&self) -> ::core::option::Option<&(dyn #error_trait + '_)> {
+            #[allow(unused_imports)]
+            use thiserror::AsDynError;
+            #body
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::ast::Field;
+use crate::expand::type_is_option;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// The value that generated From impls and constructors put in a #[timestamp]
+// field: the current time, as told by the `now()` of the field's type.
+pub(crate) fn now(field: &Field) -> TokenStream {
+    let ty = field.timestamp_type().unwrap();
+    if type_is_option(field.ty) {
+        quote!(::core::option::Option::Some(<#ty>::now()))
+    } else {
+        quote!(<#ty>::now())
+    }
+}
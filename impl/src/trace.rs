@@ -0,0 +1,115 @@
+use crate::ast::{Enum, Struct};
+use crate::attr::Attrs;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::Ident;
+
+impl Struct<'_> {
+    pub(crate) fn log_methods(&self) -> Option<TokenStream> {
+        self.attrs.log?;
+        let level = log_level(&self.attrs, None);
+        Some(log_method(level))
+    }
+}
+
+impl Enum<'_> {
+    pub(crate) fn log_methods(&self) -> Option<TokenStream> {
+        self.attrs.log?;
+        let ty = &self.ident;
+        let arms = self.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let level = log_level(&self.attrs, Some(&variant.attrs));
+            quote!(#ty::#ident {..} => #level,)
+        });
+        let void_deref = if self.variants.is_empty() {
+            Some(quote!(*))
+        } else {
+            None
+        };
+        Some(log_method(quote! {
+            match #void_deref self {
+                #(#arms)*
+            }
+        }))
+    }
+}
+
+// Wraps the expression constructing an error so that it is reported as it
+// is created. With #[error(trace)] on the type, a tracing event is emitted
+// for it, at the level taken from #[level(...)] on the variant, else on the
+// type, else `error`. With #[error(metrics)], its counter is incremented.
+// With #[error(hook)], the hooks registered through thiserror::on_error are
+// run on it.
+pub(crate) fn traced(
+    container: &Attrs,
+    variant: Option<&Attrs>,
+    has_source: bool,
+    construct: TokenStream,
+) -> TokenStream {
+    if container.trace.is_none() && container.metrics.is_none() && container.hook.is_none() {
+        return construct;
+    }
+    let event = container.trace.map(|_| {
+        let level = level(container, variant);
+        let level = Ident::new(&level.to_uppercase(), Span::call_site());
+        let source = if has_source {
+            Some(quote!(source = %thiserror::private::DisplaySource(&__error),))
+        } else {
+            None
+        };
+        quote! {
+            tracing::event!(tracing::Level::#level, #source "{}", __error);
+        }
+    });
+    let count = container.metrics.map(|_| {
+        quote! {
+            thiserror::private::Counted::count(&__error);
+        }
+    });
+    let hook = container.hook.map(|_| {
+        quote! {
+            thiserror::private::run_error_hooks(&__error);
+        }
+    });
+    quote! {
+        {
+            let __error = #construct;
+            #event
+            #count
+            #hook
+            __error
+        }
+    }
+}
+
+fn level(container: &Attrs, variant: Option<&Attrs>) -> String {
+    match variant
+        .and_then(|variant| variant.level.as_ref())
+        .or(container.level.as_ref())
+    {
+        Some(level) => level.to_string(),
+        None => "error".to_owned(),
+    }
+}
+
+fn log_level(container: &Attrs, variant: Option<&Attrs>) -> TokenStream {
+    let level = match level(container, variant).as_str() {
+        "error" => "Error",
+        "warn" => "Warn",
+        "info" => "Info",
+        "debug" => "Debug",
+        _ => "Trace",
+    };
+    let level = Ident::new(level, Span::call_site());
+    quote!(log::Level::#level)
+}
+
+fn log_method(level: TokenStream) -> TokenStream {
+    quote! {
+        /// Logs this error and its chain of sources through the `log` crate.
+        pub fn log(&self) {
+            let level = #level;
+            log::log!(level, "{}", thiserror::private::DisplayChain(self));
+        }
+    }
+}
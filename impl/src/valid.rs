@@ -1,8 +1,12 @@
 use crate::ast::{Enum, Field, Input, Struct, Variant};
-use crate::attr::Attrs;
-use quote::ToTokens;
-use std::collections::BTreeSet as Set;
-use syn::{Error, Member, Result};
+use crate::attr::{Adapter, Attrs, Display, From, FromWrap, Into, MetaValue, Response};
+use crate::case::snake_case;
+use crate::expand::type_is_option;
+use crate::prop::{type_is_ident, type_is_string};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, ToTokens};
+use std::collections::{BTreeMap as Map, BTreeSet as Set};
+use syn::{Attribute, Error, Fields, Generics, Ident, Member, Result, Type};
 
 impl Input<'_> {
     pub(crate) fn validate(&self) -> Result<()> {
@@ -16,21 +20,127 @@ impl Input<'_> {
 impl Struct<'_> {
     fn validate(&self) -> Result<()> {
         check_non_field_attrs(&self.attrs)?;
-        if let Some(transparent) = self.attrs.transparent {
-            if self.fields.len() != 1 {
+        check_response_attrs(&self.attrs)?;
+        check_utoipa_feature(&self.attrs)?;
+        check_jsonrpc_attrs(&self.attrs, &self.fields)?;
+        check_py_feature(&self.attrs)?;
+        check_js_value_feature(&self.attrs)?;
+        check_log_feature(&self.attrs)?;
+        check_metrics_feature(&self.attrs)?;
+        check_diagnostic_feature(&self.attrs)?;
+        check_hook_feature(&self.attrs)?;
+        check_sentry_feature(&self.attrs)?;
+        check_meta_fields(&self.attrs, Some(&self.fields))?;
+        check_defmt_attrs(&self.attrs, None.into_iter())?;
+        check_repr_code_generics(&self.attrs, self.generics)?;
+        if let Some(from_anyhow) = self.attrs.from_anyhow {
+            return Err(Error::new_spanned(
+                from_anyhow,
+                "#[error(from_anyhow)] is only supported on enums",
+            ));
+        }
+        if let Some(from_eyre) = self.attrs.from_eyre {
+            return Err(Error::new_spanned(
+                from_eyre,
+                "#[error(from_eyre)] is only supported on enums",
+            ));
+        }
+        check_eyre_feature(&self.attrs)?;
+        if let Some(accessors) = self.attrs.accessors {
+            return Err(Error::new_spanned(
+                accessors,
+                "#[error(accessors)] is only supported on enums",
+            ));
+        }
+        if let Some(kind) = self.attrs.kind {
+            return Err(Error::new_spanned(
+                kind,
+                "#[error(kind)] is only supported on enums",
+            ));
+        }
+        if let Some(dispatch) = self.attrs.dispatch {
+            return Err(Error::new_spanned(
+                dispatch,
+                "#[error(dispatch)] is only supported on enums",
+            ));
+        }
+        if let Some(constructors) = self.attrs.constructors {
+            return Err(Error::new_spanned(
+                constructors,
+                "#[error(constructors)] is only supported on enums",
+            ));
+        }
+        if let Some(context) = self.attrs.context {
+            return Err(Error::new_spanned(
+                context,
+                "#[error(context)] is only supported on enums",
+            ));
+        }
+        if let Some(errno) = &self.attrs.errno {
+            if errno.value.is_none() {
+                return Err(Error::new_spanned(
+                    errno,
+                    "#[errno(other)] belongs on the enum variant that receives unknown error codes",
+                ));
+            }
+        }
+        check_hresult_feature(&self.attrs)?;
+        if let Some(hresult) = &self.attrs.hresult {
+            if hresult.value.is_none() {
                 return Err(Error::new_spanned(
-                    transparent,
-                    "#[error(transparent)] requires exactly one field",
+                    hresult,
+                    "#[hresult(other)] belongs on the enum variant that receives unknown HRESULTs",
                 ));
             }
-            if let Some(source) = self.fields.iter().filter_map(|f| f.attrs.source).next() {
+        }
+        check_serde_attrs(&self.attrs, self.fields.iter())?;
+        check_variant_only_attrs(&self.attrs)?;
+        if let (Some(from_str), None) = (self.attrs.from_str, &self.attrs.code) {
+            return Err(Error::new_spanned(
+                from_str,
+                "#[error(from_str)] requires a #[code(\"...\")] attribute",
+            ));
+        }
+        if let (Some(code_method), None) = (self.attrs.code_method, &self.attrs.code) {
+            return Err(Error::new_spanned(
+                code_method,
+                "#[error(code)] requires a #[code(\"...\")] attribute",
+            ));
+        }
+        if let Some(into) = &self.attrs.into {
+            check_into_target(into)?;
+            if into.mapping.is_none() {
                 return Err(Error::new_spanned(
-                    source,
-                    "transparent error struct can't contain #[source]",
+                    into,
+                    "#[into(...)] on a struct requires a conversion, such as `kind = ...` or `with = ...`",
                 ));
             }
         }
+        if let Some(transparent) = self.attrs.transparent {
+            check_transparent(transparent, &self.fields, "error struct")?;
+        }
+        if let (Some(deref), None) = (self.attrs.deref, self.attrs.transparent) {
+            return Err(Error::new_spanned(
+                deref,
+                "#[error(deref)] requires #[error(transparent)]",
+            ));
+        }
         check_field_attrs(&self.fields)?;
+        check_clone_sources(&self.attrs, &self.fields)?;
+        check_no_alloc(&self.attrs, &self.attrs, &self.fields)?;
+        check_message_style(&self.attrs, self.attrs.display.iter())?;
+        check_unused_fields(
+            &self.attrs,
+            &self.attrs,
+            &self.fields,
+            &[
+                self.source_field(),
+                self.backtrace_field(),
+                self.span_trace_field(),
+                self.error_id_field(),
+                self.timestamp_field(),
+            ],
+        )?;
         for field in &self.fields {
             field.validate()?;
         }
@@ -41,25 +151,368 @@ impl Struct<'_> {
 impl Enum<'_> {
     fn validate(&self) -> Result<()> {
         check_non_field_attrs(&self.attrs)?;
-        let has_display = self.has_display();
+        check_response_attrs(&self.attrs)?;
+        check_utoipa_feature(&self.attrs)?;
+        check_jsonrpc_attrs(&self.attrs, &[])?;
+        check_py_feature(&self.attrs)?;
+        check_js_value_feature(&self.attrs)?;
+        check_log_feature(&self.attrs)?;
+        check_metrics_feature(&self.attrs)?;
+        check_diagnostic_feature(&self.attrs)?;
+        check_hook_feature(&self.attrs)?;
+        check_sentry_feature(&self.attrs)?;
+        check_meta_fields(&self.attrs, None)?;
+        if let Some(deref) = self.attrs.deref {
+            return Err(Error::new_spanned(
+                deref,
+                "#[error(deref)] is only supported on transparent structs",
+            ));
+        }
+        check_defmt_attrs(
+            &self.attrs,
+            self.variants
+                .iter()
+                .filter_map(|variant| variant.attrs.fuzz_safe),
+        )?;
+        if let Some(errno) = &self.attrs.errno {
+            return Err(Error::new_spanned(
+                errno,
+                "not expected here; the #[errno(...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        check_repr_code_generics(&self.attrs, self.generics)?;
+        check_context_generics(&self.attrs, self.generics)?;
+        if let Some(repr_code) = &self.attrs.repr_code {
+            if repr_code.value.is_some() {
+                return Err(Error::new_spanned(
+                    repr_code,
+                    "not expected here; codes belong on the enum variants, with a bare #[repr_code] on the enum",
+                ));
+            }
+        }
+        let mut repr_codes = Set::new();
+        for (variant, code) in self.variants.iter().zip(self.repr_codes()) {
+            if let Some(repr_code) = &variant.attrs.repr_code {
+                if self.attrs.repr_code.is_none() {
+                    return Err(Error::new_spanned(
+                        repr_code,
+                        "#[repr_code(...)] on a variant requires #[repr_code] on the enum",
+                    ));
+                }
+                if repr_code.value.is_none() {
+                    return Err(Error::new_spanned(
+                        repr_code,
+                        "expected a code, as in #[repr_code(2)]",
+                    ));
+                }
+            }
+            if self.attrs.repr_code.is_some() && !repr_codes.insert(code) {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    format!(
+                        "duplicate #[repr_code] {}; another variant has the same code",
+                        code
+                    ),
+                ));
+            }
+        }
+        check_anyhow_feature(&self.attrs)?;
+        check_eyre_feature(&self.attrs)?;
+        let downcast = match (self.attrs.from_anyhow, self.attrs.from_eyre) {
+            (Some(_), Some(from_eyre)) => {
+                return Err(Error::new_spanned(
+                    from_eyre,
+                    "cannot have both #[error(from_anyhow)] and #[error(from_eyre)]",
+                ));
+            }
+            (Some(from_anyhow), None) => Some((from_anyhow, "from_anyhow")),
+            (None, Some(from_eyre)) => Some((from_eyre, "from_eyre")),
+            (None, None) => None,
+        };
+        let mut fallback = None;
         for variant in &self.variants {
-            variant.validate()?;
-            if has_display && variant.attrs.display.is_none() && variant.attrs.transparent.is_none()
+            if let Some(attr) = variant.attrs.fallback {
+                if downcast.is_none() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "#[error(fallback)] requires #[error(from_anyhow)] or #[error(from_eyre)] on the enum",
+                    ));
+                }
+                if fallback.replace(attr).is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "only one variant can be #[error(fallback)]",
+                    ));
+                }
+            }
+        }
+        if let (Some((attr, name)), None, None) = (downcast, fallback, self.other_variant()) {
+            return Err(Error::new_spanned(
+                attr,
+                format!(
+                    "#[error({})] requires a variant marked #[error(fallback)] or #[error(other)] to receive errors of other types",
+                    name,
+                ),
+            ));
+        }
+        check_hresult_feature(&self.attrs)?;
+        if let Some(hresult) = &self.attrs.hresult {
+            if hresult.value.is_none() {
+                return Err(Error::new_spanned(
+                    hresult,
+                    "#[hresult(other)] belongs on the enum variant that receives unknown HRESULTs",
+                ));
+            }
+        }
+        let mut hresult_other = None;
+        for variant in &self.variants {
+            if let Some(hresult) = &variant.attrs.hresult {
+                if hresult.value.is_none() && hresult_other.replace(hresult).is_some() {
+                    return Err(Error::new_spanned(
+                        hresult,
+                        "only one variant can be #[hresult(other)]",
+                    ));
+                }
+            }
+        }
+        let mut errno_other = None;
+        for variant in &self.variants {
+            if let Some(errno) = &variant.attrs.errno {
+                if errno.value.is_none() && errno_other.replace(errno).is_some() {
+                    return Err(Error::new_spanned(
+                        errno,
+                        "only one variant can be #[errno(other)]",
+                    ));
+                }
+            }
+        }
+        check_serde_attrs(
+            &self.attrs,
+            self.variants.iter().flat_map(|variant| &variant.fields),
+        )?;
+        check_variant_only_attrs(&self.attrs)?;
+        if let Some(code) = &self.attrs.code {
+            return Err(Error::new_spanned(
+                code,
+                "not expected here; the #[code(...)] attribute belongs on top of a struct or an enum variant",
+            ));
+        }
+        let mut codes = Set::new();
+        for variant in &self.variants {
+            if let Some(code) = &variant.attrs.code {
+                if !codes.insert(code.value()) {
+                    return Err(Error::new_spanned(
+                        code,
+                        "duplicate error code; another variant has the same #[code(...)]",
+                    ));
+                }
+            } else if self.attrs.code_method.is_some() {
+                return Err(Error::new_spanned(
+                    &variant.original.ident,
+                    "missing #[code(\"...\")]; #[error(code)] on the enum requires one on every variant",
+                ));
+            }
+        }
+        if let (Some(from_str), true) = (self.attrs.from_str, codes.is_empty()) {
+            return Err(Error::new_spanned(
+                from_str,
+                "#[error(from_str)] requires at least one variant with a #[code(\"...\")] attribute",
+            ));
+        }
+        if let Some(into) = &self.attrs.into {
+            check_into_target(into)?;
+            if into.mapping.is_none() {
+                if let Some(variant) = self
+                    .variants
+                    .iter()
+                    .find(|variant| variant.attrs.into.is_none())
+                {
+                    return Err(Error::new_spanned(
+                        variant.original,
+                        "missing #[into(...)] conversion; add `kind = ...` or `with = ...` here or on the enum",
+                    ));
+                }
+            }
+        } else if let Some(into) = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.into.as_ref())
+            .next()
+        {
+            return Err(Error::new_spanned(
+                into,
+                "#[into(...)] on a variant requires #[into(Type)] on top of the enum",
+            ));
+        }
+        check_no_alloc(&self.attrs, &self.attrs, &[])?;
+        let mut error_id_fields = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.error_id_field());
+        if let Some(first) = error_id_fields.next() {
+            let repr = first.ty.to_token_stream().to_string();
+            if let Some(other) =
+                error_id_fields.find(|field| field.ty.to_token_stream().to_string() != repr)
             {
                 return Err(Error::new_spanned(
-                    variant.original,
-                    "missing #[error(\"...\")] display attribute",
+                    other.ty,
+                    "every #[error_id] field of an enum must have the same type, for error_id() to return",
+                ));
+            }
+        }
+        let has_display = self.has_display();
+        if let Some(dispatch) = self.attrs.dispatch {
+            if !self.generics.params.is_empty() {
+                return Err(Error::new_spanned(
+                    dispatch,
+                    "#[error(dispatch)] is not supported on generic types",
+                ));
+            }
+            if !has_display {
+                return Err(Error::new_spanned(
+                    dispatch,
+                    "#[error(dispatch)] requires a message on every variant, since it generates the Display impl",
                 ));
             }
         }
-        let mut from_types = Set::new();
+        let mut missing_display: Option<Error> = None;
+        for variant in &self.variants {
+            variant.validate()?;
+            check_clone_sources(&self.attrs, &variant.fields)?;
+            check_no_alloc(&self.attrs, &variant.attrs, &variant.fields)?;
+            check_unused_fields(
+                &self.attrs,
+                &variant.attrs,
+                &variant.fields,
+                &[
+                    variant.source_field(),
+                    variant.backtrace_field(),
+                    variant.span_trace_field(),
+                    variant.error_id_field(),
+                    variant.timestamp_field(),
+                    variant.whatever_message_field(),
+                ],
+            )?;
+            if has_display
+                && variant.attrs.display.is_none()
+                && variant.attrs.transparent.is_none()
+                && variant.attrs.message.is_none()
+                && variant.attrs.whatever.is_none()
+            {
+                let error = missing_display_error(variant);
+                match &mut missing_display {
+                    Some(missing_display) => missing_display.combine(error),
+                    None => missing_display = Some(error),
+                }
+            }
+        }
+        if let Some(missing_display) = missing_display {
+            return Err(missing_display);
+        }
+        check_message_style(
+            &self.attrs,
+            self.variants
+                .iter()
+                .filter_map(|variant| variant.attrs.display.as_ref()),
+        )?;
+        let mut messages = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.message);
+        if let (Some(_), Some(message)) = (messages.next(), messages.next()) {
+            return Err(Error::new_spanned(
+                message,
+                "only one variant can be marked #[error(message)]",
+            ));
+        }
+        let mut whatevers = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.whatever);
+        if let (Some(_), Some(whatever)) = (whatevers.next(), whatevers.next()) {
+            return Err(Error::new_spanned(
+                whatever,
+                "only one variant can be marked #[whatever]",
+            ));
+        }
+        let mut others = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.attrs.other);
+        if let (Some(_), Some(other)) = (others.next(), others.next()) {
+            return Err(Error::new_spanned(
+                other,
+                "only one variant can be marked #[error(other)]",
+            ));
+        }
+        let mut from_variants = Map::new();
+        for variant in &self.variants {
+            if let Some(from_field) = variant.from_field() {
+                let from = from_field.attrs.from.as_ref().unwrap();
+                for from_type in from_field.from_types() {
+                    let repr = from_type.to_token_stream().to_string();
+                    if let Some((first, first_from)) = from_variants.insert(repr, (variant, from)) {
+                        return Err(conflicting_from(first, first_from, variant, from));
+                    }
+                }
+            }
+        }
+        let from_impls = self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.from_field())
+            .map(|from_field| from_field.from_types().len())
+            .sum::<usize>()
+            + self
+                .variants
+                .iter()
+                .filter(|variant| {
+                    variant.attrs.from_match.is_some() || variant.attrs.from_parts.is_some()
+                })
+                .count();
         for variant in &self.variants {
             if let Some(from_field) = variant.from_field() {
-                let repr = from_field.ty.to_token_stream().to_string();
-                if !from_types.insert(repr) {
+                let generic = from_field.from_types().into_iter().any(|from_type| {
+                    self.generics
+                        .type_params()
+                        .any(|param| type_is_ident(from_type, &param.ident))
+                });
+                if generic && from_impls > 1 {
                     return Err(Error::new_spanned(
                         from_field.original,
-                        "cannot derive From because another variant has the same source type",
+                        "cannot derive From for a generic type parameter alongside other #[from] conversions, since the impls would overlap",
+                    ));
+                }
+            }
+        }
+        let mut try_into_types = Set::new();
+        for variant in &self.variants {
+            if let Some(try_into) = variant.attrs.try_into {
+                let field = match variant.try_into_field() {
+                    Some(field) => field,
+                    None => {
+                        return Err(Error::new_spanned(
+                            try_into,
+                            "#[try_into] requires a source field or exactly one field",
+                        ));
+                    }
+                };
+                let repr = field.ty.to_token_stream().to_string();
+                if !try_into_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        try_into,
+                        "cannot derive TryFrom because another variant has the same field type",
+                    ));
+                }
+            }
+        }
+        for variant in &self.variants {
+            if let Some(from_match) = &variant.attrs.from_match {
+                let repr = from_match.ty.to_token_stream().to_string();
+                if from_variants.contains_key(&repr) {
+                    return Err(Error::new_spanned(
+                        from_match,
+                        "cannot derive From because another variant has #[from] with the same source type",
                     ));
                 }
             }
@@ -68,23 +521,152 @@ impl Enum<'_> {
     }
 }
 
+// Both variants are pointed at, since either one may be the #[from] to drop.
+fn conflicting_from(
+    first: &Variant,
+    first_from: &From,
+    second: &Variant,
+    second_from: &From,
+) -> Error {
+    let mut error = Error::new_spanned(
+        second_from.original,
+        format!(
+            "cannot derive From because variant `{}` already has #[from] with the same source type; drop one of the #[from] attributes, or pick the variant by matching on the error with #[from_match(Type, pattern)] on each of them",
+            first.ident,
+        ),
+    );
+    error.combine(Error::new_spanned(
+        first_from.original,
+        format!(
+            "variant `{}` also has #[from] with this source type",
+            second.ident,
+        ),
+    ));
+    error
+}
+
 impl Variant<'_> {
     fn validate(&self) -> Result<()> {
         check_non_field_attrs(&self.attrs)?;
-        if self.attrs.transparent.is_some() {
+        check_jsonrpc_attrs(&self.attrs, &self.fields)?;
+        check_py_feature(&self.attrs)?;
+        check_sentry_feature(&self.attrs)?;
+        check_meta_fields(&self.attrs, Some(&self.fields))?;
+        check_container_only_attrs(&self.attrs)?;
+        if let Some(fallback) = self.attrs.fallback {
+            if self.fields.len() != 1 {
+                return Err(Error::new_spanned(
+                    fallback,
+                    "#[error(fallback)] requires exactly one field, holding the anyhow::Error or eyre::Report",
+                ));
+            }
+            if let Some(from) = &self.fields[0].attrs.from {
+                return Err(Error::new_spanned(
+                    from.original,
+                    "the #[error(fallback)] variant already receives errors of other types and cannot also be #[from]",
+                ));
+            }
+        }
+        if let (Some(builder), false) = (
+            self.attrs.builder,
+            matches!(self.original.fields, Fields::Named(_)),
+        ) {
+            return Err(Error::new_spanned(
+                builder,
+                "#[error(builder)] requires a variant with named fields",
+            ));
+        }
+        check_hresult_feature(&self.attrs)?;
+        if let Some(hresult) = &self.attrs.hresult {
+            if hresult.value.is_none() && self.fields.len() != 1 {
+                return Err(Error::new_spanned(
+                    hresult,
+                    "#[hresult(other)] requires exactly one field, holding the windows::core::Error",
+                ));
+            }
+        }
+        if let Some(errno) = &self.attrs.errno {
+            if errno.value.is_none() && self.fields.len() != 1 {
+                return Err(Error::new_spanned(
+                    errno,
+                    "#[errno(other)] requires exactly one field, holding the i32 error code",
+                ));
+            }
+        }
+        if let Some(into) = &self.attrs.into {
+            if let Some(target) = &into.target {
+                return Err(Error::new_spanned(
+                    target,
+                    "not expected here; the target type of #[into(...)] belongs on top of the enum",
+                ));
+            }
+        }
+        if let Some(message) = self.attrs.message {
+            if self.fields.len() != 1 || !type_is_string(self.fields[0].ty) {
+                return Err(Error::new_spanned(
+                    message,
+                    "#[error(message)] requires a variant with exactly one field of type String",
+                ));
+            }
+            if self.attrs.transparent.is_some() {
+                return Err(Error::new_spanned(
+                    message,
+                    "cannot have both #[error(transparent)] and #[error(message)]",
+                ));
+            }
+        }
+        if let Some(whatever) = self.attrs.whatever {
+            let is_whatever_field = |field: &Field| match &field.member {
+                Member::Named(ident) if ident == "message" => type_is_string(field.ty),
+                Member::Named(ident) if ident == "source" => type_is_option(field.ty),
+                _ => false,
+            };
+            if self.fields.len() != 2 || !self.fields.iter().all(is_whatever_field) {
+                return Err(Error::new_spanned(
+                    whatever,
+                    "#[whatever] requires a variant with fields `message: String` and `source: Option<Box<dyn Error + Send + Sync>>`",
+                ));
+            }
+            if self.attrs.transparent.is_some() {
+                return Err(Error::new_spanned(
+                    whatever,
+                    "cannot have both #[error(transparent)] and #[whatever]",
+                ));
+            }
+        }
+        if let Some(other) = self.attrs.other {
             if self.fields.len() != 1 {
                 return Err(Error::new_spanned(
-                    self.original,
-                    "#[error(transparent)] requires exactly one field",
+                    other,
+                    "#[error(other)] requires exactly one field, holding a Box<dyn Error + Send + Sync>",
+                ));
+            }
+        }
+        if let Some(from_parts) = self.attrs.from_parts {
+            if self.fields.len() < 2 {
+                return Err(Error::new_spanned(
+                    from_parts,
+                    "#[from_parts] requires a variant with at least two fields; use #[from] on a single field instead",
+                ));
+            }
+        }
+        if let Some(from_match) = &self.attrs.from_match {
+            if self.from_field().is_some() {
+                return Err(Error::new_spanned(
+                    from_match,
+                    "#[from_match] cannot be combined with a #[from] field",
                 ));
             }
-            if let Some(source) = self.fields.iter().filter_map(|f| f.attrs.source).next() {
+            if self.source_field().is_none() {
                 return Err(Error::new_spanned(
-                    source,
-                    "transparent variant can't contain #[source]",
+                    from_match,
+                    "#[from_match] requires a source field to store the matched error",
                 ));
             }
         }
+        if let Some(transparent) = self.attrs.transparent {
+            check_transparent(transparent, &self.fields, "variant")?;
+        }
         check_field_attrs(&self.fields)?;
         for field in &self.fields {
             field.validate()?;
@@ -95,91 +677,1334 @@ impl Variant<'_> {
 
 impl Field<'_> {
     fn validate(&self) -> Result<()> {
+        if let Some(error) = &self.attrs.display_with_error {
+            return Err(error.clone());
+        }
         if let Some(display) = &self.attrs.display {
             return Err(Error::new_spanned(
                 display.original,
                 "not expected here; the #[error(...)] attribute belongs on top of a struct or an enum variant",
             ));
         }
-        Ok(())
-    }
-}
-
-fn check_non_field_attrs(attrs: &Attrs) -> Result<()> {
-    if let Some(from) = &attrs.from {
-        return Err(Error::new_spanned(
-            from,
-            "not expected here; the #[from] attribute belongs on a specific field",
-        ));
-    }
-    if let Some(source) = &attrs.source {
-        return Err(Error::new_spanned(
-            source,
-            "not expected here; the #[source] attribute belongs on a specific field",
-        ));
-    }
-    if let Some(backtrace) = &attrs.backtrace {
-        return Err(Error::new_spanned(
-            backtrace,
-            "not expected here; the #[backtrace] attribute belongs on a specific field",
-        ));
-    }
-    if let Some(display) = &attrs.display {
-        if attrs.transparent.is_some() {
+        check_container_only_attrs(&self.attrs)?;
+        check_variant_only_attrs(&self.attrs)?;
+        if let Some(code) = &self.attrs.code {
             return Err(Error::new_spanned(
-                display.original,
-                "cannot have both #[error(transparent)] and a display attribute",
+                code,
+                "not expected here; the #[code(...)] attribute belongs on top of a struct or an enum variant",
             ));
         }
-    }
-    Ok(())
-}
-
-fn check_field_attrs(fields: &[Field]) -> Result<()> {
-    let mut from_field = None;
-    let mut source_field = None;
-    let mut backtrace_field = None;
-    let mut has_backtrace = false;
-    for field in fields {
-        if let Some(from) = field.attrs.from {
-            if from_field.is_some() {
-                return Err(Error::new_spanned(from, "duplicate #[from] attribute"));
-            }
-            from_field = Some(field);
+        if let Some(exit_code) = &self.attrs.exit_code {
+            return Err(Error::new_spanned(
+                exit_code,
+                "not expected here; the #[exit_code(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
         }
-        if let Some(source) = field.attrs.source {
-            if source_field.is_some() {
-                return Err(Error::new_spanned(source, "duplicate #[source] attribute"));
-            }
-            source_field = Some(field);
+        if let Some(errno) = &self.attrs.errno {
+            return Err(Error::new_spanned(
+                errno,
+                "not expected here; the #[errno(...)] attribute belongs on top of a struct or an enum variant",
+            ));
         }
-        if let Some(backtrace) = field.attrs.backtrace {
-            if backtrace_field.is_some() {
+        if let Some(hresult) = &self.attrs.hresult {
+            return Err(Error::new_spanned(
+                hresult,
+                "not expected here; the #[hresult(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(sentry) = &self.attrs.sentry {
+            return Err(Error::new_spanned(
+                sentry,
+                "not expected here; the #[sentry(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(meta) = &self.attrs.meta {
+            return Err(Error::new_spanned(
+                meta,
+                "not expected here; the #[meta(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(repr_code) = &self.attrs.repr_code {
+            return Err(Error::new_spanned(
+                repr_code,
+                "not expected here; the #[repr_code] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(py) = &self.attrs.py {
+            return Err(Error::new_spanned(
+                py,
+                "not expected here; the #[py(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(level) = &self.attrs.level {
+            return Err(Error::new_spanned(
+                level,
+                "not expected here; the #[level(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(severity) = &self.attrs.severity {
+            return Err(Error::new_spanned(
+                severity,
+                "not expected here; the #[severity(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(help) = &self.attrs.help {
+            return Err(Error::new_spanned(
+                help.original,
+                "not expected here; the #[help(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(retryable) = &self.attrs.retryable {
+            return Err(Error::new_spanned(
+                retryable.original,
+                "not expected here; the #[retryable] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(jsonrpc) = &self.attrs.jsonrpc {
+            return Err(Error::new_spanned(
+                jsonrpc,
+                "not expected here; the #[jsonrpc(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(into) = &self.attrs.into {
+            return Err(Error::new_spanned(
+                into,
+                "not expected here; the #[into(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(fuzz_safe) = self.attrs.fuzz_safe {
+            return Err(Error::new_spanned(
+                fuzz_safe,
+                "not expected here; the #[error(fuzz_safe)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(status) = &self.attrs.status {
+            return Err(Error::new_spanned(
+                status,
+                "not expected here; the #[status(...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        if let Some(expose) = &self.attrs.expose {
+            return Err(Error::new_spanned(
+                expose,
+                "not expected here; the #[error(expose = ...)] attribute belongs on top of a struct, an enum, or an enum variant",
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Every illegal combination is reported at once, each at the attribute or
+// field that has to go, since fixing them one build at a time is tedious.
+fn check_transparent(transparent: &Attribute, fields: &[Field], kind: &str) -> Result<()> {
+    let mut errors: Option<Error> = None;
+    let mut push = |error: Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+    match fields.len() {
+        0 => push(Error::new_spanned(
+            transparent,
+            "#[error(transparent)] requires exactly one field, the error to forward to",
+        )),
+        1 => {}
+        _ => {
+            push(Error::new_spanned(
+                transparent,
+                "#[error(transparent)] requires exactly one field",
+            ));
+            for field in &fields[1..] {
+                push(Error::new_spanned(
+                    field.original,
+                    format!(
+                        "transparent {} forwards Display and source() to its first field; remove this field or drop #[error(transparent)]",
+                        kind,
+                    ),
+                ));
+            }
+        }
+    }
+    for field in fields {
+        if let Some(source) = field.attrs.source {
+            push(Error::new_spanned(
+                source,
+                format!("transparent {} can't contain #[source]", kind),
+            ));
+        }
+        if let Some(backtrace) = field.attrs.backtrace {
+            push(Error::new_spanned(
+                backtrace,
+                format!(
+                    "transparent {} can't contain #[backtrace]; the backtrace of the underlying error is forwarded as is",
+                    kind,
+                ),
+            ));
+        }
+        if let Some(sensitive) = &field.attrs.sensitive {
+            push(Error::new_spanned(
+                sensitive.original,
+                format!(
+                    "transparent {} can't contain #[sensitive]; the message of the underlying error is forwarded as is",
+                    kind,
+                ),
+            ));
+        }
+        if let Some(display_with) = &field.attrs.display_with {
+            push(Error::new_spanned(
+                display_with.original,
+                format!(
+                    "transparent {} can't contain #[display(...)]; the message of the underlying error is forwarded as is",
+                    kind,
+                ),
+            ));
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
+
+fn check_non_field_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(from) = &attrs.from {
+        return Err(Error::new_spanned(
+            from,
+            "not expected here; the #[from] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(from_default) = attrs.from_default {
+        return Err(Error::new_spanned(
+            from_default,
+            "not expected here; the #[from(default)] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(source) = &attrs.source {
+        return Err(Error::new_spanned(
+            source,
+            "not expected here; the #[source] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(backtrace) = &attrs.backtrace {
+        return Err(Error::new_spanned(
+            backtrace,
+            "not expected here; the #[backtrace] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(sensitive) = &attrs.sensitive {
+        return Err(Error::new_spanned(
+            sensitive.original,
+            "not expected here; the #[sensitive] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(display_with) = &attrs.display_with {
+        return Err(Error::new_spanned(
+            display_with.original,
+            "not expected here; the #[display(...)] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(error_id) = attrs.error_id {
+        return Err(Error::new_spanned(
+            error_id,
+            "not expected here; the #[error_id] attribute belongs on a specific field",
+        ));
+    }
+    if let Some(timestamp) = attrs.timestamp {
+        return Err(Error::new_spanned(
+            timestamp,
+            "not expected here; the #[timestamp] attribute belongs on a specific field",
+        ));
+    }
+    if let (Some(_), Some(cold)) = (attrs.inline, attrs.cold) {
+        return Err(Error::new_spanned(
+            cold,
+            "cannot have both #[error(inline)] and #[error(cold)]",
+        ));
+    }
+    if let Some(display) = &attrs.display {
+        if attrs.transparent.is_some() {
+            return Err(Error::new_spanned(
+                display.original,
+                "cannot have both #[error(transparent)] and a display attribute",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_into_target(into: &Into) -> Result<()> {
+    if into.target.is_none() {
+        return Err(Error::new_spanned(
+            into,
+            "expected a target type, as in #[into(std::io::Error, kind = ...)]",
+        ));
+    }
+    Ok(())
+}
+
+fn check_variant_only_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(fallback) = attrs.fallback {
+        return Err(Error::new_spanned(
+            fallback,
+            "not expected here; the #[error(fallback)] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(builder) = attrs.builder {
+        return Err(Error::new_spanned(
+            builder,
+            "not expected here; the #[error(builder)] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(whatever) = attrs.whatever {
+        return Err(Error::new_spanned(
+            whatever,
+            "not expected here; the #[whatever] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(other) = attrs.other {
+        return Err(Error::new_spanned(
+            other,
+            "not expected here; the #[error(other)] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(from_match) = &attrs.from_match {
+        return Err(Error::new_spanned(
+            from_match,
+            "not expected here; the #[from_match] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(from_parts) = attrs.from_parts {
+        return Err(Error::new_spanned(
+            from_parts,
+            "not expected here; the #[from_parts] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(message) = attrs.message {
+        return Err(Error::new_spanned(
+            message,
+            "not expected here; the #[error(message)] attribute belongs on top of an enum variant",
+        ));
+    }
+    if let Some(try_into) = attrs.try_into {
+        return Err(Error::new_spanned(
+            try_into,
+            "not expected here; the #[try_into] attribute belongs on top of an enum variant",
+        ));
+    }
+    Ok(())
+}
+
+fn check_response_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(into_response) = &attrs.into_response {
+        if !cfg!(feature = "axum") {
+            return Err(Error::new_spanned(
+                into_response,
+                "#[error(into_response)] requires the \"axum\" feature of thiserror",
+            ));
+        }
+        if let Some(with) = &into_response.with {
+            return Err(Error::new_spanned(
+                with,
+                "#[error(into_response)] does not take a response builder; implement IntoResponse by hand instead",
+            ));
+        }
+        check_json_body(into_response)?;
+    }
+    if let Some(response_error) = &attrs.response_error {
+        if !cfg!(feature = "actix") {
+            return Err(Error::new_spanned(
+                response_error,
+                "#[error(response_error)] requires the \"actix\" feature of thiserror",
+            ));
+        }
+        check_json_body(response_error)?;
+    }
+    Ok(())
+}
+
+fn check_jsonrpc_attrs(attrs: &Attrs, fields: &[Field]) -> Result<()> {
+    let jsonrpc = match &attrs.jsonrpc {
+        Some(jsonrpc) => jsonrpc,
+        None => return Ok(()),
+    };
+    if let Some(data) = &jsonrpc.data {
+        if !fields.iter().any(|field| field.member == *data) {
+            return Err(Error::new_spanned(
+                data,
+                "#[jsonrpc(data = ...)] must name a field of this struct or variant",
+            ));
+        }
+    }
+    if !cfg!(feature = "jsonrpc") {
+        return Err(Error::new_spanned(
+            jsonrpc,
+            "#[jsonrpc(...)] requires the \"jsonrpc\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_py_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(py), false) = (&attrs.py, cfg!(feature = "pyo3")) {
+        return Err(Error::new_spanned(
+            py,
+            "#[py(...)] requires the \"pyo3\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_js_value_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(js_value), false) = (attrs.js_value, cfg!(feature = "wasm")) {
+        return Err(Error::new_spanned(
+            js_value,
+            "#[error(js_value)] requires the \"wasm\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_log_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(log), false) = (attrs.log, cfg!(feature = "log")) {
+        return Err(Error::new_spanned(
+            log,
+            "#[error(log)] requires the \"log\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_metrics_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(metrics), false) = (attrs.metrics, cfg!(feature = "metrics")) {
+        return Err(Error::new_spanned(
+            metrics,
+            "#[error(metrics)] requires the \"metrics\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_diagnostic_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(diagnostic), false) = (attrs.derive_diagnostic, cfg!(feature = "miette")) {
+        return Err(Error::new_spanned(
+            diagnostic,
+            "#[error(diagnostic)] requires the \"miette\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_hook_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(hook), false) = (attrs.hook, cfg!(feature = "std")) {
+        return Err(Error::new_spanned(
+            hook,
+            "#[error(hook)] requires the \"std\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_anyhow_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(from_anyhow), false) = (attrs.from_anyhow, cfg!(feature = "anyhow")) {
+        return Err(Error::new_spanned(
+            from_anyhow,
+            "#[error(from_anyhow)] requires the \"anyhow\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_eyre_feature(attrs: &Attrs) -> Result<()> {
+    if cfg!(feature = "eyre") {
+        return Ok(());
+    }
+    if let Some(from_eyre) = attrs.from_eyre {
+        return Err(Error::new_spanned(
+            from_eyre,
+            "#[error(from_eyre)] requires the \"eyre\" feature of thiserror",
+        ));
+    }
+    if let Some(into_report) = attrs.into_report {
+        return Err(Error::new_spanned(
+            into_report,
+            "#[error(into_report)] requires the \"eyre\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_hresult_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(hresult), false) = (&attrs.hresult, cfg!(feature = "windows")) {
+        return Err(Error::new_spanned(
+            hresult,
+            "#[hresult(...)] requires the \"windows\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+// Fields are only in scope on a struct or a variant. The enum itself holds
+// literals shared by all of its variants.
+fn check_meta_fields(attrs: &Attrs, fields: Option<&[Field]>) -> Result<()> {
+    let meta = match &attrs.meta {
+        Some(meta) => meta,
+        None => return Ok(()),
+    };
+    for (key, value) in &meta.entries {
+        let member = match value {
+            MetaValue::Field(member) => member,
+            MetaValue::Lit(_) => continue,
+        };
+        match fields {
+            None => {
+                return Err(Error::new_spanned(
+                    key,
+                    "#[meta(...)] on top of an enum can only hold literals; refer to fields from the variants instead",
+                ));
+            }
+            Some(fields) if !fields.iter().any(|field| field.member == *member) => {
+                return Err(Error::new_spanned(member, "no such field"));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn check_sentry_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(sentry), false) = (&attrs.sentry, cfg!(feature = "sentry")) {
+        return Err(Error::new_spanned(
+            sentry,
+            "#[sentry(...)] requires the \"sentry\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_defmt_attrs<'a>(
+    attrs: &Attrs,
+    mut fuzz_safe: impl Iterator<Item = &'a Attribute>,
+) -> Result<()> {
+    let defmt = match attrs.defmt {
+        Some(defmt) => defmt,
+        None => return Ok(()),
+    };
+    if !cfg!(feature = "defmt") {
+        return Err(Error::new_spanned(
+            defmt,
+            "#[error(defmt)] requires the \"defmt\" feature of thiserror",
+        ));
+    }
+    if let Some(fuzz_safe) = attrs.fuzz_safe.or_else(|| fuzz_safe.next()) {
+        return Err(Error::new_spanned(
+            fuzz_safe,
+            "#[error(fuzz_safe)] messages cannot be formatted by defmt",
+        ));
+    }
+    Ok(())
+}
+
+// The extern "C" accessors are free functions naming the concrete type, which
+// a generic type does not have.
+fn check_repr_code_generics(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(repr_code) = &attrs.repr_code {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                repr_code,
+                "#[repr_code] is not supported on generic types",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_context_generics(attrs: &Attrs, generics: &Generics) -> Result<()> {
+    if let Some(context) = attrs.context {
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                context,
+                "#[error(context)] is not supported on generic types",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_utoipa_feature(attrs: &Attrs) -> Result<()> {
+    if let (Some(into_responses), false) = (attrs.into_responses, cfg!(feature = "utoipa")) {
+        return Err(Error::new_spanned(
+            into_responses,
+            "#[error(into_responses)] requires the \"utoipa\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_json_body(response: &Response) -> Result<()> {
+    if let (Some(json), false) = (&response.json, cfg!(feature = "serde")) {
+        return Err(Error::new_spanned(
+            json,
+            "a JSON response body requires the \"serde\" feature of thiserror",
+        ));
+    }
+    Ok(())
+}
+
+fn check_serde_attrs<'a>(
+    attrs: &Attrs,
+    mut fields: impl Iterator<Item = &'a Field<'a>>,
+) -> Result<()> {
+    if let (Some(serialize), false) = (attrs.serialize, cfg!(feature = "serde")) {
+        return Err(Error::new_spanned(
+            serialize,
+            "#[error(serialize)] requires the \"serde\" feature of thiserror",
+        ));
+    }
+    if let Some(json_schema) = attrs.json_schema {
+        if attrs.serialize.is_none() {
+            return Err(Error::new_spanned(
+                json_schema,
+                "#[error(json_schema)] describes the layout written by #[error(serialize)] and requires it",
+            ));
+        }
+        if !cfg!(feature = "schemars") {
+            return Err(Error::new_spanned(
+                json_schema,
+                "#[error(json_schema)] requires the \"schemars\" feature of thiserror",
+            ));
+        }
+    }
+    let deserialize = match attrs.deserialize {
+        Some(deserialize) => deserialize,
+        None => return Ok(()),
+    };
+    if !cfg!(feature = "serde") {
+        return Err(Error::new_spanned(
+            deserialize,
+            "#[error(deserialize)] requires the \"serde\" feature of thiserror",
+        ));
+    }
+    if attrs.serialize.is_some() {
+        return Err(Error::new_spanned(
+            deserialize,
+            "#[error(deserialize)] reads the fields of the error and cannot read back the message written by #[error(serialize)]; derive serde::Serialize instead",
+        ));
+    }
+    if let Some(field) = fields.find(|field| {
+        field.attrs.backtrace.is_some()
+            || field.is_backtrace()
+            || field.span_trace_type().is_some()
+            || type_contains_dyn(field.ty)
+    }) {
+        return Err(Error::new_spanned(
+            field.original,
+            "#[error(deserialize)] requires plain data fields; backtraces, span traces and trait objects cannot be deserialized",
+        ));
+    }
+    Ok(())
+}
+
+fn type_contains_dyn(ty: &Type) -> bool {
+    fn tokens_contain_dyn(tokens: TokenStream) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "dyn",
+            TokenTree::Group(group) => tokens_contain_dyn(group.stream()),
+            _ => false,
+        })
+    }
+    tokens_contain_dyn(ty.to_token_stream())
+}
+
+// The Rust API guidelines' conventions for error messages: lowercase, without
+// trailing punctuation. A message starting with an acronym such as "HTTP" or
+// with a placeholder is fine.
+fn check_message_style<'a>(
+    attrs: &Attrs,
+    displays: impl Iterator<Item = &'a Display<'a>>,
+) -> Result<()> {
+    let style = match &attrs.style {
+        Some(style) => style,
+        None => return Ok(()),
+    };
+    let mut violations: Option<Error> = None;
+    let mut seen: Vec<&Attribute> = Vec::new();
+    for display in displays {
+        // An enum-level message is shared by the variants without their own.
+        if seen
+            .iter()
+            .any(|seen| core::ptr::eq(*seen, display.original))
+        {
+            continue;
+        }
+        seen.push(display.original);
+        let template = &display.template;
+        let message = template.value();
+        let mut chars = message.chars();
+        let starts_capitalized = match (chars.next(), chars.next()) {
+            (Some(first), Some(second)) => first.is_uppercase() && !second.is_uppercase(),
+            (Some(first), None) => first.is_uppercase(),
+            _ => false,
+        };
+        let ends_with_period = message.ends_with('.') && !message.ends_with("...");
+        let mut errors = Vec::new();
+        if style.lowercase_start && starts_capitalized {
+            errors.push(Error::new_spanned(
+                template,
+                "error messages start with a lowercase letter, as required by #[error(style(lowercase_start))]",
+            ));
+        }
+        if style.no_trailing_period && ends_with_period {
+            errors.push(Error::new_spanned(
+                template,
+                "error messages do not end with a period, as required by #[error(style(no_trailing_period))]",
+            ));
+        }
+        for error in errors {
+            match &mut violations {
+                Some(violations) => violations.combine(error),
+                None => violations = Some(error),
+            }
+        }
+    }
+    match violations {
+        Some(violations) => Err(violations),
+        None => Ok(()),
+    }
+}
+
+// Fields that the message never interpolates and that are neither source nor
+// backtrace go unseen. A leading underscore marks a field as unused on purpose.
+fn check_unused_fields(
+    container: &Attrs,
+    attrs: &Attrs,
+    fields: &[Field],
+    used: &[Option<&Field>],
+) -> Result<()> {
+    if container.deny_unused.is_none() || attrs.transparent.is_some() || attrs.message.is_some() {
+        return Ok(());
+    }
+    let mut displayed = Vec::new();
+    let displays = attrs.display.iter().chain(&attrs.help).chain(
+        attrs
+            .diagnostic
+            .iter()
+            .flat_map(|diagnostic| diagnostic.help.iter().chain(&diagnostic.url)),
+    );
+    for display in displays {
+        displayed.push(display.args.clone());
+    }
+    let mut unused: Option<Error> = None;
+    for field in fields {
+        if used
+            .iter()
+            .flatten()
+            .any(|used| used.member == field.member)
+        {
+            continue;
+        }
+        let (local, placeholder) = match &field.member {
+            Member::Named(ident) => (ident.clone(), ident.to_string()),
+            Member::Unnamed(index) => (format_ident!("_{}", index.index), index.index.to_string()),
+        };
+        if matches!(&field.member, Member::Named(ident) if ident.to_string().starts_with('_')) {
+            continue;
+        }
+        if displayed
+            .iter()
+            .any(|args| tokens_contain(args.clone(), &local))
+        {
+            continue;
+        }
+        let rename = match &field.member {
+            Member::Named(ident) => {
+                format!(", or rename it to `_{}` if it is unused on purpose", ident)
+            }
+            Member::Unnamed(_) => String::new(),
+        };
+        let error = Error::new_spanned(
+            field.original,
+            format!(
+                "field is not shown by the message, nor used as source or backtrace; interpolate it as {{{}}}{}",
+                placeholder, rename,
+            ),
+        );
+        match &mut unused {
+            Some(unused) => unused.combine(error),
+            None => unused = Some(error),
+        }
+    }
+    match unused {
+        Some(unused) => Err(unused),
+        None => Ok(()),
+    }
+}
+
+fn tokens_contain(tokens: TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(token) => token == *ident,
+        TokenTree::Group(group) => tokens_contain(group.stream(), ident),
+        _ => false,
+    })
+}
+
+// Reported on every variant that lacks a message, rather than only the first,
+// with an attribute to paste above it that interpolates one of its fields.
+fn missing_display_error(variant: &Variant) -> Error {
+    let ident = &variant.ident;
+    let mut example = snake_case(&ident.to_string()).replace('_', " ");
+    match variant.fields.first().map(|field| &field.member) {
+        Some(Member::Named(field)) => example += &format!(": {{{}}}", field),
+        Some(Member::Unnamed(_)) => example += ": {0}",
+        None => {}
+    }
+    let transparent = if variant.fields.len() == 1 && variant.source_field().is_some() {
+        ", or #[error(transparent)] to display its only field as is"
+    } else {
+        ""
+    };
+    Error::new_spanned(
+        variant.original,
+        format!(
+            "missing #[error(\"...\")] display attribute on variant `{}`; add one above it, such as #[error(\"{}\")]{}",
+            ident, example, transparent,
+        ),
+    )
+}
+
+fn check_container_only_attrs(attrs: &Attrs) -> Result<()> {
+    if let Some(chain) = attrs.chain {
+        return Err(Error::new_spanned(
+            chain,
+            "not expected here; the #[error(chain)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(from_str) = attrs.from_str {
+        return Err(Error::new_spanned(
+            from_str,
+            "not expected here; the #[error(from_str)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(serialize) = attrs.serialize {
+        return Err(Error::new_spanned(
+            serialize,
+            "not expected here; the #[error(serialize)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(deserialize) = attrs.deserialize {
+        return Err(Error::new_spanned(
+            deserialize,
+            "not expected here; the #[error(deserialize)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(into_responses) = attrs.into_responses {
+        return Err(Error::new_spanned(
+            into_responses,
+            "not expected here; the #[error(into_responses)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(name) = attrs.name {
+        return Err(Error::new_spanned(
+            name,
+            "not expected here; the #[error(name)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(macros) = attrs.macros {
+        return Err(Error::new_spanned(
+            macros,
+            "not expected here; the #[error(macros)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(deref) = attrs.deref {
+        return Err(Error::new_spanned(
+            deref,
+            "not expected here; the #[error(deref)] attribute belongs on top of a struct",
+        ));
+    }
+    if let Some(style) = &attrs.style {
+        return Err(Error::new_spanned(
+            style.original,
+            "not expected here; the #[error(style(...))] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(deny_unused) = attrs.deny_unused {
+        return Err(Error::new_spanned(
+            deny_unused,
+            "not expected here; the #[error(deny_unused)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(dispatch) = attrs.dispatch {
+        return Err(Error::new_spanned(
+            dispatch,
+            "not expected here; the #[error(dispatch)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(inline) = attrs.inline {
+        return Err(Error::new_spanned(
+            inline,
+            "not expected here; the #[error(inline)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(cold) = attrs.cold {
+        return Err(Error::new_spanned(
+            cold,
+            "not expected here; the #[error(cold)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(bound) = &attrs.bound {
+        return Err(Error::new_spanned(
+            bound.original,
+            "not expected here; the #[error(bound = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(custom_trait) = &attrs.custom_trait {
+        return Err(Error::new_spanned(
+            custom_trait,
+            "not expected here; the #[error(trait = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(crate_path) = &attrs.crate_path {
+        return Err(Error::new_spanned(
+            crate_path,
+            "not expected here; the #[error(crate = ...)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(no_alloc) = attrs.no_alloc {
+        return Err(Error::new_spanned(
+            no_alloc,
+            "not expected here; the #[error(no_alloc)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(clone) = attrs.clone {
+        return Err(Error::new_spanned(
+            clone,
+            "not expected here; the #[error(clone)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(partial_eq) = attrs.partial_eq {
+        return Err(Error::new_spanned(
+            partial_eq,
+            "not expected here; the #[error(partial_eq)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(describe) = attrs.describe {
+        return Err(Error::new_spanned(
+            describe,
+            "not expected here; the #[error(describe)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(catalog) = attrs.catalog {
+        return Err(Error::new_spanned(
+            catalog,
+            "not expected here; the #[error(catalog)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(json_schema) = attrs.json_schema {
+        return Err(Error::new_spanned(
+            json_schema,
+            "not expected here; the #[error(json_schema)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(owned) = attrs.owned {
+        return Err(Error::new_spanned(
+            owned,
+            "not expected here; the #[error(owned)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(into_response) = &attrs.into_response {
+        return Err(Error::new_spanned(
+            into_response,
+            "not expected here; the #[error(into_response)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(response_error) = &attrs.response_error {
+        return Err(Error::new_spanned(
+            response_error,
+            "not expected here; the #[error(response_error)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(js_value) = attrs.js_value {
+        return Err(Error::new_spanned(
+            js_value,
+            "not expected here; the #[error(js_value)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(trace) = attrs.trace {
+        return Err(Error::new_spanned(
+            trace,
+            "not expected here; the #[error(trace)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(hook) = attrs.hook {
+        return Err(Error::new_spanned(
+            hook,
+            "not expected here; the #[error(hook)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(log) = attrs.log {
+        return Err(Error::new_spanned(
+            log,
+            "not expected here; the #[error(log)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(metrics) = attrs.metrics {
+        return Err(Error::new_spanned(
+            metrics,
+            "not expected here; the #[error(metrics)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(diagnostic) = attrs.derive_diagnostic {
+        return Err(Error::new_spanned(
+            diagnostic,
+            "not expected here; the #[error(diagnostic)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(defmt) = attrs.defmt {
+        return Err(Error::new_spanned(
+            defmt,
+            "not expected here; the #[error(defmt)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(from_anyhow) = attrs.from_anyhow {
+        return Err(Error::new_spanned(
+            from_anyhow,
+            "not expected here; the #[error(from_anyhow)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(from_eyre) = attrs.from_eyre {
+        return Err(Error::new_spanned(
+            from_eyre,
+            "not expected here; the #[error(from_eyre)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(into_report) = attrs.into_report {
+        return Err(Error::new_spanned(
+            into_report,
+            "not expected here; the #[error(into_report)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    if let Some(accessors) = attrs.accessors {
+        return Err(Error::new_spanned(
+            accessors,
+            "not expected here; the #[error(accessors)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(kind) = attrs.kind {
+        return Err(Error::new_spanned(
+            kind,
+            "not expected here; the #[error(kind)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(constructors) = attrs.constructors {
+        return Err(Error::new_spanned(
+            constructors,
+            "not expected here; the #[error(constructors)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(context) = attrs.context {
+        return Err(Error::new_spanned(
+            context,
+            "not expected here; the #[error(context)] attribute belongs on top of an enum",
+        ));
+    }
+    if let Some(code_method) = attrs.code_method {
+        return Err(Error::new_spanned(
+            code_method,
+            "not expected here; the #[error(code)] attribute belongs on top of a struct or an enum",
+        ));
+    }
+    Ok(())
+}
+
+// Errors are rarely Clone, so under #[error(clone)] a #[from] source has to be
+// kept in an Arc unless its type is declared to be Clone with #[from(raw)].
+fn check_clone_sources(container: &Attrs, fields: &[Field]) -> Result<()> {
+    if container.clone.is_none() {
+        return Ok(());
+    }
+    for field in fields {
+        if let Some(from) = &field.attrs.from {
+            if from.wrap.is_none() && from.types.is_empty() && from.with.is_none() {
+                return Err(Error::new_spanned(
+                    from.original,
+                    "#[error(clone)] requires #[from] sources to be stored in an Arc, as in `#[from] Arc<io::Error>`; write #[from(raw)] if the source type is Clone itself",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Each of these generates a String, a Box, or an Arc somewhere in the derived
+// impls, so #[error(no_alloc)] turns them away at the attribute that asked.
+fn check_no_alloc(container: &Attrs, attrs: &Attrs, fields: &[Field]) -> Result<()> {
+    if container.no_alloc.is_none() {
+        return Ok(());
+    }
+    let allocating = [
+        (
+            "#[error(message)]",
+            attrs.message.map(ToTokens::to_token_stream),
+        ),
+        ("#[whatever]", attrs.whatever.map(ToTokens::to_token_stream)),
+        (
+            "#[error(other)]",
+            attrs.other.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(owned)]",
+            attrs.owned.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(from_str)]",
+            attrs.from_str.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(serialize)]",
+            attrs.serialize.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(deserialize)]",
+            attrs.deserialize.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(json_schema)]",
+            attrs.json_schema.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(into_responses)]",
+            attrs.into_responses.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(into_report)]",
+            attrs.into_report.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(from_anyhow)]",
+            attrs.from_anyhow.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(from_eyre)]",
+            attrs.from_eyre.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(js_value)]",
+            attrs.js_value.map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(into_response)]",
+            attrs.into_response.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(response_error)]",
+            attrs.response_error.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[error(expose = ...)]",
+            attrs.expose.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[status(...)]",
+            attrs.status.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[jsonrpc(...)]",
+            attrs.jsonrpc.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[sentry(...)]",
+            attrs.sentry.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[meta(...)]",
+            attrs.meta.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[py(...)]",
+            attrs.py.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[repr_code]",
+            attrs.repr_code.as_ref().map(ToTokens::to_token_stream),
+        ),
+        (
+            "#[help(...)]",
+            attrs
+                .help
+                .as_ref()
+                .map(|help| help.original.to_token_stream()),
+        ),
+        (
+            "#[diagnostic(...)]",
+            attrs
+                .diagnostic
+                .as_ref()
+                .map(|diagnostic| diagnostic.original.to_token_stream()),
+        ),
+    ];
+    for (option, tokens) in allocating {
+        if let Some(tokens) = tokens {
+            return Err(Error::new_spanned(
+                tokens,
+                format!("{} allocates, which #[error(no_alloc)] rules out", option),
+            ));
+        }
+    }
+    for field in fields {
+        if let Some(from) = &field.attrs.from {
+            if let Some(FromWrap::Arc) | Some(FromWrap::Box) = from.wrap {
+                return Err(Error::new_spanned(
+                    from.original,
+                    "#[from] boxes or reference-counts the source, which #[error(no_alloc)] rules out",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_field_attrs(fields: &[Field]) -> Result<()> {
+    let mut from_field = None;
+    let mut source_field = None;
+    let mut backtrace_field = None;
+    let mut from_default = None;
+    let mut error_id_field = None;
+    let mut timestamp_field = None;
+    for field in fields {
+        if let Some(display_with) = &field.attrs.display_with {
+            if let (Adapter::Humantime, false) =
+                (&display_with.adapter, cfg!(feature = "humantime"))
+            {
+                return Err(Error::new_spanned(
+                    display_with.original,
+                    "#[display(humantime)] requires the \"humantime\" feature of thiserror",
+                ));
+            }
+        }
+        if let Some(error_id) = field.attrs.error_id {
+            if error_id_field.is_some() {
+                return Err(Error::new_spanned(
+                    error_id,
+                    "duplicate #[error_id] attribute",
+                ));
+            }
+            if field.attrs.from.is_some()
+                || field.attrs.source.is_some()
+                || field.attrs.backtrace.is_some()
+            {
+                return Err(Error::new_spanned(
+                    error_id,
+                    "#[error_id] belongs on a field of its own, not on the source or the backtrace",
+                ));
+            }
+            if !cfg!(feature = "std") {
+                return Err(Error::new_spanned(
+                    error_id,
+                    "#[error_id] requires the \"std\" feature of thiserror",
+                ));
+            }
+            error_id_field = Some(field);
+        }
+        if let Some(timestamp) = field.attrs.timestamp {
+            if timestamp_field.is_some() {
+                return Err(Error::new_spanned(
+                    timestamp,
+                    "duplicate #[timestamp] attribute",
+                ));
+            }
+            if field.attrs.from.is_some()
+                || field.attrs.source.is_some()
+                || field.attrs.backtrace.is_some()
+            {
+                return Err(Error::new_spanned(
+                    timestamp,
+                    "#[timestamp] belongs on a field of its own, not on the source or the backtrace",
+                ));
+            }
+            if !cfg!(feature = "std") {
+                return Err(Error::new_spanned(
+                    timestamp,
+                    "#[timestamp] requires the \"std\" feature of thiserror",
+                ));
+            }
+            timestamp_field = Some(field);
+        }
+        if let Some(from) = &field.attrs.from {
+            let mut from_types = Set::new();
+            for from_type in &from.types {
+                let repr = from_type.to_token_stream().to_string();
+                if !from_types.insert(repr) {
+                    return Err(Error::new_spanned(
+                        from_type,
+                        "duplicate type in #[from(...)] attribute",
+                    ));
+                }
+            }
+            if let (Some(_), Some(with)) = (from.wrap, &from.with) {
+                return Err(Error::new_spanned(
+                    with,
+                    "a conversion function in #[from(...)] cannot be combined with a wrapper",
+                ));
+            }
+            if let Some(wrap) = from.wrap {
+                if !from.types.is_empty() {
+                    return Err(Error::new_spanned(
+                        from,
+                        "a wrapper in #[from(...)] cannot be combined with a list of source types",
+                    ));
+                }
+                let msg = match wrap {
+                    FromWrap::Arc => Some("#[from(arc)] requires a field of type Arc<T>"),
+                    FromWrap::Box => Some("#[from(box)] requires a field of type Box<T>"),
+                    FromWrap::Raw => None,
+                };
+                if let Some(msg) = msg {
+                    if field.wrapped_type(wrap).is_none() {
+                        return Err(Error::new_spanned(field.ty, msg));
+                    }
+                }
+            }
+            if from_field.is_some() {
+                return Err(Error::new_spanned(from, "duplicate #[from] attribute"));
+            }
+            from_field = Some(field);
+        }
+        if let Some(source) = field.attrs.source {
+            if source_field.is_some() {
+                return Err(Error::new_spanned(source, "duplicate #[source] attribute"));
+            }
+            source_field = Some(field);
+        }
+        if let Some(backtrace) = field.attrs.backtrace {
+            if backtrace_field.is_some() {
                 return Err(Error::new_spanned(
                     backtrace,
                     "duplicate #[backtrace] attribute",
                 ));
             }
             backtrace_field = Some(field);
-            has_backtrace = true;
         }
-        has_backtrace |= field.is_backtrace();
+        if let Some(default) = field.attrs.from_default {
+            if field.attrs.from.is_some() {
+                return Err(Error::new_spanned(
+                    default,
+                    "#[from(default)] is not allowed on the #[from] field itself",
+                ));
+            }
+            from_default = Some(default);
+        }
     }
     if let (Some(from_field), Some(source_field)) = (from_field, source_field) {
         if !same_member(from_field, source_field) {
             return Err(Error::new_spanned(
-                from_field.attrs.from,
+                &from_field.attrs.from,
                 "#[from] is only supported on the source field, not any other field",
             ));
         }
     }
-    if let Some(from_field) = from_field {
-        if fields.len() > 1 + has_backtrace as usize {
-            return Err(Error::new_spanned(
-                from_field.attrs.from,
-                "deriving From requires no fields other than source and backtrace",
-            ));
-        }
+    if let (None, Some(from_default)) = (from_field, from_default) {
+        return Err(Error::new_spanned(
+            from_default,
+            "#[from(default)] requires another field with a #[from] attribute",
+        ));
     }
     Ok(())
 }
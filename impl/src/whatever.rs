@@ -0,0 +1,77 @@
+use crate::ast::Enum;
+use crate::case::snake_case;
+use crate::prop::type_parameter_of;
+use crate::trace::traced;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Member;
+
+impl Enum<'_> {
+    pub(crate) fn whatever_constructors(&self) -> Option<TokenStream> {
+        let variant = self.whatever_variant()?;
+        let ty = &self.ident;
+        let ident = &variant.ident;
+        let source_field = variant
+            .fields
+            .iter()
+            .find(|field| matches!(&field.member, Member::Named(ident) if ident == "source"))?;
+        let source_ty = type_parameter_of(source_field.ty, "Option")?;
+        let without_source = traced(
+            &self.attrs,
+            Some(&variant.attrs),
+            false,
+            quote! {
+                #ty::#ident {
+                    message: thiserror::private::std::string::ToString::to_string(&message),
+                    source: ::core::option::Option::None,
+                }
+            },
+        );
+        let with_source = traced(
+            &self.attrs,
+            Some(&variant.attrs),
+            true,
+            quote! {
+                #ty::#ident {
+                    message: thiserror::private::std::string::ToString::to_string(&message),
+                    source: ::core::option::Option::Some(::core::convert::Into::into(source)),
+                }
+            },
+        );
+        Some(quote! {
+            /// Creates the ad hoc #[whatever] variant from a message.
+            pub fn whatever(message: impl ::core::fmt::Display) -> Self {
+                #without_source
+            }
+
+            /// Creates the ad hoc #[whatever] variant from a message
+            /// and the error that caused it.
+            pub fn whatever_with(
+                source: impl ::core::convert::Into<#source_ty>,
+                message: impl ::core::fmt::Display,
+            ) -> Self {
+                #with_source
+            }
+        })
+    }
+
+    // Like format!, with an optional source ahead of the format string:
+    // `my_error_whatever!("{} failed", op)` or
+    // `my_error_whatever!(error, "{} failed", op)`.
+    pub(crate) fn whatever_macro(&self) -> Option<TokenStream> {
+        self.whatever_variant()?;
+        let ty = &self.ident;
+        let name = format_ident!("{}_whatever", snake_case(&ty.to_string()));
+        Some(quote! {
+            #[allow(unused_macros)]
+            macro_rules! #name {
+                ($fmt:literal $($args:tt)*) => {
+                    #ty::whatever(::core::format_args!($fmt $($args)*))
+                };
+                ($source:expr, $fmt:literal $($args:tt)*) => {
+                    #ty::whatever_with($source, ::core::format_args!($fmt $($args)*))
+                };
+            }
+        })
+    }
+}
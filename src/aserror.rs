@@ -1,6 +1,37 @@
-use std::error::Error;
+use crate::error::Error;
 
+/// Views any error as a `&dyn Error`.
+///
+/// This is what the derived `source()` uses to hand out fields of many
+/// different types, and it is just as convenient in hand written code that
+/// collects errors of different types. It is implemented for every sized
+/// `Error + 'static`, which includes `Box<E>`, `Arc<E>` and `&'static E`,
+/// and for `dyn Error` itself with or without `Send` and `Sync`. Method call
+/// syntax reaches through other pointers such as `Rc<E>` or
+/// `Box<dyn Error>` by auto-deref.
+///
+/// ```
+/// use std::error::Error;
+/// use std::io;
+/// use std::rc::Rc;
+/// use std::sync::Arc;
+/// use thiserror::AsDynError;
+///
+/// let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+/// let boxed: Box<dyn Error + Send + Sync> = Box::new(io::Error::from(io::ErrorKind::NotFound));
+/// let shared = Arc::new(io::Error::from(io::ErrorKind::TimedOut));
+/// let counted = Rc::new(io::Error::from(io::ErrorKind::Interrupted));
+///
+/// let errors: [&(dyn Error + 'static); 4] = [
+///     io.as_dyn_error(),
+///     boxed.as_dyn_error(),
+///     shared.as_dyn_error(),
+///     counted.as_dyn_error(),
+/// ];
+/// assert_eq!(errors[0].to_string(), "oh no!");
+/// ```
 pub trait AsDynError {
+    /// Returns this error as a trait object.
     fn as_dyn_error(&self) -> &(dyn Error + 'static);
 }
 
@@ -0,0 +1,69 @@
+use std::backtrace::Backtrace;
+use std::sync::Arc;
+
+/// Errors that can hand out the backtrace they captured.
+///
+/// Implemented by `#[derive(Error)]` for types with a backtrace field, so
+/// callers can look for a backtrace on any such error on stable Rust. An
+/// error whose source implements this trait reports the source's backtrace
+/// in preference to its own.
+///
+/// ```rust
+/// # use std::backtrace::Backtrace;
+/// # use thiserror::{Backtraced, Error};
+/// #
+/// #[derive(Error, Debug)]
+/// #[error("request failed")]
+/// pub struct RequestError {
+///     backtrace: Backtrace,
+/// }
+///
+/// fn report(error: &dyn Backtraced) {
+///     if let Some(backtrace) = error.backtrace_ref() {
+///         eprintln!("{}", backtrace);
+///     }
+/// }
+/// #
+/// # report(&RequestError {
+/// #     backtrace: Backtrace::capture(),
+/// # });
+/// ```
+pub trait Backtraced {
+    fn backtrace_ref(&self) -> Option<&Backtrace>;
+}
+
+impl<T: Backtraced + ?Sized> Backtraced for Box<T> {
+    fn backtrace_ref(&self) -> Option<&Backtrace> {
+        (**self).backtrace_ref()
+    }
+}
+
+impl<T: Backtraced + ?Sized> Backtraced for Arc<T> {
+    fn backtrace_ref(&self) -> Option<&Backtrace> {
+        (**self).backtrace_ref()
+    }
+}
+
+// Lets a generated impl ask a source for its backtrace if the source
+// implements Backtraced, without requiring that it does. Method resolution
+// picks BacktracedDelegate on `&BacktraceOf<T>` when T: Backtraced, and
+// otherwise autorefs once more to reach BacktracedFallback.
+pub struct BacktraceOf<'a, T: ?Sized>(pub &'a T);
+
+pub trait BacktracedDelegate<'a> {
+    fn backtrace_ref(&self) -> Option<&'a Backtrace>;
+}
+
+impl<'a, T: Backtraced + ?Sized> BacktracedDelegate<'a> for BacktraceOf<'a, T> {
+    fn backtrace_ref(&self) -> Option<&'a Backtrace> {
+        self.0.backtrace_ref()
+    }
+}
+
+pub trait BacktracedFallback<'a> {
+    fn backtrace_ref(&self) -> Option<&'a Backtrace> {
+        None
+    }
+}
+
+impl<'a, T: ?Sized> BacktracedFallback<'a> for &BacktraceOf<'a, T> {}
@@ -0,0 +1,27 @@
+use crate::error::Error;
+
+/// Iterator over an error and its chain of sources.
+///
+/// The first item is the error itself, followed by the result of repeatedly
+/// calling [`source()`][Error::source] until it returns `None`. This is the
+/// iterator behind the `chain()` method generated by `#[error(chain)]`.
+#[derive(Clone)]
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> Chain<'a> {
+    pub fn new(head: &'a (dyn Error + 'static)) -> Self {
+        Chain { next: Some(head) }
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next?;
+        self.next = next.source();
+        Some(next)
+    }
+}
@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// Error returned by a derived `FromStr` impl when the string is not one of
+/// the `#[code("...")]` identifiers declared on the type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCodeError {
+    code: String,
+}
+
+impl ParseCodeError {
+    #[doc(hidden)]
+    pub fn new(code: &str) -> Self {
+        ParseCodeError {
+            code: code.to_owned(),
+        }
+    }
+
+    /// The string that failed to parse.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+impl Display for ParseCodeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "unknown error code `{}`", self.code)
+    }
+}
+
+impl Error for ParseCodeError {}
@@ -0,0 +1,44 @@
+use core::fmt::{Debug, Display};
+
+/// Adapter through which `#[error(trait = path::to::Trait)]` implements an
+/// error trait of your own, alongside `std::error::Error`.
+///
+/// The derive implements `CustomError<dyn Trait>` with the source field
+/// unsized into `dyn Trait`, and a framework bridges that to its trait with
+/// a single blanket impl. Source fields need to implement the framework's
+/// trait, and a transparent field needs to implement `CustomError` for it
+/// too, typically by deriving with the same attribute.
+///
+/// ```rust
+/// # use std::fmt::{Debug, Display};
+/// # use thiserror::{CustomError, Error};
+/// #
+/// pub trait RichError: Debug + Display {
+///     fn origin(&self) -> Option<&dyn RichError>;
+/// }
+///
+/// impl<T: CustomError<dyn RichError>> RichError for T {
+///     fn origin(&self) -> Option<&dyn RichError> {
+///         self.custom_source()
+///     }
+/// }
+///
+/// #[derive(Error, Debug)]
+/// #[error("page {0} is missing")]
+/// #[error(trait = RichError)]
+/// pub struct MissingPage(u32);
+///
+/// #[derive(Error, Debug)]
+/// #[error("render failed")]
+/// #[error(trait = RichError)]
+/// pub struct RenderError {
+///     #[source]
+///     page: MissingPage,
+/// }
+/// #
+/// # let error = RenderError { page: MissingPage(4) };
+/// # assert_eq!(error.origin().unwrap().to_string(), "page 4 is missing");
+/// ```
+pub trait CustomError<Dyn: ?Sized>: Debug + Display {
+    fn custom_source(&self) -> Option<&Dyn>;
+}
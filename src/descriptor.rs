@@ -0,0 +1,19 @@
+use crate::severity::Severity;
+
+/// Static description of one error variant, or of an error struct.
+///
+/// Descriptors are generated by the derive macro and carry the metadata that
+/// is known at compile time, without needing an instance of the error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ErrorDescriptor {
+    /// Name of the enum variant, or of the type itself for structs.
+    pub name: &'static str,
+    /// The `#[error("...")]` message template exactly as written, or `None`
+    /// for `#[error(transparent)]` and for variants without a message.
+    pub message: Option<&'static str>,
+    /// The `#[code("...")]` of the variant or struct, if it has one.
+    pub code: Option<&'static str>,
+    /// The severity that the generated `severity()` method reports, which is
+    /// `Error` for types without `#[severity(...)]`.
+    pub severity: Severity,
+}
@@ -0,0 +1,120 @@
+//! Comparison of error catalogs between two versions of a crate.
+//!
+//! Every derived type carries a static list of [`ErrorDescriptor`]s. Diffing
+//! the descriptors of an old and a new version of the same type reports which
+//! changes are visible to downstream code, so that reviewing the semver impact
+//! of a public error type becomes mechanical.
+//!
+//! ```
+//! use thiserror::diff::{self, Change};
+//! use thiserror::{ErrorDescriptor, Severity};
+//!
+//! let descriptor = |name, message| ErrorDescriptor {
+//!     name,
+//!     message: Some(message),
+//!     code: None,
+//!     severity: Severity::Error,
+//! };
+//! let old = [
+//!     descriptor("NotFound", "not found"),
+//!     descriptor("Timeout", "timed out"),
+//! ];
+//! let new = [descriptor("NotFound", "missing")];
+//!
+//! let changes = diff::diff(&old, &new);
+//! assert_eq!(
+//!     changes,
+//!     [
+//!         Change::ChangedMessage {
+//!             name: "NotFound",
+//!             old: Some("not found"),
+//!             new: Some("missing"),
+//!         },
+//!         Change::RemovedVariant { name: "Timeout" },
+//!     ],
+//! );
+//! assert!(changes.iter().any(Change::is_breaking));
+//! ```
+//!
+//! [`ErrorDescriptor`]: crate::ErrorDescriptor
+
+use crate::descriptor::ErrorDescriptor;
+use core::fmt::{self, Display};
+
+/// A single difference between two versions of an error catalog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// A variant present in the old version is missing from the new one.
+    RemovedVariant { name: &'static str },
+    /// A variant was added in the new version.
+    AddedVariant { name: &'static str },
+    /// The message template of a variant changed.
+    ChangedMessage {
+        name: &'static str,
+        old: Option<&'static str>,
+        new: Option<&'static str>,
+    },
+}
+
+impl Change {
+    /// Whether downstream code matching on or inspecting the error may break.
+    ///
+    /// Removed variants are breaking. Added variants and reworded messages
+    /// are not.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Change::RemovedVariant { .. } => true,
+            Change::AddedVariant { .. } | Change::ChangedMessage { .. } => false,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::RemovedVariant { name } => write!(formatter, "removed variant `{}`", name),
+            Change::AddedVariant { name } => write!(formatter, "added variant `{}`", name),
+            Change::ChangedMessage { name, old, new } => write!(
+                formatter,
+                "changed message of `{}` from {:?} to {:?}",
+                name, old, new,
+            ),
+        }
+    }
+}
+
+/// Compares the descriptors of two versions of the same error type.
+///
+/// Variants are matched by name. Changes to variants that exist in both
+/// versions are listed first in the order of `old`, followed by removed
+/// variants, followed by added variants in the order of `new`.
+pub fn diff(old: &[ErrorDescriptor], new: &[ErrorDescriptor]) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+
+    for old in old {
+        match new.iter().find(|new| new.name == old.name) {
+            Some(new) => compare(old, new, &mut changes),
+            None => removed.push(Change::RemovedVariant { name: old.name }),
+        }
+    }
+    changes.extend(removed);
+
+    for new in new {
+        if !old.iter().any(|old| old.name == new.name) {
+            changes.push(Change::AddedVariant { name: new.name });
+        }
+    }
+
+    changes
+}
+
+fn compare(old: &ErrorDescriptor, new: &ErrorDescriptor, changes: &mut Vec<Change>) {
+    if old.message != new.message {
+        changes.push(Change::ChangedMessage {
+            name: new.name,
+            old: old.message,
+            new: new.message,
+        });
+    }
+}
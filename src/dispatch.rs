@@ -0,0 +1,12 @@
+use crate::error::Error;
+use core::fmt;
+
+// One variant of an enum deriving Error with #[error(dispatch)], as found by
+// the single match that the generated source(), backtrace_ref() and fmt() share.
+// Each part is computed only when the caller asks for it.
+pub struct Dispatch<'a, 'b> {
+    pub source: &'b dyn Fn() -> Option<&'a (dyn Error + 'static)>,
+    #[cfg(feature = "std")]
+    pub backtrace: &'b dyn Fn() -> Option<&'a std::backtrace::Backtrace>,
+    pub display: &'b dyn Fn(&mut fmt::Formatter) -> fmt::Result,
+}
@@ -1,5 +1,13 @@
-use std::fmt::Display;
+use crate::error::Error;
+use core::fmt::{self, Debug, Display};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::ffi::{CStr, CString, OsStr, OsString};
+#[cfg(feature = "std")]
 use std::path::{self, Path, PathBuf};
+#[cfg(feature = "std")]
+use std::str;
 
 pub trait DisplayAsDisplay {
     fn as_display(&self) -> Self;
@@ -11,18 +19,170 @@ impl<T: Display> DisplayAsDisplay for &T {
     }
 }
 
+#[cfg(feature = "std")]
 pub trait PathAsDisplay {
     fn as_display(&self) -> path::Display<'_>;
 }
 
+// Paths only exist in std. The trait stays so that derived code imports the
+// same names either way.
+#[cfg(not(feature = "std"))]
+pub trait PathAsDisplay {}
+
+#[cfg(feature = "std")]
 impl PathAsDisplay for Path {
     fn as_display(&self) -> path::Display<'_> {
         self.display()
     }
 }
 
+#[cfg(feature = "std")]
 impl PathAsDisplay for PathBuf {
     fn as_display(&self) -> path::Display<'_> {
         self.display()
     }
 }
+
+// OS strings, C strings and bytes, which need not be UTF-8. OS strings are
+// shown lossily, as by `to_string_lossy`; C strings and bytes are shown with
+// their invalid bytes escaped as in `\xFF`.
+#[cfg(feature = "std")]
+pub trait LossyAsDisplay {
+    fn as_display(&self) -> DisplayLossy<'_>;
+}
+
+#[cfg(not(feature = "std"))]
+pub trait LossyAsDisplay {}
+
+#[cfg(feature = "std")]
+pub enum DisplayLossy<'a> {
+    Str(Cow<'a, str>),
+    Bytes(&'a [u8]),
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for OsStr {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Str(self.to_string_lossy())
+    }
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for OsString {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Str(self.to_string_lossy())
+    }
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for CStr {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Bytes(self.to_bytes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for CString {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Bytes(self.as_bytes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for [u8] {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Bytes(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl LossyAsDisplay for Vec<u8> {
+    fn as_display(&self) -> DisplayLossy<'_> {
+        DisplayLossy::Bytes(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for DisplayLossy<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = match self {
+            DisplayLossy::Str(string) => return formatter.pad(string),
+            DisplayLossy::Bytes(bytes) => *bytes,
+        };
+        loop {
+            match str::from_utf8(bytes) {
+                Ok(valid) => return formatter.write_str(valid),
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    formatter.write_str(unsafe { str::from_utf8_unchecked(valid) })?;
+                    let invalid = error.error_len().unwrap_or(rest.len());
+                    for byte in &rest[..invalid] {
+                        write!(formatter, "\\x{:02X}", byte)?;
+                    }
+                    bytes = &rest[invalid..];
+                }
+            }
+        }
+    }
+}
+
+// The body of a message that is nothing but one `{field}`. As with write!,
+// the field is formatted without the flags given to the error itself, so
+// `{:>8}` pads neither. The Arguments are built once here rather than inline
+// in every variant.
+pub fn write_display<T: Display + ?Sized>(
+    formatter: &mut fmt::Formatter,
+    value: &T,
+) -> fmt::Result {
+    write!(formatter, "{}", value)
+}
+
+// Displays the message of an error's source, or nothing if it has none.
+pub struct DisplaySource<'a>(pub &'a (dyn Error + 'a));
+
+impl Display for DisplaySource<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.source() {
+            Some(source) => Display::fmt(source, formatter),
+            None => Ok(()),
+        }
+    }
+}
+
+// Displays the message of an error followed by the messages of its chain of
+// sources, separated by colons.
+pub struct DisplayChain<'a>(pub &'a (dyn Error + 'a));
+
+impl Display for DisplayChain<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self.0, formatter)?;
+        let mut source = self.0.source();
+        while let Some(cause) = source {
+            write!(formatter, ": {}", cause)?;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+// A field with #[display(with = path)], written by calling the function with
+// the field wherever it is interpolated, whichever the placeholder.
+pub struct DisplayWith<'a, T: ?Sized, F>(pub &'a T, pub F);
+
+impl<T: ?Sized, F> Display for DisplayWith<'_, T, F>
+where
+    F: Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        (self.1)(self.0, formatter)
+    }
+}
+
+impl<T: ?Sized, F> Debug for DisplayWith<'_, T, F>
+where
+    F: Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        (self.1)(self.0, formatter)
+    }
+}
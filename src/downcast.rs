@@ -0,0 +1,53 @@
+use crate::chain::Chain;
+use crate::error::Error;
+
+/// Searching an error's whole chain of sources for a concrete type.
+///
+/// `downcast_ref` on `dyn Error` only looks at the error itself. This trait
+/// also looks at every lower level source, as returned by repeatedly calling
+/// [`source()`][Error::source], and returns the first one of type `T`.
+///
+/// ```
+/// # use std::io;
+/// use thiserror::{Error, ErrorDowncastExt};
+///
+/// #[derive(Error, Debug)]
+/// #[error("failed to load config")]
+/// pub struct ConfigError(#[source] io::Error);
+///
+/// let error = ConfigError(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+/// let io = error.downcast_ref_chain::<io::Error>().unwrap();
+/// assert_eq!(io.kind(), io::ErrorKind::NotFound);
+/// ```
+pub trait ErrorDowncastExt {
+    /// Returns the first error of type `T` among this error and its sources.
+    fn downcast_ref_chain<T: Error + 'static>(&self) -> Option<&T>;
+}
+
+impl<E: Error + 'static> ErrorDowncastExt for E {
+    fn downcast_ref_chain<T: Error + 'static>(&self) -> Option<&T> {
+        find(self)
+    }
+}
+
+impl ErrorDowncastExt for dyn Error + 'static {
+    fn downcast_ref_chain<T: Error + 'static>(&self) -> Option<&T> {
+        find(self)
+    }
+}
+
+impl ErrorDowncastExt for dyn Error + Send + 'static {
+    fn downcast_ref_chain<T: Error + 'static>(&self) -> Option<&T> {
+        find(self)
+    }
+}
+
+impl ErrorDowncastExt for dyn Error + Send + Sync + 'static {
+    fn downcast_ref_chain<T: Error + 'static>(&self) -> Option<&T> {
+        find(self)
+    }
+}
+
+fn find<'a, T: Error + 'static>(error: &'a (dyn Error + 'static)) -> Option<&'a T> {
+    Chain::new(error).find_map(|cause| cause.downcast_ref::<T>())
+}
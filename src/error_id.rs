@@ -0,0 +1,57 @@
+use core::fmt::{self, Display};
+use core::num::NonZeroU64;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT: AtomicU64 = AtomicU64::new(1);
+
+/// An identifier unique to one error within the running process.
+///
+/// Fields marked `#[error_id]` are filled in with a fresh `ErrorId` by the
+/// generated `From` impls and constructors, so that the identifier shown to a
+/// user can be matched with the error logged on the server. The field may
+/// also be a `u64`, or any type implementing `From<ErrorId>`, for example to
+/// combine it with a per-process random prefix.
+///
+/// ```rust
+/// # use std::io;
+/// # use thiserror::{Error, ErrorId};
+/// #
+/// #[derive(Error, Debug)]
+/// #[error("request failed (error id {id})")]
+/// pub struct RequestError {
+///     #[from]
+///     source: io::Error,
+///     #[error_id]
+///     id: ErrorId,
+/// }
+/// #
+/// # let one = RequestError::from(io::Error::new(io::ErrorKind::Other, "..."));
+/// # let two = RequestError::from(io::Error::new(io::ErrorKind::Other, "..."));
+/// # assert_ne!(one.error_id(), two.error_id());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorId(NonZeroU64);
+
+impl ErrorId {
+    /// Hands out the next identifier.
+    pub fn next() -> Self {
+        let id = NEXT.fetch_add(1, Ordering::Relaxed);
+        ErrorId(NonZeroU64::new(id).expect("error ids exhausted"))
+    }
+
+    pub fn get(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl Display for ErrorId {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{:016x}", self.0)
+    }
+}
+
+impl From<ErrorId> for u64 {
+    fn from(id: ErrorId) -> Self {
+        id.get()
+    }
+}
@@ -0,0 +1,63 @@
+use std::error::Error;
+use std::process::{ExitCode, Termination};
+
+/// Errors that determine the status with which the process exits.
+///
+/// Implemented by `#[derive(Error)]` for types carrying `#[exit_code(...)]`.
+pub trait ExitStatus {
+    fn exit_code(&self) -> ExitCode;
+}
+
+/// Return type for `main` that exits with the status of the error.
+///
+/// On error, the message is printed to stderr followed by the messages of its
+/// chain of sources, and the process exits with the error's
+/// [`exit_code()`][ExitStatus::exit_code].
+///
+/// ```rust
+/// # use thiserror::Error;
+/// #
+/// #[derive(Error, Debug)]
+/// pub enum CliError {
+///     #[error("invalid arguments")]
+///     #[exit_code(2)]
+///     Usage,
+///     #[error("input file is empty")]
+///     #[exit_code(65)]
+///     EmptyInput,
+/// }
+///
+/// fn run() -> Result<(), CliError> {
+///     Ok(())
+/// }
+///
+/// fn main() -> thiserror::Exit<CliError> {
+///     run().into()
+/// }
+/// ```
+pub struct Exit<E>(pub Result<(), E>);
+
+impl<E> From<Result<(), E>> for Exit<E> {
+    fn from(result: Result<(), E>) -> Self {
+        Exit(result)
+    }
+}
+
+impl<E> Termination for Exit<E>
+where
+    E: Error + ExitStatus,
+{
+    fn report(self) -> ExitCode {
+        let error = match self.0 {
+            Ok(()) => return ExitCode::SUCCESS,
+            Err(error) => error,
+        };
+        eprintln!("Error: {}", error);
+        let mut source = error.source();
+        while let Some(cause) = source {
+            eprintln!("Caused by: {}", cause);
+            source = cause.source();
+        }
+        error.exit_code()
+    }
+}
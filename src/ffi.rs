@@ -0,0 +1,18 @@
+use std::os::raw::c_char;
+use std::ptr;
+
+/// Copies as much of the message as fits into a C buffer of `len` bytes,
+/// always leaving it NUL terminated, and returns the length of the whole
+/// message as snprintf does so that callers can detect truncation.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `len` bytes.
+pub unsafe fn write_c_message(message: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        let n = message.len().min(len - 1);
+        ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, buf, n);
+        *buf.add(n) = 0;
+    }
+    message.len()
+}
@@ -0,0 +1,64 @@
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use std::error::Error;
+
+/// Registers a function to be called with every error created by a derived
+/// `From` impl or constructor of a type marked `#[error(hook)]`.
+///
+/// Hooks run in the order they were registered, on the thread creating the
+/// error, before the error is handed to the caller. They are meant for
+/// centralized metrics and logging; a hook that itself creates an error of a
+/// type marked `#[error(hook)]` recurses. Hooks cannot be unregistered.
+///
+/// ```rust
+/// # use std::error::Error as _;
+/// # use std::io;
+/// # use thiserror::Error;
+/// #
+/// #[derive(Error, Debug)]
+/// #[error(hook)]
+/// #[error("request failed")]
+/// pub struct RequestError {
+///     #[from]
+///     source: io::Error,
+/// }
+///
+/// fn count(error: &dyn std::error::Error) {
+///     eprintln!("error created: {}", error);
+/// }
+///
+/// thiserror::on_error(count);
+/// # let _ = RequestError::from(io::Error::new(io::ErrorKind::Other, "..."));
+/// ```
+pub fn on_error(hook: fn(&dyn Error)) {
+    let new = Box::into_raw(Box::new(Hook {
+        hook,
+        next: AtomicPtr::new(ptr::null_mut()),
+    }));
+    let mut slot = &HOOKS;
+    loop {
+        match slot.compare_exchange(ptr::null_mut(), new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return,
+            Err(next) => slot = unsafe { &(*next).next },
+        }
+    }
+}
+
+// Hooks are kept in a list appended to at its end, so that running them takes
+// no lock. Nodes are never freed.
+static HOOKS: AtomicPtr<Hook> = AtomicPtr::new(ptr::null_mut());
+
+struct Hook {
+    hook: fn(&dyn Error),
+    next: AtomicPtr<Hook>,
+}
+
+// Not public API. Used by generated code.
+#[doc(hidden)]
+pub fn run_error_hooks(error: &dyn Error) {
+    let mut next = HOOKS.load(Ordering::Acquire);
+    while let Some(hook) = unsafe { next.as_ref() } {
+        (hook.hook)(error);
+        next = hook.next.load(Ordering::Acquire);
+    }
+}
@@ -0,0 +1,44 @@
+use core::fmt;
+use std::time::{Duration, SystemTime};
+
+// Types that `#[display(humantime)]` knows how to show.
+pub trait Humantime {
+    fn fmt_human(&self, formatter: &mut fmt::Formatter) -> fmt::Result;
+}
+
+// Durations under a minute are shown in the largest unit that fits, with
+// seconds to one decimal, as in `2.3s` or `150ms`. Longer durations are
+// shown to the second, as in `1h 2m 3s`.
+impl Humantime for Duration {
+    fn fmt_human(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let rendered = if self.as_secs() >= 60 {
+            let whole_secs = Duration::from_secs(self.as_secs());
+            humantime::format_duration(whole_secs).to_string()
+        } else if self.as_millis() >= 1000 {
+            let tenths = (self.as_millis() + 50) / 100;
+            if tenths % 10 == 0 {
+                format!("{}s", tenths / 10)
+            } else {
+                format!("{}.{}s", tenths / 10, tenths % 10)
+            }
+        } else if self.as_millis() >= 1 {
+            format!("{}ms", self.as_millis())
+        } else if self.as_micros() >= 1 {
+            format!("{}µs", self.as_micros())
+        } else {
+            format!("{}ns", self.as_nanos())
+        };
+        formatter.pad(&rendered)
+    }
+}
+
+// The same RFC 3339 rendering as a `#[timestamp]`.
+impl Humantime for SystemTime {
+    fn fmt_human(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        crate::timestamp::write_rfc3339(*self, formatter)
+    }
+}
+
+pub fn humantime<T: Humantime + ?Sized>(value: &T, formatter: &mut fmt::Formatter) -> fmt::Result {
+    value.fmt_human(formatter)
+}
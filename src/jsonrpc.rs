@@ -0,0 +1,15 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// A JSON-RPC 2.0 error object, as returned by the `to_jsonrpc_error()` method
+/// generated for types annotated with `#[jsonrpc(...)]`.
+///
+/// Serializes to `{"code": ..., "message": ..., "data": ...}`, leaving out
+/// `data` if there is none.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
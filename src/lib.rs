@@ -89,12 +89,122 @@
 //!   }
 //!   ```
 //!
+//!   Fields of type `Path` or `PathBuf`, `OsStr` or `OsString`, `CStr` or
+//!   `CString`, and byte slices or `Vec<u8>` can be interpolated with `{var}`
+//!   as well, even though they don't implement `Display`. Paths and OS strings
+//!   are shown lossily, with U+FFFD in place of what is not valid Unicode; C
+//!   strings and bytes are shown with each byte that is not valid UTF-8
+//!   escaped, as in `\xFF`.
+//!
+//!   ```rust
+//!   # use std::ffi::{CString, OsString};
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum EnvError {
+//!       #[error("environment variable {0} is not unicode")]
+//!       NotUnicode(OsString),
+//!       #[error("library returned {message}")]
+//!       Library { message: CString },
+//!       #[error("unexpected response {0}")]
+//!       Response(Vec<u8>),
+//!   }
+//!   #
+//!   # let error = EnvError::Response(b"ok\xff".to_vec());
+//!   # assert_eq!(error.to_string(), "unexpected response ok\\xFF");
+//!   ```
+//!
+//!   Fields whose type involves a type parameter get the bound their
+//!   placeholder needs on the generated `Display` impl, such as `T: Display`
+//!   for `{0}` or `T: Debug` for `{0:?}`, so the definition can leave `T`
+//!   unbounded.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum ParseError<T> {
+//!       #[error("bad value {0}")]
+//!       Bad(T),
+//!       #[error("empty input")]
+//!       Empty,
+//!   }
+//!   ```
+//!
+//!   Where the inferred bounds are too strict or too loose,
+//!   `#[error(bound = "...")]` on the struct or enum replaces them, including
+//!   the `E: Error` bound of a generic source, with the given where-predicates.
+//!
+//!   ```rust
+//!   # use std::fmt::Display;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("wrapped {0}")]
+//!   #[error(bound = "T: Display + Send")]
+//!   pub struct Wrapper<T>(T);
+//!   ```
+//!
+//!   Const generic parameters are in scope in the message too, so `{N}`
+//!   interpolates the parameter's value.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("buffer of {N} bytes overflowed by {excess}")]
+//!   pub struct Overflow<const N: usize> {
+//!       excess: usize,
+//!   }
+//!   ```
+//!
+//!   With `#[error(deny_unused)]` on the struct or enum, a field that the
+//!   message never mentions and that is neither the source nor the backtrace
+//!   is a compile error, which catches context fields that silently stopped
+//!   being rendered. Prefix a field with an underscore to leave it out on
+//!   purpose.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(deny_unused)]
+//!   pub enum FetchError {
+//!       #[error("fetching {url} failed after {attempts} attempts")]
+//!       Exhausted { url: String, attempts: u32 },
+//!       #[error("cancelled")]
+//!       Cancelled { _reason: String },
+//!   }
+//!   ```
+//!
+//!   `#[error(style(lowercase_start, no_trailing_period))]` enforces the
+//!   usual conventions for error messages at compile time: each message
+//!   starts with a lowercase letter, unless it opens with an acronym such as
+//!   `HTTP`, and does not end with a period. Either check can be listed on
+//!   its own.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(style(lowercase_start, no_trailing_period))]
+//!   pub enum ConnectError {
+//!       #[error("connection refused by {0}")]
+//!       Refused(String),
+//!       #[error("HTTP status {0}")]
+//!       Status(u16),
+//!   }
+//!   ```
+//!
 //! - A `From` impl is generated for each variant containing a `#[from]`
 //!   attribute.
 //!
-//!   Note that the variant must not contain any other fields beyond the source
-//!   error and possibly a backtrace. A backtrace is captured from within the
-//!   `From` impl if there is a field for it.
+//!   A backtrace is captured from within the `From` impl if there is a field
+//!   for it, unless the source error already carries a backtrace of its own,
+//!   in which case the field is left empty or disabled and `backtrace_ref()`
+//!   returns the source's. Any other fields of the variant are filled in with
+//!   `Default::default()`; marking them `#[from(default)]` spells this out at
+//!   the definition.
 //!
 //!   ```rust
 //!   # const IGNORE: &str = stringify! {
@@ -109,6 +219,230 @@
 //!   # };
 //!   ```
 //!
+//!   By default the `From` impl converts from exactly the field's type, so a
+//!   `#[from] Box<T>` field generates `From<Box<T>>`; `#[from(raw)]` spells
+//!   this out. A source stored as `Box<T>` or `Arc<T>` can instead be
+//!   converted from a plain `T` by writing `#[from(box)]` or `#[from(arc)]`,
+//!   in which case the `From` impl wraps the source in `Box::new` or
+//!   `Arc::new`.
+//!
+//!   Several source types can be collapsed into one variant by listing them in
+//!   the attribute. One `From` impl is generated per listed type, converting
+//!   into the field through `Into`.
+//!
+//!   ```rust
+//!   # use std::{fmt, io};
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum MyError {
+//!       #[error("formatting failed")]
+//!       Format(#[from(io::Error, fmt::Error)] Box<dyn std::error::Error + Send + Sync>),
+//!   }
+//!   ```
+//!
+//!   A mapping step can be run before the source is stored by naming a
+//!   function with `#[from(with = path)]`. The function takes the source type
+//!   and returns the field type; the source type is the field's own type
+//!   unless other types are listed, as in
+//!   `#[from(reqwest::Error, with = normalize)] io::Error`.
+//!
+//!   A single source type can be routed to different variants with
+//!   `#[from_match(Type, pattern)]` on each variant, optionally followed by an
+//!   `if` guard. The arms are collected into one `From<Type>` impl matching on
+//!   a reference to the source in declaration order, so the last variant
+//!   usually carries a bare `#[from_match(Type)]` as the catch-all. The
+//!   matched error is stored in the variant's source field.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum StorageError {
+//!       #[error("not found")]
+//!       #[from_match(io::Error, e if e.kind() == io::ErrorKind::NotFound)]
+//!       NotFound(#[source] io::Error),
+//!       #[error("io error")]
+//!       #[from_match(io::Error)]
+//!       Other(#[source] io::Error),
+//!   }
+//!   ```
+//!
+//!   In the other direction, marking a variant `#[try_into]` generates
+//!   `TryFrom<MyError>` for the type of its source field, or of its only field,
+//!   handing back the original error if it is a different variant.
+//!
+//!   A variant with several fields can be built from a tuple of them by
+//!   marking it `#[from_parts]`, which generates `From<(A, B, ...)>` filling
+//!   the fields in declaration order. This lets `?` convert the
+//!   `(PathBuf, io::Error)` pairs returned by helper functions.
+//!
+//!   A catch-all variant holding a single `String` can be marked
+//!   `#[error(message)]`. It displays the string as is, converts from both
+//!   `String` and `&str`, and gets a `msg()` constructor accepting anything
+//!   that implements `Display`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       #[error("io error")]
+//!       Io(#[from] io::Error),
+//!       #[error(message)]
+//!       Other(String),
+//!   }
+//!   #
+//!   # let error = AppError::msg(format_args!("{} retries left", 0));
+//!   # assert_eq!(error.to_string(), "0 retries left");
+//!   ```
+//!
+//!   For ad hoc errors that may also carry a cause, one variant with fields
+//!   `message: String` and `source: Option<Box<dyn Error + Send + Sync>>`
+//!   can be marked `#[whatever]`. Unless it has its own `#[error("...")]` it
+//!   displays the message. The enum gets `whatever()` and
+//!   `whatever_with(source, message)` constructors, and a `format!`-like
+//!   macro named after it in snake case, e.g.
+//!   `app_error_whatever!(source, "reading {}", path)`, where the source is
+//!   optional.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AppError {
+//!       #[error("config missing")]
+//!       ConfigMissing,
+//!       #[whatever]
+//!       Whatever {
+//!           message: String,
+//!           source: Option<Box<dyn std::error::Error + Send + Sync>>,
+//!       },
+//!   }
+//!
+//!   fn load(path: &str) -> Result<String, AppError> {
+//!       std::fs::read_to_string(path)
+//!           .map_err(|error| app_error_whatever!(error, "reading {}", path))
+//!   }
+//!   #
+//!   # assert!(load("/nonexistent").is_err());
+//!   ```
+//!
+//!   The catch-all for errors of any other type is a variant marked
+//!   `#[error(other)]` holding a `Box<dyn Error + Send + Sync>`, which is its
+//!   source. The enum gets an `other(error)` constructor accepting any error,
+//!   and `#[from_match]` conversions send the errors matching none of their
+//!   patterns there.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum StoreError {
+//!       #[error("key not found")]
+//!       #[from_match(io::Error, e if e.kind() == io::ErrorKind::NotFound)]
+//!       NotFound(#[source] io::Error),
+//!       #[error(transparent)]
+//!       #[error(other)]
+//!       Other(Box<dyn std::error::Error + Send + Sync>),
+//!   }
+//!   #
+//!   # let error = StoreError::from(io::Error::new(io::ErrorKind::Other, "disk full"));
+//!   # assert!(matches!(error, StoreError::Other(_)));
+//!   # assert_eq!(StoreError::other(std::fmt::Error).to_string(), "an error occurred when formatting an argument");
+//!   ```
+//!
+//!   Errors that must be handed out as a foreign type can declare
+//!   `#[into(Type, kind = ...)]` to generate `From<MyError> for Type`. The
+//!   `kind` is passed along with the error to `Type::new`, which fits
+//!   `std::io::Error`; `with = path` calls a function taking the error
+//!   instead. Each variant may override the conversion with its own
+//!   `#[into(kind = ...)]` or `#[into(with = ...)]`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[into(io::Error, kind = io::ErrorKind::Other)]
+//!   pub enum ConfigError {
+//!       #[error("missing key {0}")]
+//!       #[into(kind = io::ErrorKind::NotFound)]
+//!       Missing(String),
+//!       #[error("malformed config")]
+//!       Malformed,
+//!   }
+//!   ```
+//!
+//! - A struct wrapping another error with `#[error(transparent)]` can add
+//!   `deref` to generate `Deref`, `DerefMut` and `AsRef` to the inner error,
+//!   so that its methods stay reachable through the wrapper.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(transparent, deref)]
+//!   pub struct StorageError(io::Error);
+//!
+//!   # let error = StorageError(io::Error::new(io::ErrorKind::NotFound, "missing"));
+//!   if error.kind() == io::ErrorKind::NotFound {
+//!       // ...
+//!   }
+//!   ```
+//!
+//! - `#[error(partial_eq)]` generates a `PartialEq` impl comparing every
+//!   field except captured backtraces and span traces, which would otherwise
+//!   rule out `#[derive(PartialEq)]`. Tests can then compare errors with
+//!   `assert_eq!`.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   # use tracing_error::SpanTrace;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("parse error at line {line}")]
+//!   #[error(partial_eq)]
+//!   pub struct ParseError {
+//!       line: usize,
+//!       span_trace: SpanTrace,
+//!   }
+//!   #
+//!   # let error = |line| ParseError { line, span_trace: SpanTrace::capture() };
+//!   # assert_eq!(error(1), error(1));
+//!   ```
+//!
+//! - `#[error(clone)]` generates a `Clone` impl, for caching errors or
+//!   sending them through broadcast channels. Since most errors are not
+//!   `Clone`, a `#[from]` source is stored in an `Arc` and the `From` impl
+//!   converts from the plain error, as with `#[from(arc)]`. A source whose
+//!   type is `Clone` itself is written `#[from(raw)]`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::sync::Arc;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(clone)]
+//!   pub enum CacheError {
+//!       #[error("i/o failed")]
+//!       Io(#[from] Arc<io::Error>),
+//!       #[error("key {0} expired")]
+//!       Expired(String),
+//!   }
+//!
+//!   fn load() -> Result<Vec<u8>, CacheError> {
+//!       Ok(std::fs::read("/var/cache/app")?)
+//!   }
+//!   #
+//!   # let _ = load().map_err(|error| error.clone());
+//!   ```
+//!
 //! - The Error trait's `source()` method is implemented to return whichever
 //!   field has a `#[source]` attribute or is named `source`, if any. This is
 //!   for identifying the underlying lower level error that caused your error.
@@ -119,6 +453,15 @@
 //!   Any error type that implements `std::error::Error` or dereferences to `dyn
 //!   std::error::Error` will work as a source.
 //!
+//!   The source may also be a type parameter, as in `struct Wrapper<E> {
+//!   #[from] source: E }`. The generated `Error` impl then requires `E:
+//!   std::error::Error + 'static` without the definition having to say so.
+//!
+//!   A source that borrows, such as `#[source] cause: Unexpected<'a>`, cannot
+//!   be returned from `source()`, which hands out `dyn Error + 'static`. It is
+//!   left out there, and a generated `source_ref()` method returns it as
+//!   `&(dyn Error + '_)` instead, alongside any sources that do not borrow.
+//!
 //!   ```rust
 //!   # use std::fmt::{self, Display};
 //!   # use thiserror::Error;
@@ -137,19 +480,1076 @@
 //!   # }
 //!   ```
 //!
-//! - The Error trait's `backtrace()` method is implemented to return whichever
-//!   field has a type named `Backtrace`, if any.
+//! - The [`Backtraced`] trait is implemented to return whichever field has a
+//!   type named `Backtrace`, if any, or the backtrace of a source that
+//!   implements `Backtraced` itself. Unlike the Error trait's unstable
+//!   `backtrace()` method this works on stable Rust.
 //!
 //!   ```rust
-//!   # const IGNORE: &str = stringify! {
+//!   # use thiserror::Error;
 //!   use std::backtrace::Backtrace;
+//!   use thiserror::Backtraced;
 //!
 //!   #[derive(Error, Debug)]
+//!   #[error("{msg}")]
 //!   pub struct MyError {
 //!       msg: String,
 //!       backtrace: Backtrace, // automatically detected
 //!   }
-//!   # };
+//!
+//!   fn print_backtrace(error: &MyError) {
+//!       if let Some(backtrace) = error.backtrace_ref() {
+//!           eprintln!("{}", backtrace);
+//!       }
+//!   }
+//!   ```
+//!
+//!   Similarly a field whose type is named `SpanTrace`, such as
+//!   `tracing_error::SpanTrace` or `Option<SpanTrace>`, is filled in with
+//!   `SpanTrace::capture()` by the generated `From` impl.
+//!
+//! - Messages that interpolate untrusted input can opt in to
+//!   `#[error(fuzz_safe)]`, either on the whole type or on individual enum
+//!   variants. Every interpolated field, whether as `{field}` or as `.field`,
+//!   then has its control characters and invisible formatting characters such
+//!   as bidirectional overrides escaped and is truncated after 256 characters,
+//!   so attacker-controlled bytes cannot corrupt terminal output, forge log
+//!   lines, or disguise the text around them. A width in the format spec pads
+//!   the sanitized text; precision is not applied.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(fuzz_safe)]
+//!   #[error("unknown user {name}")]
+//!   pub struct UnknownUser {
+//!       name: String,
+//!   }
+//!   #
+//!   # let error = UnknownUser { name: "\x1b[2J".to_owned() };
+//!   # assert_eq!(error.to_string(), "unknown user \\u{1b}[2J");
+//!   ```
+//!
+//! - A field whose type has no `Display` impl, or not the one wanted in the
+//!   message, can name a function to format it with
+//!   `#[display(with = path)]`. The function has the signature
+//!   `fn(&FieldTy, &mut fmt::Formatter) -> fmt::Result` and is used wherever
+//!   the field is interpolated.
+//!
+//!   ```rust
+//!   # use std::fmt;
+//!   # use thiserror::Error;
+//!   #
+//!   fn hex(bytes: &Vec<u8>, formatter: &mut fmt::Formatter) -> fmt::Result {
+//!       for byte in bytes {
+//!           write!(formatter, "{:02x}", byte)?;
+//!       }
+//!       Ok(())
+//!   }
+//!
+//!   #[derive(Error, Debug)]
+//!   #[error("checksum mismatch, got {actual}")]
+//!   pub struct ChecksumError {
+//!       #[display(with = hex)]
+//!       actual: Vec<u8>,
+//!   }
+//!   #
+//!   # let error = ChecksumError { actual: vec![0xde, 0xad] };
+//!   # assert_eq!(error.to_string(), "checksum mismatch, got dead");
+//!   ```
+//!
+//!   With the `humantime` feature enabled, `#[display(humantime)]` shows a
+//!   `Duration` field the way a person would write it, such as `2.3s`,
+//!   `150ms` or `1h 2m 3s`, and a `SystemTime` field as an RFC 3339 date and
+//!   time in UTC.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[error("request timed out after {elapsed}")]
+//!   pub struct Timeout {
+//!       #[display(humantime)]
+//!       elapsed: Duration,
+//!   }
+//!   ```
+//!
+//! - Fields holding secrets can be marked `#[sensitive]`, which renders
+//!   `<redacted>` wherever the message interpolates them, including the
+//!   message written by `#[error(serialize)]`. `#[sensitive(hash)]` shows a
+//!   short non-cryptographic digest instead, so that messages about the same
+//!   value can be correlated, and `#[sensitive(last4)]` shows the last four
+//!   characters of values at least eight characters long. A field passed as
+//!   an additional format argument, as in `.field`, is redacted the same way;
+//!   using it within a larger expression is a compile error. Any `Debug` impl
+//!   of the type is left as is.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum AuthError {
+//!       #[error("invalid token {token}")]
+//!       InvalidToken {
+//!           #[sensitive]
+//!           token: String,
+//!       },
+//!       #[error("unknown api key {0}")]
+//!       UnknownKey(#[sensitive(last4)] String),
+//!   }
+//!   #
+//!   # let error = AuthError::UnknownKey("sk_live_4242424242".to_owned());
+//!   # assert_eq!(error.to_string(), "unknown api key <redacted ...4242>");
+//!   ```
+//!
+//! - A field marked `#[error_id]` is filled in with a fresh [`ErrorId`] by the
+//!   generated `From` impls and constructors, and returned by a generated
+//!   `error_id()` method, so that an identifier shown to a user can be found
+//!   again in the server's logs. The field may also be a `u64` or any other
+//!   type implementing `From<ErrorId>`. On an enum, `error_id()` returns an
+//!   `Option` that is `None` for variants without such a field.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::{Error, ErrorId};
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("could not save your changes; quote {id} when contacting support")]
+//!   pub struct SaveError {
+//!       #[from]
+//!       source: io::Error,
+//!       #[error_id]
+//!       id: ErrorId,
+//!   }
+//!   ```
+//!
+//! - A `SystemTime` or `Instant` field marked `#[timestamp]` is filled in with
+//!   the current time by the generated `From` impls and constructors. Written
+//!   as `{field}` in a message, it is shown as an RFC 3339 date and time in
+//!   UTC, such as `2024-05-01T12:34:56.789Z`. `Option<SystemTime>` works too,
+//!   as does any other type with a `now()` constructor, which is then
+//!   interpolated through its own `Display`.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use std::time::SystemTime;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("upload failed at {at}")]
+//!   pub struct UploadError {
+//!       #[from]
+//!       source: io::Error,
+//!       #[timestamp]
+//!       at: SystemTime,
+//!   }
+//!   ```
+//!
+//! - Errors reported over HTTP can declare `#[status(404)]` on the type or on
+//!   individual variants, which generates a `status_code()` method returning
+//!   the variant's status, else the type's, else 500. The status may also be
+//!   given as a constant such as `#[status(StatusCode::NOT_FOUND)]` of the
+//!   `http` crate, or as `#[error(status = 404)]`. Variants marked
+//!   `#[error(expose = false)]` keep their full message in `Display` but
+//!   render only the reason phrase of their status from the generated
+//!   `public_message()` method, so internal details don't leak into response
+//!   bodies.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(expose = false)]
+//!   pub enum ApiError {
+//!       #[status(404)]
+//!       #[error(expose = true)]
+//!       #[error("no user named {0}")]
+//!       UnknownUser(String),
+//!       #[error("query failed: {0}")]
+//!       Database(String),
+//!   }
+//!   #
+//!   # let error = ApiError::Database("connection reset".to_owned());
+//!   # assert_eq!(error.status_code(), 500);
+//!   # assert_eq!(error.public_message(), "Internal Server Error");
+//!   ```
+//!
+//!   With the `axum` feature enabled, `#[error(into_response)]` on the type
+//!   additionally implements axum's `IntoResponse`, responding with the
+//!   status and the public message as plain text. Writing
+//!   `#[error(into_response(json))]` instead responds with a JSON body of the
+//!   form `{"code": ..., "message": ...}`, where the code comes from
+//!   `#[code("...")]` and is left out if there is none; this layout also needs
+//!   the `serde` feature.
+//!
+//!   Likewise with the `actix` feature, `#[error(response_error)]` implements
+//!   actix-web's `ResponseError` from the same status and public message,
+//!   and accepts `(json)` in the same way. To build the response some other
+//!   way, name a function with `#[error(response_error(with = path))]`; it is
+//!   called as `path(&error, &mut builder)` with an `HttpResponseBuilder`
+//!   already carrying the status, and returns the `HttpResponse`.
+//!
+//!   To document these responses, `#[error(into_responses)]` with the
+//!   `utoipa` feature implements utoipa's `ToSchema` for the body and
+//!   `IntoResponses` with one response per status. Variants sharing a status
+//!   are described together by their message templates, or by the reason
+//!   phrase where the message is not exposed. The error can then be listed
+//!   in `#[utoipa::path(responses(...))]` and stays in sync with the enum.
+//!
+//! - Command line tools can declare `#[exit_code(2)]` on the type or on
+//!   individual variants, which generates an `exit_code()` method returning a
+//!   `std::process::ExitCode`. Variants without an exit code of their own or
+//!   on their enum exit with status 1. Returning [`Exit`] from `main` prints
+//!   the error and its sources to stderr and exits with that status.
+//!
+//! - Errors bridging C APIs can map variants to errno values with
+//!   `#[errno(libc::ENOENT)]` or `#[errno(2)]`, which generates a
+//!   `raw_os_error()` method returning the value. If one variant with a single
+//!   `i32` field is marked `#[errno(other)]`, a `From<i32>` impl is generated
+//!   as well, routing each known value to its variant, with any fields set to
+//!   their default, and every other value to the `other` variant.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum FsError {
+//!       #[error("no such file or directory")]
+//!       #[errno(libc::ENOENT)]
+//!       NotFound,
+//!       #[error("os error {0}")]
+//!       #[errno(other)]
+//!       Other(i32),
+//!   }
+//!   #
+//!   # assert!(matches!(FsError::from(2), FsError::NotFound));
+//!   # assert_eq!(FsError::Other(5).raw_os_error(), Some(5));
+//!   ```
+//!
+//! - Similarly with the `windows` feature enabled, `#[hresult(0x8007_0005)]`
+//!   or `#[hresult(E_ACCESSDENIED)]` naming an `HRESULT` constant generates
+//!   an `hresult()` method for COM callers. Variants of an enum without one
+//!   of their own report the enum's, else `E_FAIL`. A variant marked
+//!   `#[hresult(other)]` holds a `windows::core::Error` and reports its code,
+//!   and enables a `From<windows::core::Error>` impl routing each known
+//!   HRESULT to its variant.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   pub enum ComError {
+//!       #[error("access denied")]
+//!       #[hresult(E_ACCESSDENIED)]
+//!       AccessDenied,
+//!       #[error("not implemented")]
+//!       #[hresult(0x8000_4001)]
+//!       NotImplemented,
+//!       #[error(transparent)]
+//!       #[hresult(other)]
+//!       Other(windows::core::Error),
+//!   }
+//!   ```
+//!
+//! - With the `pyo3` feature enabled, `#[py(exception = ...)]` on the type or
+//!   on individual variants generates `impl From<MyError> for pyo3::PyErr`,
+//!   raising the given Python exception type with the `Display` message.
+//!   Variants without an exception type of their own or on their enum raise
+//!   `RuntimeError`. The crate deriving `Error` needs its own dependency on
+//!   pyo3.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[py(exception = pyo3::exceptions::PyValueError)]
+//!   pub enum ParseError {
+//!       #[error("invalid digit {0:?}")]
+//!       InvalidDigit(char),
+//!       #[error("unknown key {0}")]
+//!       #[py(exception = pyo3::exceptions::PyKeyError)]
+//!       UnknownKey(String),
+//!   }
+//!   ```
+//!
+//! - With the `wasm` feature enabled, `#[error(js_value)]` on the type
+//!   generates `impl From<MyError> for wasm_bindgen::JsValue`, producing a
+//!   JavaScript `Error` with the `Display` message and, if the variant has a
+//!   `#[code("...")]`, a `code` property. Derived errors can then be returned
+//!   directly from `#[wasm_bindgen]` functions as `Result<T, MyError>`. The
+//!   crate deriving `Error` needs its own dependencies on wasm-bindgen and
+//!   js-sys.
+//!
+//! - With `#[error(trace)]` on the type, every generated `From` impl and
+//!   constructor emits a `tracing` event for the error it creates, with the
+//!   `Display` message and, for variants with a source, a `source` field
+//!   holding the message of the source. The level is `error` unless
+//!   `#[level(warn)]` or similar is given on the variant or on the type. The
+//!   crate deriving `Error` needs its own dependency on tracing.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[error(trace)]
+//!   pub enum AppError {
+//!       #[error("failed to read config")]
+//!       Config(#[from] io::Error),
+//!       #[error("request timed out")]
+//!       #[level(warn)]
+//!       Timeout(#[from] Elapsed),
+//!   }
+//!   ```
+//!
+//!   Similarly with the `log` feature enabled, `#[error(log)]` on the type
+//!   generates a `log()` method that logs the error through the `log` crate
+//!   at the level given by `#[level(...)]`, rendering the message followed by
+//!   the messages of its sources as in `could not sync: connection reset`.
+//!
+//!   With the `metrics` feature enabled, `#[error(metrics)]` on the type makes
+//!   the same `From` impls and constructors increment a counter of the
+//!   `metrics` crate named `errors_total`, labeled with the name of the type
+//!   and, for enums, of the variant, as in
+//!   `errors_total{type="AppError",variant="Timeout"}`. The crate deriving
+//!   `Error` needs its own dependency on metrics.
+//!
+//! - With `#[error(hook)]` on the type, every generated `From` impl and
+//!   constructor passes the error it creates to the functions registered
+//!   through [`on_error`], so that every error can be counted or logged in
+//!   one place without touching the code that returns it.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(hook)]
+//!   pub enum StoreError {
+//!       #[error("failed to open the store")]
+//!       Open(#[from] io::Error),
+//!   }
+//!
+//!   fn main() {
+//!       thiserror::on_error(|error| eprintln!("error: {}", error));
+//!       // ...
+//!   }
+//!   ```
+//!
+//! - `#[severity(...)]` on the type or on individual variants generates a
+//!   `severity()` method returning a [`Severity`], one of `Info`, `Warning`,
+//!   `Error` or `Fatal`. Variants without a severity of their own take the
+//!   one on the type, or `Error` if there is none. Logging and alerting layers
+//!   can then act on severity without knowing the concrete error type.
+//!
+//!   ```rust
+//!   # use thiserror::{Error, Severity};
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[severity(Warning)]
+//!   pub enum SyncError {
+//!       #[error("retrying after timeout")]
+//!       Timeout,
+//!       #[error("database is corrupt")]
+//!       #[severity(Fatal)]
+//!       Corrupt,
+//!   }
+//!
+//!   # fn report(error: SyncError) {
+//!   if error.severity() >= Severity::Error {
+//!       eprintln!("page someone: {}", error);
+//!   }
+//!   # }
+//!   ```
+//!
+//! - `#[error(catalog)]` generates a `CATALOG` associated constant listing an
+//!   [`ErrorDescriptor`] for each variant, or a single one for a struct, with
+//!   its name, `#[code("...")]`, severity and message template as written.
+//!   Documentation sites and support tooling can be built from the catalog
+//!   instead of scraping source code.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(catalog)]
+//!   pub enum ApiError {
+//!       #[error("user {0} not found")]
+//!       #[code("E404")]
+//!       NotFound(u64),
+//!       #[error("database unavailable")]
+//!       #[code("E503")]
+//!       Unavailable,
+//!   }
+//!
+//!   for descriptor in ApiError::CATALOG {
+//!       println!("{:?}: {:?}", descriptor.code, descriptor.message);
+//!   }
+//!   ```
+//!
+//! - `#[help("...")]` on a struct or on enum variants attaches a hint for
+//!   resolving the error, generating a `help()` method that returns it as an
+//!   `Option<String>`. Fields are interpolated the same way as in
+//!   `#[error("...")]`. A `#[help(...)]` on the enum applies to variants
+//!   without one of their own, and variants with neither return `None`.
+//!
+//!   ```rust
+//!   # use std::path::PathBuf;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum InstallError {
+//!       #[error("{} already exists", path.display())]
+//!       #[help("try running with --force to overwrite {}", path.display())]
+//!       Exists { path: PathBuf },
+//!       #[error("download failed")]
+//!       Download,
+//!   }
+//!
+//!   # fn report(error: InstallError) {
+//!   eprintln!("error: {}", error);
+//!   if let Some(help) = error.help() {
+//!       eprintln!("help: {}", help);
+//!   }
+//!   # }
+//!   ```
+//!
+//! - `#[retryable]` on a struct or on enum variants marks the operation that
+//!   failed as worth retrying, generating an `is_retryable()` method and an
+//!   implementation of the [`Retryable`] trait for retry middleware to call.
+//!   `#[retryable(if = path)]` decides at runtime instead, by calling
+//!   `path(&error)`. A transparent variant asks its source if the source
+//!   implements `Retryable`, a `#[retryable]` on the enum applies to the
+//!   remaining variants, and anything else is not retryable.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum FetchError {
+//!       #[error("request timed out")]
+//!       #[retryable]
+//!       Timeout,
+//!       #[error("server returned {0}")]
+//!       #[retryable(if = is_server_error)]
+//!       Status(u16),
+//!       #[error("invalid url")]
+//!       InvalidUrl,
+//!   }
+//!
+//!   fn is_server_error(error: &FetchError) -> bool {
+//!       matches!(error, FetchError::Status(500..=599))
+//!   }
+//!   #
+//!   # assert!(FetchError::Status(503).is_retryable());
+//!   # assert!(!FetchError::Status(404).is_retryable());
+//!   ```
+//!
+//! - With the `defmt` feature enabled, `#[error(defmt)]` on the type
+//!   implements `defmt::Format` from the same `#[error("...")]` messages, so
+//!   that embedded targets log the interned format string and the raw field
+//!   values rather than a formatted `String`. Fields referenced by the message
+//!   must implement `defmt::Format`, and only the format specs that defmt
+//!   understands can be used. Named placeholders such as `{value}` are passed
+//!   to defmt positionally.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[error(defmt)]
+//!   pub enum SensorError {
+//!       #[error("sensor {0} timed out")]
+//!       Timeout(u8),
+//!       #[error("reading {value} out of range")]
+//!       OutOfRange { value: u16 },
+//!   }
+//!   ```
+//!
+//! - With the `sentry` feature enabled, `#[sentry(...)]` on a struct or on
+//!   enum variants declares how the error is grouped and tagged in Sentry,
+//!   generating `sentry_fingerprint()` and `sentry_tags()` methods for the
+//!   reporting layer to pass along. `fingerprint = "..."` may be repeated to
+//!   build up the fingerprint, which defaults to `["{{ default }}"]`. Tags
+//!   given on the enum apply to every variant, and a variant's own tags take
+//!   precedence.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[sentry(tag(component = "store"))]
+//!   pub enum StoreError {
+//!       #[error("i/o failed")]
+//!       #[sentry(fingerprint = "store-io", tag(subsystem = "io"))]
+//!       Io(#[source] io::Error),
+//!       #[error("key {0} not found")]
+//!       NotFound(String),
+//!   }
+//!   ```
+//!
+//! - `#[meta(...)]` on a struct or on enum variants attaches key-value context
+//!   for logging and tracing pipelines, returned by a generated `metadata()`
+//!   method as `(&'static str, String)` pairs. Values are literals or fields
+//!   of the error written as `.field`, which are rendered with `Display` and
+//!   stay redacted if they are `#[sensitive]`. Literals given on the enum apply
+//!   to every variant, and a variant's own entries take precedence.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[meta(subsystem = "storage")]
+//!   pub enum StorageError {
+//!       #[error("tenant {tenant_id} is over quota")]
+//!       #[meta(tenant = .tenant_id, "quota.kind" = "bytes")]
+//!       OverQuota { tenant_id: u64 },
+//!       #[error("disk full")]
+//!       DiskFull,
+//!   }
+//!
+//!   fn record(error: &StorageError) {
+//!       for (key, value) in error.metadata() {
+//!           println!("{}={}", key, value);
+//!       }
+//!   }
+//!   #
+//!   # record(&StorageError::OverQuota { tenant_id: 7 });
+//!   ```
+//!
+//! - With the `anyhow` feature enabled, `#[error(from_anyhow)]` on an enum
+//!   generates `From<anyhow::Error>`, which tries to downcast the error into
+//!   the type of each `#[from]` field in the order the variants are declared.
+//!   An error of none of those types goes to the variant marked
+//!   `#[error(fallback)]`, whose only field holds the `anyhow::Error`, or
+//!   without one, boxed into the `#[error(other)]` variant. This lets
+//!   application code built on anyhow hand typed errors back at an API
+//!   boundary with `?`.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[error(from_anyhow)]
+//!   pub enum ApiError {
+//!       #[error("i/o failed")]
+//!       Io(#[from] io::Error),
+//!       #[error(transparent)]
+//!       #[error(fallback)]
+//!       Other(anyhow::Error),
+//!   }
+//!   ```
+//!
+//!   The `eyre` feature provides the same for `eyre::Report` with
+//!   `#[error(from_eyre)]`. In the other direction, `#[error(into_report)]`
+//!   generates an `into_report()` method converting the error into an
+//!   `eyre::Report` that keeps its chain of sources, with the `#[code("...")]`
+//!   of the error attached as a color-eyre section.
+//!
+//! - With the `jsonrpc` feature enabled, `#[jsonrpc(code = -32001)]` on a
+//!   struct or on enum variants generates a `to_jsonrpc_error()` method
+//!   returning a [`JsonRpcError`] with that code and the `Display` message.
+//!   A code on the enum applies to variants without one of their own, and
+//!   the default is -32603, the code JSON-RPC 2.0 reserves for internal
+//!   errors. `data = field` serializes that field as the object's data.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   pub enum RpcError {
+//!       #[error("method {0} not found")]
+//!       #[jsonrpc(code = -32601)]
+//!       MethodNotFound(String),
+//!       #[error("invalid params")]
+//!       #[jsonrpc(code = -32602, data = fields)]
+//!       InvalidParams { fields: Vec<String> },
+//!   }
+//!   ```
+//!
+//! - For crates exposing a C API, `#[repr_code]` on the type assigns each
+//!   variant a stable `i32` code and generates a `repr_code()` method along
+//!   with two `extern "C"` functions named after the type: `store_error_code`
+//!   returning the code, and `store_error_message` writing the `Display`
+//!   message into a caller-provided buffer. Like the discriminants of a C
+//!   enum, codes count up from 1 unless a variant gives its own with
+//!   `#[repr_code(-10)]`. The type must not be generic.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[repr_code]
+//!   pub enum StoreError {
+//!       #[error("key not found")]
+//!       NotFound, // 1
+//!       #[error("store is read only")]
+//!       ReadOnly, // 2
+//!       #[error("store is corrupt")]
+//!       #[repr_code(-10)]
+//!       Corrupt,
+//!   }
+//!   #
+//!   # assert_eq!(store_error_code(&StoreError::ReadOnly), 2);
+//!   ```
+//!
+//! - Variants can be tagged with a stable identifier using `#[code("...")]`.
+//!   Adding `#[error(from_str)]` to the type generates a `FromStr` impl that
+//!   parses a code back into its variant, filling in any fields with
+//!   `Default::default()`, along with a `codes()` method listing every code
+//!   so that the round trip can be checked in tests. Adding `#[error(code)]`
+//!   generates a `code()` method returning the code of the variant at hand,
+//!   which requires every variant to have one. Two variants cannot share a
+//!   code.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(from_str, code)]
+//!   pub enum ApiError {
+//!       #[error("rate limited")]
+//!       #[code("E0001")]
+//!       RateLimited,
+//!       #[error("quota {0} exceeded")]
+//!       #[code("E0002")]
+//!       Quota(u64),
+//!   }
+//!   #
+//!   # for code in ApiError::codes() {
+//!   #     assert_eq!(code.parse::<ApiError>().unwrap().code(), *code);
+//!   # }
+//!   ```
+//!
+//! - With the `serde` feature enabled, `#[error(serialize)]` on the type
+//!   implements `serde::Serialize` for it. The error is serialized as its
+//!   variant name, its rendered message, its `#[code("...")]` if it has one,
+//!   and the messages of its chain of sources. The fields themselves are not
+//!   serialized, so backtraces and other internals stay out of response
+//!   bodies.
+//!
+//!   ```json
+//!   {
+//!     "variant": "Storage",
+//!     "message": "storage failed",
+//!     "chain": ["could not write block 7", "disk full"]
+//!   }
+//!   ```
+//!
+//!   For errors made of plain data, `#[error(deserialize)]` implements
+//!   `serde::Deserialize` from the fields instead, in the same representation
+//!   that serde's own `#[derive(Serialize)]` writes, so that an error sent
+//!   across an RPC boundary can be rebuilt on the other side. Backtraces,
+//!   span traces and trait objects cannot be deserialized and are rejected.
+//!   Any `#[serde(...)]` attributes on the type are respected.
+//!
+//!   With the `schemars` feature enabled as well, adding
+//!   `#[error(json_schema)]` next to `#[error(serialize)]` implements
+//!   `schemars::JsonSchema` describing that layout, listing the possible
+//!   variant names and codes, so that error bodies appear correctly in
+//!   generated API schemas. The crate deriving `Error` needs its own
+//!   dependency on schemars 1.x.
+//!
+//! - With the `miette` feature enabled, `#[error(diagnostic)]` implements
+//!   `miette::Diagnostic` for the type from its `#[diagnostic(code(...),
+//!   help(...), url(...))]` attributes. The help and url messages interpolate
+//!   fields the same way `#[error(...)]` does. On an enum the attribute may go
+//!   on the enum to set defaults for every variant, and on individual variants
+//!   to override them. A variant without a diagnostic code falls back to its
+//!   `#[code("...")]`. The crate deriving `Error` needs its own dependency on
+//!   miette. Other keys of miette's, such as `severity(...)`, are ignored.
+//!   Without `#[error(diagnostic)]`, `#[diagnostic]` is left alone for
+//!   `#[derive(miette::Diagnostic)]`, so the two derives never collide.
+//!
+//!   ```ignore
+//!   #[derive(Error, Debug)]
+//!   #[error(diagnostic)]
+//!   #[diagnostic(url("https://docs.example.com/errors/{}", self.code().unwrap()))]
+//!   pub enum ConfigError {
+//!       #[error("invalid port {port}")]
+//!       #[diagnostic(code(config::port), help("ports must be at most {max}"))]
+//!       Port { port: u32, max: u16 },
+//!       #[error("missing key {0}")]
+//!       #[code("config::missing")]
+//!       Missing(String),
+//!   }
+//!   ```
+//!
+//! - Errors that borrow from their input can request an owned counterpart
+//!   with `#[error(owned)]`. The derive generates a `MyErrorOwned` type with
+//!   the same variants and messages, in which `&'a T` fields become
+//!   `T::Owned` and `Cow<'a, T>` fields become `Cow<'static, T>`, plus a
+//!   `into_owned()` method converting from one to the other. A
+//!   `#[non_exhaustive]` on the type or its variants carries over to the twin.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(owned)]
+//!   pub enum ParseError<'a> {
+//!       #[error("unexpected token {0:?}")]
+//!       Unexpected(&'a str),
+//!   }
+//!
+//!   fn store(error: ParseError) -> ParseErrorOwned {
+//!       error.into_owned()
+//!   }
+//!   ```
+//!
+//! - An inherent `chain()` method is generated for types carrying an
+//!   `#[error(chain)]` attribute. It iterates over the error itself followed by
+//!   every lower level source, which saves writing a `source()` loop by hand.
+//!   A `root_cause()` method returning the innermost source is generated
+//!   alongside it.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(chain)]
+//!   pub enum MyError {
+//!       #[error("failed to read config")]
+//!       Config(#[source] io::Error),
+//!   }
+//!
+//!   # fn log(error: MyError) {
+//!   for cause in error.chain() {
+//!       eprintln!("caused by: {}", cause);
+//!   }
+//!   eprintln!("root cause: {}", error.root_cause());
+//!   # }
+//!   ```
+//!
+//!   Regardless of `#[error(chain)]`, the [`ErrorDowncastExt`] trait looks
+//!   for a concrete type anywhere in the chain of any error, answering "is
+//!   there an `io::Error` in here?" in one call.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   use thiserror::ErrorDowncastExt;
+//!   #
+//!   # #[derive(Error, Debug)]
+//!   # #[error("failed to read config")]
+//!   # pub struct MyError(#[source] io::Error);
+//!
+//!   # fn is_missing(error: &MyError) -> bool {
+//!   match error.downcast_ref_chain::<io::Error>() {
+//!       Some(io) => io.kind() == io::ErrorKind::NotFound,
+//!       None => false,
+//!   }
+//!   # }
+//!   ```
+//!
+//! - `#[error(name)]` generates a `name()` method returning the identifier of
+//!   the variant, or of the type itself for a struct, as a `&'static str`.
+//!   This is a stable key for metrics and logs that does not allocate the way
+//!   formatting the message would.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(name)]
+//!   pub enum FetchError {
+//!       #[error("request timed out")]
+//!       Timeout,
+//!       #[error("server returned {0}")]
+//!       Status(u16),
+//!   }
+//!
+//!   assert_eq!(FetchError::Status(503).name(), "Status");
+//!   ```
+//!
+//! - `#[error(describe)]` generates a `describe()` method returning the doc
+//!   comment of the variant, or of the type for a struct, as a
+//!   `&'static str`. Variants without a doc comment return an empty string.
+//!   This gives CLIs and APIs a longer explanation to show next to the
+//!   message, kept in one place with the variant it explains.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(describe)]
+//!   pub enum LoginError {
+//!       /// The password did not match. Accounts are locked after five
+//!       /// failed attempts.
+//!       #[error("wrong password")]
+//!       WrongPassword,
+//!   }
+//!
+//!   assert_eq!(
+//!       LoginError::WrongPassword.describe(),
+//!       "The password did not match. Accounts are locked after five\nfailed attempts.",
+//!   );
+//!   ```
+//!
+//! - `#[error(kind)]` on an enum generates a fieldless `<Name>Kind` enum with
+//!   one variant per variant of the error, deriving `Copy`, `Eq` and `Hash`,
+//!   along with a `kind()` method. Kinds are cheap to compare and can be used
+//!   as map keys without holding on to the data the error carries. If the
+//!   error is `#[non_exhaustive]`, so is the kind enum.
+//!
+//!   ```rust
+//!   # use std::collections::HashMap;
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(kind)]
+//!   pub enum FetchError {
+//!       #[error("request timed out")]
+//!       Timeout,
+//!       #[error("I/O error")]
+//!       Io(#[from] io::Error),
+//!   }
+//!
+//!   # fn count(errors: &[FetchError]) {
+//!   let mut counts = HashMap::new();
+//!   for error in errors {
+//!       *counts.entry(error.kind()).or_insert(0) += 1;
+//!   }
+//!   println!("{} timeouts", counts.get(&FetchErrorKind::Timeout).unwrap_or(&0));
+//!   # }
+//!   ```
+//!
+//! - `#[error(constructors)]` on an enum generates a constructor per variant,
+//!   named after the variant in snake case and taking each field as
+//!   `impl Into<T>`. Backtrace and span trace fields are captured rather than
+//!   passed in, as are fields of type `&'static Location<'static>`, which
+//!   record where the constructor was called from. The constructor of a
+//!   `#[deprecated]` variant is deprecated too, whereas the derive's own uses
+//!   of the variant do not warn.
+//!
+//!   ```rust
+//!   # use std::panic::Location;
+//!   # use std::path::PathBuf;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(constructors)]
+//!   pub enum StoreError {
+//!       #[error("{} not found", path.display())]
+//!       NotFound {
+//!           path: PathBuf,
+//!           location: &'static Location<'static>,
+//!       },
+//!       #[error("store is closed")]
+//!       Closed,
+//!   }
+//!
+//!   # fn open(path: &str) -> Result<(), StoreError> {
+//!   return Err(StoreError::not_found(path));
+//!   # }
+//!   ```
+//!
+//!   For variants with many fields, `#[error(builder)]` on the variant
+//!   generates a builder instead, so that adding a field later does not break
+//!   every place the error is created. The builder function takes the source,
+//!   if there is one, and `build()` fills in any field that was not set with
+//!   its `Default`.
+//!
+//!   ```rust
+//!   # use std::time::Duration;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   pub enum NetError {
+//!       #[error("{op} timed out after {elapsed:?}")]
+//!       #[error(builder)]
+//!       Timeout {
+//!           op: String,
+//!           elapsed: Duration,
+//!           host: String,
+//!           retries: u32,
+//!       },
+//!   }
+//!
+//!   # let elapsed = Duration::from_secs(3);
+//!   let error = NetError::timeout_builder()
+//!       .op("read")
+//!       .elapsed(elapsed)
+//!       .build();
+//!   ```
+//!
+//! - `#[error(context)]` on an enum generates a `<Name>Context` extension
+//!   trait for `Result`, with a `ctx_*` method for each variant that has a
+//!   source. The method turns the error of the result into that variant,
+//!   taking the variant's other fields as arguments, so a foreign error can
+//!   be given context with `?` at the point where it occurs. The enum must not
+//!   be generic.
+//!
+//!   ```rust
+//!   # use std::fs;
+//!   # use std::io;
+//!   # use std::path::PathBuf;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(context)]
+//!   pub enum ConfigError {
+//!       #[error("failed to read {}", path.display())]
+//!       Read { source: io::Error, path: PathBuf },
+//!       #[error("invalid port on line {1}")]
+//!       Port(#[source] std::num::ParseIntError, usize),
+//!   }
+//!
+//!   fn load(path: &str) -> Result<String, ConfigError> {
+//!       let config = fs::read_to_string(path).ctx_read(path)?;
+//!       Ok(config)
+//!   }
+//!   ```
+//!
+//! - `#[error(macros)]` generates `bail!` and `ensure!` style macros for the
+//!   type, named after it in snake case, which return early with the error
+//!   converted by `From` into the function's error type. The argument is the
+//!   variant as it would be written after `MyError::`, or for a struct, the
+//!   fields as written after `MyError`. Like any `macro_rules!` macro they can
+//!   be used after the type's definition in the same module and its children.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(macros)]
+//!   pub enum StoreError {
+//!       #[error("key {0} is too long")]
+//!       KeyTooLong(usize),
+//!       #[error("store is read-only")]
+//!       ReadOnly,
+//!   }
+//!
+//!   fn put(key: &str, read_only: bool) -> Result<(), StoreError> {
+//!       store_error_ensure!(key.len() <= 64, KeyTooLong(key.len()));
+//!       if read_only {
+//!           store_error_bail!(ReadOnly);
+//!       }
+//!       Ok(())
+//!   }
+//!   ```
+//!
+//! - Enums carrying `#[error(accessors)]` get an `is_*` method per variant,
+//!   and for variants with fields an `as_*` method borrowing them and an
+//!   `into_*` method taking them, named after the variant in snake case.
+//!   Variants with several fields are returned as a tuple.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error(accessors)]
+//!   pub enum StoreError {
+//!       #[error("key not found")]
+//!       NotFound,
+//!       #[error("I/O error")]
+//!       Io(#[from] io::Error),
+//!   }
+//!
+//!   # fn handle(error: StoreError) -> Result<(), StoreError> {
+//!   if error.is_not_found() {
+//!       return Ok(());
+//!   }
+//!   if let Some(io) = error.as_io() {
+//!       eprintln!("{}", io.kind());
+//!   }
+//!   let io: io::Error = error.into_io()?;
+//!   # drop(io);
+//!   # Ok(())
+//!   # }
+//!   ```
+//!
+//! - The derive works in `no_std` crates with the default `std` feature
+//!   turned off. Derived errors then implement `core::error::Error`, which
+//!   needs Rust 1.81 or newer, and backtrace fields are not reported.
+//!   Options that allocate or that integrate with std itself, such as
+//!   `#[error(message)]` or `#[exit_code(...)]`, still need std. A `no_std`
+//!   crate keeps building when some other crate in the dependency graph turns
+//!   the `std` feature back on.
+//!
+//!   ```toml
+//!   [dependencies]
+//!   thiserror = { version = "1.0", default-features = false }
+//!   ```
+//!
+//! - `#[error(no_alloc)]` on a struct or enum guarantees that the derived
+//!   impls never allocate, for targets without a heap. Options that would
+//!   build a `String`, a `Box` or an `Arc`, such as `#[error(message)]`,
+//!   `#[help(...)]` or `#[from(box)]`, are rejected at compile time. Fields
+//!   that allocate on their own are still up to you.
+//!
+//!   ```rust
+//!   # use thiserror::Error;
+//!   #[derive(Error, Debug)]
+//!   #[error(no_alloc)]
+//!   pub enum SensorError {
+//!       #[error("sensor {0} timed out")]
+//!       Timeout(u8),
+//!       #[error("bus fault")]
+//!       Bus(#[from] core::fmt::Error),
+//!   }
+//!   ```
+//!
+//! - `#[error(trait = path::to::Trait)]` additionally implements a
+//!   framework's own error trait, through the [`CustomError`] adapter that
+//!   hands out the source as `&dyn Trait`. The framework bridges the adapter
+//!   to its trait once, with a blanket impl.
+//!
+//!   ```rust
+//!   # use std::fmt::{Debug, Display};
+//!   # use thiserror::{CustomError, Error};
+//!   #
+//!   # pub trait RichError: Debug + Display {}
+//!   # impl<T: CustomError<dyn RichError>> RichError for T {}
+//!   #
+//!   #[derive(Error, Debug)]
+//!   #[error("page {0} is missing")]
+//!   #[error(trait = RichError)]
+//!   pub struct MissingPage(u32);
+//!   ```
+//!
+//! - `#[error(cold)]` on a struct or enum marks the generated `From` impls
+//!   and `fmt` with `#[cold]` and `#[inline(never)]`, keeping error paths out
+//!   of hot loops. `#[error(inline)]` asks for `#[inline]` instead.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #[derive(Error, Debug)]
+//!   #[error(cold)]
+//!   pub enum DecodeError {
+//!       #[error("truncated input")]
+//!       Truncated,
+//!       #[error("read failed")]
+//!       Io(#[from] io::Error),
+//!   }
+//!   ```
+//!
+//! - `#[error(dispatch)]` on an enum generates a single match over the
+//!   variants that `source()`, `backtrace_ref()` and `fmt()` all go through,
+//!   instead of one match each. For enums with hundreds of variants this cuts
+//!   the code handed to the compiler, at the cost of an indirect call per
+//!   method. Generic enums are not supported.
+//!
+//!   ```rust
+//!   # use std::io;
+//!   # use thiserror::Error;
+//!   #[derive(Error, Debug)]
+//!   #[error(dispatch)]
+//!   pub enum StorageError {
+//!       #[error("disk full")]
+//!       DiskFull,
+//!       #[error("i/o failed on block {block}")]
+//!       Io { block: u64, source: io::Error },
+//!   }
+//!   ```
+//!
+//! - The generated code refers to this crate as `thiserror`, which fails to
+//!   resolve when the derive is used through a facade crate that re-exports
+//!   it. `#[error(crate = path)]` on the struct or enum names the path under
+//!   which thiserror is reachable instead.
+//!
+//!   ```rust
+//!   # mod facade {
+//!   #     pub mod __private {
+//!   #         pub use thiserror::*;
+//!   #     }
+//!   #     pub use thiserror::Error;
+//!   # }
+//!   use facade::Error;
+//!
+//!   #[derive(Error, Debug)]
+//!   #[error(crate = facade::__private)]
+//!   #[error("request timed out")]
+//!   pub struct TimeoutError;
 //!   ```
 //!
 //! - See also the [`anyhow`] library for a convenient single error type to use
@@ -157,14 +1557,115 @@
 //!
 //!   [`anyhow`]: https://github.com/dtolnay/anyhow
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+use core::error;
+#[cfg(feature = "std")]
+use std::error;
+
 mod aserror;
+#[cfg(feature = "std")]
+mod backtrace;
+mod chain;
+#[cfg(feature = "std")]
+mod code;
+mod custom;
+mod descriptor;
+#[cfg(feature = "std")]
+pub mod diff;
+mod dispatch;
 mod display;
+mod downcast;
+#[cfg(feature = "std")]
+mod error_id;
+#[cfg(feature = "std")]
+mod exit;
+#[cfg(feature = "std")]
+mod ffi;
+#[cfg(feature = "std")]
+mod hook;
+mod http;
+#[cfg(feature = "humantime")]
+mod humantime;
+#[cfg(feature = "jsonrpc")]
+mod jsonrpc;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod redact;
+#[cfg(feature = "registry")]
+mod registry;
+mod retry;
+mod sanitize;
+#[cfg(feature = "serde")]
+mod serialize;
+mod severity;
+#[cfg(feature = "std")]
+mod timestamp;
 
+pub use crate::aserror::AsDynError;
+#[cfg(feature = "std")]
+pub use crate::backtrace::Backtraced;
+pub use crate::chain::Chain;
+#[cfg(feature = "std")]
+pub use crate::code::ParseCodeError;
+pub use crate::custom::CustomError;
+pub use crate::descriptor::ErrorDescriptor;
+pub use crate::downcast::ErrorDowncastExt;
+#[cfg(feature = "std")]
+pub use crate::error_id::ErrorId;
+#[cfg(feature = "std")]
+pub use crate::exit::{Exit, ExitStatus};
+#[cfg(feature = "std")]
+pub use crate::hook::on_error;
+#[cfg(feature = "jsonrpc")]
+pub use crate::jsonrpc::JsonRpcError;
+#[cfg(feature = "registry")]
+pub use crate::registry::{register, registry, RegisteredError, Registrable, Registry};
+pub use crate::retry::Retryable;
+pub use crate::severity::Severity;
 pub use thiserror_impl::*;
 
 // Not public API.
 #[doc(hidden)]
 pub mod private {
+    // Generated code names std and the Error trait through here, so that
+    // whether they are available is decided by the features of this crate,
+    // not by those of the derive macro or of the crate deriving Error.
     pub use crate::aserror::AsDynError;
-    pub use crate::display::{DisplayAsDisplay, PathAsDisplay};
+    #[cfg(feature = "std")]
+    pub use crate::backtrace::{BacktraceOf, BacktracedDelegate, BacktracedFallback};
+    pub use crate::chain::Chain;
+    pub use crate::dispatch::Dispatch;
+    pub use crate::display::{
+        write_display, DisplayAsDisplay, DisplayChain, DisplaySource, DisplayWith, LossyAsDisplay,
+        PathAsDisplay,
+    };
+    pub use crate::error::Error;
+    #[cfg(feature = "std")]
+    pub use crate::ffi::write_c_message;
+    #[cfg(feature = "std")]
+    pub use crate::hook::run_error_hooks;
+    pub use crate::http::canonical_reason;
+    #[cfg(feature = "serde")]
+    pub use crate::http::ResponseBody;
+    #[cfg(feature = "humantime")]
+    pub use crate::humantime::humantime;
+    #[cfg(feature = "metrics")]
+    pub use crate::metrics::Counted;
+    pub use crate::redact::{Redacted, RedactedHash, RedactedLast4};
+    #[cfg(feature = "registry")]
+    pub use crate::registry::Node as RegistryNode;
+    pub use crate::retry::{Retry, RetryableDelegate, RetryableFallback};
+    pub use crate::sanitize::Sanitized;
+    #[cfg(feature = "serde")]
+    pub use crate::serialize::serialize_error;
+    #[cfg(feature = "std")]
+    pub use crate::timestamp::DisplayTimestamp;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    #[cfg(feature = "jsonrpc")]
+    pub use serde_json;
+    #[cfg(feature = "std")]
+    pub use std;
 }
@@ -0,0 +1,8 @@
+// Not public API. Implemented by generated code for types marked
+// #[error(metrics)], incrementing the `errors_total` counter of the metrics
+// crate for an error as it is created. Generated From impls and constructors
+// call it through this trait so that no method is added to the error type.
+#[doc(hidden)]
+pub trait Counted {
+    fn count(&self);
+}
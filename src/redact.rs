@@ -0,0 +1,144 @@
+use core::fmt::{self, Debug, Display, Write};
+
+// Least number of characters a `#[sensitive(last4)]` value needs before its
+// last four are shown, so that short values are not given away entirely.
+const MIN_LAST4_LEN: usize = 8;
+
+// Stands in for a `#[sensitive]` field interpolated into a message. The value
+// itself is never formatted.
+pub struct Redacted;
+
+impl Display for Redacted {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.pad("<redacted>")
+    }
+}
+
+impl Debug for Redacted {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, formatter)
+    }
+}
+
+// Wrapper applied to `#[sensitive(hash)]` fields. Shows a digest of the value,
+// so that messages about the same value can be told apart from messages about
+// another without revealing either. FNV-1a keeps the digest the same across
+// builds and platforms; it is not a cryptographic hash.
+pub struct RedactedHash<T>(pub T);
+
+impl<T: Display> Display for RedactedHash<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_hash(formatter, format_args!("{}", self.0))
+    }
+}
+
+impl<T: Debug> Debug for RedactedHash<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_hash(formatter, format_args!("{:?}", self.0))
+    }
+}
+
+fn write_hash(formatter: &mut fmt::Formatter, value: fmt::Arguments) -> fmt::Result {
+    let mut hasher = Fnv(0x811c_9dc5);
+    hasher.write_fmt(value)?;
+    let mut placeholder = Placeholder::new();
+    write!(placeholder, "<redacted #{:08x}>", hasher.0)?;
+    formatter.pad(placeholder.as_str())
+}
+
+struct Fnv(u32);
+
+impl Write for Fnv {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.0 = (self.0 ^ u32::from(byte)).wrapping_mul(0x0100_0193);
+        }
+        Ok(())
+    }
+}
+
+// Wrapper applied to `#[sensitive(last4)]` fields. Shows the last four
+// characters of the value, as is customary for card numbers and API keys.
+pub struct RedactedLast4<T>(pub T);
+
+impl<T: Display> Display for RedactedLast4<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_last4(formatter, format_args!("{}", self.0))
+    }
+}
+
+// The last four characters are those of the value itself, also for a `{:?}`
+// placeholder, whose quotes and escapes would otherwise take their place.
+impl<T: Display> Debug for RedactedLast4<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_last4(formatter, format_args!("{}", self.0))
+    }
+}
+
+fn write_last4(formatter: &mut fmt::Formatter, value: fmt::Arguments) -> fmt::Result {
+    let mut tail = Tail {
+        chars: ['\0'; 4],
+        len: 0,
+    };
+    tail.write_fmt(value)?;
+    if tail.len < MIN_LAST4_LEN {
+        return formatter.pad("<redacted>");
+    }
+    let mut placeholder = Placeholder::new();
+    placeholder.write_str("<redacted ...")?;
+    for i in tail.len - 4..tail.len {
+        placeholder.write_char(tail.chars[i % 4])?;
+    }
+    placeholder.write_char('>')?;
+    formatter.pad(placeholder.as_str())
+}
+
+// Keeps the last four characters written to it.
+struct Tail {
+    chars: [char; 4],
+    len: usize,
+}
+
+impl Write for Tail {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            self.chars[self.len % 4] = ch;
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+// The text standing in for a redacted value, put together without allocating
+// so that it can be padded as a whole. Long enough for `<redacted ...` and
+// four characters of up to four bytes each, plus `>`.
+struct Placeholder {
+    bytes: [u8; 32],
+    len: usize,
+}
+
+impl Placeholder {
+    fn new() -> Self {
+        Placeholder {
+            bytes: [0; 32],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole strs are ever written to it.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+impl Write for Placeholder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
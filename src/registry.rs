@@ -0,0 +1,108 @@
+use crate::descriptor::ErrorDescriptor;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// An error type registered by the derive macro.
+#[derive(Copy, Clone, Debug)]
+pub struct RegisteredError {
+    /// Name of the derived type.
+    pub type_name: &'static str,
+    /// Rust module path in which the type was defined.
+    pub module_path: &'static str,
+    /// One descriptor per variant, or a single descriptor for structs.
+    pub descriptors: &'static [ErrorDescriptor],
+}
+
+/// Returns an iterator over every error type derived anywhere in the running
+/// binary.
+///
+/// Registration happens before `main` through a static constructor emitted by
+/// the derive macro, so the registry is complete by the time user code runs.
+/// Static constructors are only available on Linux, Android, the BSDs,
+/// illumos, macOS, iOS and Windows. On other targets, such as wasm32, error
+/// types have to be added with [`register()`] before they are listed. The
+/// iteration order is unspecified.
+pub fn registry() -> Registry {
+    Registry {
+        next: unsafe { HEAD.load(Ordering::Acquire).as_ref() },
+    }
+}
+
+/// Iterator returned by [`registry()`].
+#[derive(Clone)]
+pub struct Registry {
+    next: Option<&'static Node>,
+}
+
+impl Iterator for Registry {
+    type Item = &'static RegisteredError;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        self.next = unsafe { node.next.load(Ordering::Acquire).as_ref() };
+        Some(node.value)
+    }
+}
+
+/// Adds an error type to the registry by hand.
+///
+/// This is only needed on targets without static constructors, see
+/// [`registry()`]. Registering a type that is already registered has no
+/// effect, so it is fine to call this on every target.
+///
+/// ```rust
+/// # use thiserror::Error;
+/// #
+/// #[derive(Error, Debug)]
+/// #[error("disk full")]
+/// pub struct DiskFull;
+///
+/// thiserror::register::<DiskFull>();
+/// assert!(thiserror::registry().any(|registered| registered.type_name == "DiskFull"));
+/// ```
+pub fn register<E: Registrable + ?Sized>() {
+    E::node().register();
+}
+
+/// Implemented by the derive macro for every error type while the `registry`
+/// feature is enabled, see [`register()`].
+pub trait Registrable {
+    #[doc(hidden)]
+    fn node() -> &'static Node;
+}
+
+static HEAD: AtomicPtr<Node> = AtomicPtr::new(ptr::null_mut());
+
+// Not public API. Used by generated code.
+#[doc(hidden)]
+pub struct Node {
+    value: &'static RegisteredError,
+    next: AtomicPtr<Node>,
+    registered: AtomicBool,
+}
+
+impl Node {
+    pub const fn new(value: &'static RegisteredError) -> Self {
+        Node {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+            registered: AtomicBool::new(false),
+        }
+    }
+
+    pub fn register(&'static self) {
+        // A node can only be linked into the list once.
+        if self.registered.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let new = self as *const Node as *mut Node;
+        let mut head = HEAD.load(Ordering::Relaxed);
+        loop {
+            self.next.store(head, Ordering::Relaxed);
+            match HEAD.compare_exchange_weak(head, new, Ordering::Release, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(prev) => head = prev,
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+/// Errors that know whether the failed operation is worth retrying.
+///
+/// Implemented by `#[derive(Error)]` for types carrying `#[retryable]`, so
+/// retry middleware can ask any such error without downcasting to it. A
+/// transparent variant asks its source through this trait, and counts as not
+/// retryable if the source does not implement it.
+///
+/// ```rust
+/// # use std::io;
+/// # use thiserror::{Error, Retryable};
+/// #
+/// #[derive(Error, Debug)]
+/// pub enum FetchError {
+///     #[error("request timed out")]
+///     #[retryable]
+///     Timeout,
+///     #[error("invalid url")]
+///     InvalidUrl,
+/// }
+///
+/// fn should_retry(error: &dyn Retryable, attempt: u32) -> bool {
+///     attempt < 3 && error.is_retryable()
+/// }
+/// #
+/// # assert!(should_retry(&FetchError::Timeout, 0));
+/// # assert!(!should_retry(&FetchError::InvalidUrl, 0));
+/// ```
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+#[cfg(feature = "std")]
+impl<T: Retryable + ?Sized> Retryable for Box<T> {
+    fn is_retryable(&self) -> bool {
+        (**self).is_retryable()
+    }
+}
+
+// Lets a transparent variant ask its source whether it is retryable, if the
+// source implements Retryable, without requiring that it does. Method
+// resolution picks RetryableDelegate on `&Retry<T>` when T: Retryable, and
+// otherwise autorefs once more to reach RetryableFallback.
+pub struct Retry<'a, T: ?Sized>(pub &'a T);
+
+pub trait RetryableDelegate {
+    fn is_retryable(&self) -> bool;
+}
+
+impl<T: Retryable + ?Sized> RetryableDelegate for Retry<'_, T> {
+    fn is_retryable(&self) -> bool {
+        self.0.is_retryable()
+    }
+}
+
+pub trait RetryableFallback {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
+impl<T: ?Sized> RetryableFallback for &Retry<'_, T> {}
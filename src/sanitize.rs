@@ -0,0 +1,144 @@
+use core::fmt::{self, Alignment, Debug, Display, Write};
+
+// Number of characters of a single field rendered before truncating.
+const MAX_LEN: usize = 256;
+
+// Wrapper applied to interpolated fields by `#[error(fuzz_safe)]`. Control and
+// invisible formatting characters are escaped so that untrusted input cannot
+// inject terminal escape sequences, fake log lines, or reorder the text around
+// it with bidirectional overrides, and long values are cut off with an
+// ellipsis. A width given in the placeholder pads the sanitized text.
+pub struct Sanitized<T>(pub T);
+
+impl<T: Display> Display for Sanitized<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_sanitized(formatter, format_args!("{}", self.0))
+    }
+}
+
+impl<T: Debug> Debug for Sanitized<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write_sanitized(formatter, format_args!("{:?}", self.0))
+    }
+}
+
+fn write_sanitized(formatter: &mut fmt::Formatter, value: fmt::Arguments) -> fmt::Result {
+    let width = match formatter.width() {
+        Some(width) => width,
+        None => {
+            let mut writer = Writer::new(formatter);
+            writer.write_fmt(value)?;
+            return writer.finish();
+        }
+    };
+
+    // Measure the sanitized text first, as it is not kept anywhere.
+    let mut counter = Writer::new(Count(0));
+    counter.write_fmt(value)?;
+    let len = counter.finish_len();
+
+    let padding = width.saturating_sub(len);
+    let (before, after) = match formatter.align() {
+        Some(Alignment::Right) => (padding, 0),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(Alignment::Left) | None => (0, padding),
+    };
+    let fill = formatter.fill();
+    for _ in 0..before {
+        formatter.write_char(fill)?;
+    }
+    let mut writer = Writer::new(&mut *formatter);
+    writer.write_fmt(value)?;
+    writer.finish()?;
+    for _ in 0..after {
+        formatter.write_char(fill)?;
+    }
+    Ok(())
+}
+
+struct Writer<W> {
+    out: W,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl<W: Write> Writer<W> {
+    fn new(out: W) -> Self {
+        Writer {
+            out,
+            remaining: MAX_LEN,
+            truncated: false,
+        }
+    }
+
+    fn finish(mut self) -> fmt::Result {
+        if self.truncated {
+            self.out.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+impl Writer<Count> {
+    fn finish_len(self) -> usize {
+        self.out.0 + if self.truncated { 3 } else { 0 }
+    }
+}
+
+impl<W: Write> Write for Writer<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            if self.remaining == 0 {
+                self.truncated = true;
+                return Ok(());
+            }
+            self.remaining -= 1;
+            if ch.is_control() || is_invisible(ch) {
+                write!(self.out, "{}", ch.escape_default())?;
+            } else {
+                self.out.write_char(ch)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Counts the characters written to it.
+struct Count(usize);
+
+impl Write for Count {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+// Characters of Unicode's Cf (format) category, among them the bidirectional
+// overrides and isolates and the zero-width characters, as well as the line
+// and paragraph separators.
+fn is_invisible(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{ad}'
+            | '\u{600}'..='\u{605}'
+            | '\u{61c}'
+            | '\u{6dd}'
+            | '\u{70f}'
+            | '\u{890}'..='\u{891}'
+            | '\u{8e2}'
+            | '\u{180e}'
+            | '\u{200b}'..='\u{200f}'
+            | '\u{2028}'..='\u{202e}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206f}'
+            | '\u{feff}'
+            | '\u{fff9}'..='\u{fffb}'
+            | '\u{110bd}'
+            | '\u{110cd}'
+            | '\u{13430}'..='\u{1343f}'
+            | '\u{1bca0}'..='\u{1bca3}'
+            | '\u{1d173}'..='\u{1d17a}'
+            | '\u{e0001}'
+            | '\u{e0020}'..='\u{e007f}'
+    )
+}
@@ -0,0 +1,32 @@
+use serde::ser::{SerializeStruct, Serializer};
+use std::error::Error;
+
+// Serializes an error as its variant name, rendered message, optional
+// `#[code("...")]`, and the messages of its chain of sources. Fields of the
+// error itself, including any backtrace, are never serialized.
+pub fn serialize_error<S>(
+    error: &dyn Error,
+    variant: &'static str,
+    code: Option<&'static str>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut chain = Vec::new();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        chain.push(cause.to_string());
+        source = cause.source();
+    }
+
+    let mut state = serializer.serialize_struct("Error", 4)?;
+    state.serialize_field("variant", variant)?;
+    state.serialize_field("message", &error.to_string())?;
+    match code {
+        Some(code) => state.serialize_field("code", code)?,
+        None => state.skip_field("code")?,
+    }
+    state.serialize_field("chain", &chain)?;
+    state.end()
+}
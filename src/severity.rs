@@ -0,0 +1,30 @@
+use core::fmt::{self, Display};
+
+/// How serious an error is.
+///
+/// Returned by the `severity()` method that `#[derive(Error)]` generates for
+/// types carrying `#[severity(...)]`. Severities are ordered from `Info` to
+/// `Fatal`, so a threshold can be checked with a comparison.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Expected in normal operation and worth recording, not acting on.
+    Info,
+    /// Something went wrong but the operation could carry on.
+    Warning,
+    /// The operation failed. This is the default for variants without a
+    /// `#[severity(...)]` of their own or on the type.
+    Error,
+    /// The program cannot continue.
+    Fatal,
+}
+
+impl Display for Severity {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        })
+    }
+}
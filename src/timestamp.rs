@@ -0,0 +1,80 @@
+use core::fmt::{self, Display};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// A `#[timestamp]` field interpolated into a message. std's SystemTime and
+// Instant have no Display of their own, so they are written as an RFC 3339
+// date and time in UTC, with millisecond precision.
+pub struct DisplayTimestamp<'a, T: ?Sized>(pub &'a T);
+
+pub trait Timestamp {
+    fn system_time(&self) -> Option<SystemTime>;
+}
+
+impl Timestamp for SystemTime {
+    fn system_time(&self) -> Option<SystemTime> {
+        Some(*self)
+    }
+}
+
+// An Instant is only meaningful relative to another, so it is placed on the
+// wall clock by how long ago it was taken.
+impl Timestamp for Instant {
+    fn system_time(&self) -> Option<SystemTime> {
+        SystemTime::now().checked_sub(self.elapsed())
+    }
+}
+
+impl<T: Timestamp> Timestamp for Option<T> {
+    fn system_time(&self) -> Option<SystemTime> {
+        self.as_ref()?.system_time()
+    }
+}
+
+impl<T: Timestamp + ?Sized> Display for DisplayTimestamp<'_, T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.system_time() {
+            Some(time) => write_rfc3339(time, formatter),
+            None => formatter.pad("unknown"),
+        }
+    }
+}
+
+// Last second that RFC 3339 can represent, 9999-12-31T23:59:59Z.
+const MAX_RFC3339_SECS: u64 = 253_402_300_799;
+
+// RFC 3339 in UTC with millisecond precision. Times before 1970 or after 9999
+// are shown as "unknown". Also used by `#[display(humantime)]`.
+pub(crate) fn write_rfc3339(time: SystemTime, formatter: &mut fmt::Formatter) -> fmt::Result {
+    let since_epoch = match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) if since_epoch.as_secs() <= MAX_RFC3339_SECS => since_epoch,
+        _ => return formatter.pad("unknown"),
+    };
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let secs_of_day = secs % 86_400;
+    formatter.pad(&format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis(),
+    ))
+}
+
+// Days since 1970-01-01 to a proleptic Gregorian date, after Howard Hinnant's
+// `civil_from_days`, restricted to dates after the epoch.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
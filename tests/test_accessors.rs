@@ -0,0 +1,60 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(accessors)]
+pub enum StoreError {
+    #[error("key not found")]
+    NotFound,
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+    #[error("bad record {id} at {offset}")]
+    BadRecord { id: u32, offset: u64 },
+    #[error("HTTP status {0}")]
+    HTTPStatus(u16),
+}
+
+#[test]
+fn test_is() {
+    let error = StoreError::NotFound;
+    assert!(error.is_not_found());
+    assert!(!error.is_io());
+    assert!(!error.is_bad_record());
+    assert!(!error.is_http_status());
+}
+
+#[test]
+fn test_as() {
+    let error = StoreError::from(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    assert_eq!(error.as_io().unwrap().kind(), io::ErrorKind::NotFound);
+    assert!(error.as_bad_record().is_none());
+
+    let error = StoreError::BadRecord { id: 7, offset: 64 };
+    assert_eq!(error.as_bad_record(), Some((&7, &64)));
+    assert_eq!(StoreError::HTTPStatus(404).as_http_status(), Some(&404));
+}
+
+#[test]
+fn test_into() {
+    let error = StoreError::from(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    let io = error.into_io().unwrap();
+    assert_eq!(io.to_string(), "oh no!");
+
+    let error = StoreError::BadRecord { id: 7, offset: 64 };
+    let error = error.into_io().unwrap_err();
+    assert_eq!(error.into_bad_record().unwrap(), (7, 64));
+}
+
+#[derive(Error, Debug)]
+#[error(accessors)]
+pub enum Single {
+    #[error("{0}")]
+    Only(String),
+}
+
+#[test]
+fn test_single_variant() {
+    let error = Single::Only("oh no!".to_owned());
+    assert_eq!(error.as_only().map(String::as_str), Some("oh no!"));
+    assert_eq!(error.into_only().unwrap(), "oh no!");
+}
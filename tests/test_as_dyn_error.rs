@@ -0,0 +1,29 @@
+use std::error::Error as StdError;
+use std::io;
+use std::rc::Rc;
+use std::sync::Arc;
+use thiserror::{AsDynError, Error};
+
+#[derive(Error, Debug)]
+#[error("leaf")]
+pub struct Leaf;
+
+fn message(error: &dyn AsDynError) -> String {
+    error.as_dyn_error().to_string()
+}
+
+#[test]
+fn test_pointers() {
+    assert_eq!("leaf", message(&Leaf));
+    assert_eq!("leaf", message(&Box::new(Leaf)));
+    assert_eq!("leaf", message(&Arc::new(Leaf)));
+    let leaf: &'static Leaf = &Leaf;
+    assert_eq!("leaf", message(&leaf));
+
+    let arc: Arc<dyn StdError + Send + Sync> = Arc::new(Leaf);
+    assert_eq!("leaf", message(&arc));
+    let boxed: Box<dyn StdError> = Box::new(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("oh no!", boxed.as_dyn_error().to_string());
+    let rc = Rc::new(Leaf);
+    assert!(rc.as_dyn_error().downcast_ref::<Leaf>().is_some());
+}
@@ -0,0 +1,146 @@
+pub mod from {
+    use std::backtrace::Backtrace;
+    use std::io;
+    use thiserror::{Backtraced, Error};
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct Inner {
+        backtrace: Backtrace,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct Outer {
+        #[from]
+        source: Inner,
+        #[backtrace]
+        backtrace: Option<Backtrace>,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct OuterAlways {
+        #[from]
+        source: Inner,
+        backtrace: Backtrace,
+    }
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct OuterIo {
+        #[from]
+        source: io::Error,
+        #[backtrace]
+        backtrace: Option<Backtrace>,
+    }
+
+    #[test]
+    fn test_skip_capture() {
+        let inner = Inner {
+            backtrace: Backtrace::force_capture(),
+        };
+        let outer = Outer::from(inner);
+        assert!(outer.backtrace.is_none());
+        assert!(outer.backtrace_ref().is_some());
+    }
+
+    #[test]
+    fn test_skip_capture_always() {
+        let inner = Inner {
+            backtrace: Backtrace::force_capture(),
+        };
+        let outer = OuterAlways::from(inner);
+        assert_eq!(
+            outer.backtrace.status(),
+            std::backtrace::BacktraceStatus::Disabled,
+        );
+    }
+
+    #[test]
+    fn test_source_without_backtrace() {
+        let outer = OuterIo::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+        assert!(outer.backtrace.is_some());
+        assert!(outer.backtrace_ref().is_some());
+    }
+}
+
+pub mod dispatch {
+    use std::backtrace::Backtrace;
+    use thiserror::{Backtraced, Error};
+
+    #[derive(Error, Debug)]
+    #[error("...")]
+    pub struct Inner {
+        backtrace: Backtrace,
+    }
+
+    #[derive(Error, Debug)]
+    #[error(dispatch)]
+    pub enum Outer {
+        #[error("...")]
+        Wrapped {
+            #[from]
+            source: Inner,
+            #[backtrace]
+            backtrace: Option<Backtrace>,
+        },
+        #[error("...")]
+        Own { backtrace: Backtrace },
+        #[error("...")]
+        Unit,
+    }
+
+    #[test]
+    fn test_dispatch_backtrace() {
+        let inner = Inner {
+            backtrace: Backtrace::force_capture(),
+        };
+        let outer = Outer::from(inner);
+        assert!(outer.backtrace_ref().is_some());
+        let own = Outer::Own {
+            backtrace: Backtrace::force_capture(),
+        };
+        assert!(own.backtrace_ref().is_some());
+        assert!(Outer::Unit.backtrace_ref().is_none());
+    }
+}
+
+pub mod generic {
+    use std::backtrace::Backtrace;
+    use thiserror::{Backtraced, Error};
+
+    #[derive(Error, Debug)]
+    pub enum Outer<E: std::error::Error + 'static> {
+        #[error("...")]
+        Wrapped {
+            #[source]
+            source: E,
+            #[backtrace]
+            backtrace: Option<Backtrace>,
+        },
+        #[error("...")]
+        Boxed {
+            #[source]
+            source: Box<dyn std::error::Error + Send + Sync>,
+        },
+    }
+
+    fn backtrace_of(error: &dyn Backtraced) -> bool {
+        error.backtrace_ref().is_some()
+    }
+
+    #[test]
+    fn test_generic_source() {
+        let error = Outer::Wrapped {
+            source: std::fmt::Error,
+            backtrace: None,
+        };
+        assert!(!backtrace_of(&error));
+        let error = Outer::<std::fmt::Error>::Wrapped {
+            source: std::fmt::Error,
+            backtrace: Some(Backtrace::force_capture()),
+        };
+        assert!(backtrace_of(&error));
+    }
+}
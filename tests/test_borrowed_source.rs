@@ -0,0 +1,69 @@
+use std::error::Error as _;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("unexpected {token:?}")]
+pub struct Unexpected<'a> {
+    token: &'a str,
+}
+
+#[derive(Error, Debug)]
+#[error("parse failed")]
+pub struct ParseError<'a> {
+    #[source]
+    cause: Unexpected<'a>,
+}
+
+#[derive(Error, Debug)]
+pub enum LoadError<'a> {
+    #[error("parse failed")]
+    Parse(#[from] Unexpected<'a>),
+    #[error("maybe failed")]
+    Maybe {
+        #[source]
+        cause: Option<Unexpected<'a>>,
+    },
+    #[error("format failed")]
+    Format(#[source] fmt::Error),
+    #[error(transparent)]
+    Nested(ParseError<'a>),
+    #[error("empty")]
+    Empty,
+}
+
+#[test]
+fn test_struct() {
+    let input = String::from("}");
+    let error = ParseError {
+        cause: Unexpected { token: &input },
+    };
+    assert!(error.source().is_none());
+    assert_eq!(error.source_ref().unwrap().to_string(), "unexpected \"}\"");
+}
+
+#[test]
+fn test_enum() {
+    let input = String::from("{");
+    let error = LoadError::from(Unexpected { token: &input });
+    assert!(error.source().is_none());
+    assert_eq!(error.source_ref().unwrap().to_string(), "unexpected \"{\"");
+
+    let error = LoadError::Maybe { cause: None };
+    assert!(error.source_ref().is_none());
+
+    let error = LoadError::Format(fmt::Error);
+    assert!(error.source().unwrap().is::<fmt::Error>());
+    assert_eq!(
+        error.source_ref().unwrap().to_string(),
+        fmt::Error.to_string(),
+    );
+
+    let error = LoadError::Nested(ParseError {
+        cause: Unexpected { token: &input },
+    });
+    assert_eq!(error.to_string(), "parse failed");
+    assert!(error.source().is_none());
+
+    assert!(LoadError::Empty.source_ref().is_none());
+}
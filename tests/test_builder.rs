@@ -0,0 +1,95 @@
+use std::io;
+use std::panic::Location;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NetError {
+    #[error("{op} timed out after {elapsed:?} on {host}:{port}")]
+    #[error(builder)]
+    Timeout {
+        op: String,
+        elapsed: Duration,
+        host: String,
+        port: u16,
+        retries: Option<u32>,
+    },
+    #[error("failed to {op}")]
+    #[error(builder)]
+    Io {
+        #[source]
+        source: io::Error,
+        op: &'static str,
+        attempt: u32,
+        location: &'static Location<'static>,
+    },
+    #[error("closed")]
+    Closed,
+}
+
+#[derive(Error, Debug)]
+pub enum Generic<T: std::fmt::Debug + Default> {
+    #[error("unexpected {value:?}")]
+    #[error(builder)]
+    Unexpected { value: T, context: String },
+}
+
+#[test]
+fn test_builder() {
+    let error = NetError::timeout_builder()
+        .op("read")
+        .elapsed(Duration::from_secs(3))
+        .host("example.com")
+        .port(443u16)
+        .build();
+    assert_eq!(
+        error.to_string(),
+        "read timed out after 3s on example.com:443",
+    );
+    match error {
+        NetError::Timeout { retries, .. } => assert_eq!(retries, None),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_unset_fields_default() {
+    let error = NetError::timeout_builder().op("write").retries(2).build();
+    match error {
+        NetError::Timeout {
+            elapsed,
+            port,
+            retries,
+            ..
+        } => {
+            assert_eq!(elapsed, Duration::default());
+            assert_eq!(port, 0);
+            assert_eq!(retries, Some(2));
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_source_and_location() {
+    let source = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    let line = line!() + 1;
+    let error = NetError::io_builder(source).op("connect").build();
+    assert_eq!(error.to_string(), "failed to connect");
+    assert!(std::error::Error::source(&error).is_some());
+    match error {
+        NetError::Io {
+            attempt, location, ..
+        } => {
+            assert_eq!(attempt, 0);
+            assert_eq!(location.line(), line);
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_generic() {
+    let error = Generic::<i32>::unexpected_builder().value(7).build();
+    assert_eq!(error.to_string(), "unexpected 7");
+}
@@ -0,0 +1,82 @@
+use thiserror::{Error, ErrorDescriptor, Severity};
+
+#[derive(Error, Debug)]
+#[error(catalog)]
+#[severity(Warning)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[code("E404")]
+    NotFound(u64),
+    #[error("database unavailable")]
+    #[code("E503")]
+    #[severity(Fatal)]
+    Unavailable,
+    #[error(transparent)]
+    Io(std::io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("timed out after {0}s")]
+#[error(catalog)]
+pub struct Timeout(u64);
+
+#[derive(Error, Debug)]
+#[error(catalog)]
+pub enum ParseError<'a> {
+    #[error("unexpected token {0}")]
+    #[code("P001")]
+    Unexpected(&'a str),
+}
+
+#[test]
+fn test_enum_catalog() {
+    assert_eq!(
+        ApiError::CATALOG,
+        [
+            ErrorDescriptor {
+                name: "NotFound",
+                message: Some("user {0} not found"),
+                code: Some("E404"),
+                severity: Severity::Warning,
+            },
+            ErrorDescriptor {
+                name: "Unavailable",
+                message: Some("database unavailable"),
+                code: Some("E503"),
+                severity: Severity::Fatal,
+            },
+            ErrorDescriptor {
+                name: "Io",
+                message: None,
+                code: None,
+                severity: Severity::Warning,
+            },
+        ],
+    );
+}
+
+#[test]
+fn test_struct_catalog() {
+    assert_eq!(
+        Timeout::CATALOG,
+        [ErrorDescriptor {
+            name: "Timeout",
+            message: Some("timed out after {0}s"),
+            code: None,
+            severity: Severity::Error,
+        }],
+    );
+}
+
+#[test]
+fn test_borrowed_catalog() {
+    assert_eq!(
+        ParseError::CATALOG,
+        [ErrorDescriptor {
+            name: "Unexpected",
+            message: Some("unexpected token {0}"),
+            code: Some("P001"),
+            severity: Severity::Error,
+        }],
+    );
+}
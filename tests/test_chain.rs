@@ -0,0 +1,40 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(chain)]
+#[error("outer")]
+pub struct Outer {
+    #[source]
+    inner: Inner,
+}
+
+#[derive(Error, Debug)]
+#[error(chain)]
+pub enum Inner {
+    #[error("inner")]
+    Io(#[source] io::Error),
+    #[error("leaf")]
+    Leaf,
+}
+
+#[test]
+fn test_chain() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let error = Outer {
+        inner: Inner::Io(io),
+    };
+    let messages: Vec<String> = error.chain().map(ToString::to_string).collect();
+    assert_eq!(messages, ["outer", "inner", "oh no!"]);
+    assert_eq!("oh no!", error.root_cause().to_string());
+    error.root_cause().downcast_ref::<io::Error>().unwrap();
+}
+
+#[test]
+fn test_chain_without_source() {
+    let error = Inner::Leaf;
+    let mut chain = error.chain();
+    assert_eq!("leaf", chain.next().unwrap().to_string());
+    assert!(chain.next().is_none());
+    assert_eq!("leaf", error.root_cause().to_string());
+}
@@ -0,0 +1,69 @@
+use std::error::Error as _;
+use std::io;
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(clone)]
+pub enum CacheError {
+    #[error("i/o failed")]
+    Io(#[from] Arc<io::Error>),
+    #[error("key {key} expired")]
+    Expired { key: String },
+    #[error("format failed")]
+    Format(#[from(raw)] std::fmt::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("fetch of {url} failed")]
+#[error(clone)]
+pub struct FetchError {
+    url: String,
+    #[from]
+    source: Arc<io::Error>,
+}
+
+#[derive(Error, Debug)]
+#[error("wrapped {0:?}")]
+#[error(clone)]
+pub struct Generic<T: std::fmt::Debug>(T);
+
+#[test]
+fn test_enum() {
+    let error = CacheError::from(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    let clone = error.clone();
+    match (&error, &clone) {
+        (CacheError::Io(original), CacheError::Io(cloned)) => {
+            assert!(Arc::ptr_eq(original, cloned));
+        }
+        _ => panic!("expected Io"),
+    }
+    assert_eq!(clone.source().unwrap().to_string(), "oh no!");
+
+    let error = CacheError::Expired {
+        key: "session".to_owned(),
+    };
+    assert_eq!(error.clone().to_string(), "key session expired");
+
+    let error = CacheError::from(std::fmt::Error);
+    assert!(matches!(error.clone(), CacheError::Format(_)));
+}
+
+#[test]
+fn test_struct() {
+    let error = FetchError {
+        url: "https://example.com".to_owned(),
+        source: Arc::new(io::Error::new(io::ErrorKind::TimedOut, "too slow")),
+    };
+    let clone = error.clone();
+    assert_eq!(clone.to_string(), "fetch of https://example.com failed");
+    assert!(Arc::ptr_eq(&error.source, &clone.source));
+
+    let error = FetchError::from(io::Error::new(io::ErrorKind::TimedOut, "too slow"));
+    assert_eq!(error.clone().url, "");
+}
+
+#[test]
+fn test_generic() {
+    assert_eq!(Generic(1).clone().to_string(), "wrapped 1");
+}
@@ -0,0 +1,79 @@
+use thiserror::{Error, ParseCodeError};
+
+#[derive(Error, Debug, PartialEq)]
+#[error(from_str)]
+pub enum ApiError {
+    #[error("rate limited")]
+    #[code("E0001")]
+    RateLimited,
+    #[error("invalid field {field}")]
+    #[code("E0002")]
+    InvalidField { field: String, position: usize },
+    #[error("quota {0} exceeded")]
+    #[code("E0003")]
+    Quota(u64),
+    #[error("internal")]
+    Internal,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("maintenance")]
+#[error(from_str)]
+#[code("M0001")]
+pub struct Maintenance;
+
+#[test]
+fn test_from_str() {
+    assert_eq!("E0001".parse::<ApiError>().unwrap(), ApiError::RateLimited);
+    assert_eq!(
+        "E0002".parse::<ApiError>().unwrap(),
+        ApiError::InvalidField {
+            field: String::new(),
+            position: 0,
+        },
+    );
+    assert_eq!("E0003".parse::<ApiError>().unwrap(), ApiError::Quota(0));
+    assert_eq!("M0001".parse::<Maintenance>().unwrap(), Maintenance);
+}
+
+#[test]
+fn test_unknown_code() {
+    let error = "E9999".parse::<ApiError>().unwrap_err();
+    assert_eq!(error, ParseCodeError::new("E9999"));
+    assert_eq!(error.code(), "E9999");
+    assert_eq!(error.to_string(), "unknown error code `E9999`");
+}
+
+#[test]
+fn test_codes_round_trip() {
+    assert_eq!(ApiError::codes(), ["E0001", "E0002", "E0003"]);
+    for code in ApiError::codes() {
+        assert!(code.parse::<ApiError>().is_ok());
+    }
+    assert_eq!(Maintenance::codes(), ["M0001"]);
+}
+
+#[derive(Error, Debug)]
+#[error(code)]
+pub enum StoreError {
+    #[error("not found")]
+    #[code("S0001")]
+    NotFound,
+    #[error("corrupt block {0}")]
+    #[code("S0002")]
+    Corrupt(u64),
+}
+
+#[derive(Error, Debug)]
+#[error("timeout")]
+#[error(code, from_str)]
+#[code("T0001")]
+pub struct Timeout;
+
+#[test]
+fn test_code_method() {
+    assert_eq!(StoreError::NotFound.code(), "S0001");
+    assert_eq!(StoreError::Corrupt(7).code(), "S0002");
+    assert_eq!(Timeout.code(), "T0001");
+    assert_eq!(Timeout::codes(), [Timeout.code()]);
+}
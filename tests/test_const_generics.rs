@@ -0,0 +1,75 @@
+use std::error::Error as _;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("buffer of {N} bytes overflowed by {excess}")]
+#[error(partial_eq, clone, name, describe)]
+pub struct Overflow<const N: usize> {
+    excess: usize,
+}
+
+#[derive(Error, Debug)]
+#[error(partial_eq, clone, name, kind, constructors, accessors)]
+pub enum QueueError<const N: usize, T> {
+    #[error("queue of {N} is full, dropped {0:?}")]
+    Full(T),
+    #[error("format failed")]
+    Format(#[from(raw)] fmt::Error),
+    #[error(transparent)]
+    Overflow(Overflow<N>),
+}
+
+#[derive(Error, Debug)]
+#[error("array of {LEN} failed")]
+pub struct ArrayError<T, const LEN: usize> {
+    #[source]
+    source: T,
+    items: [u8; LEN],
+}
+
+#[test]
+fn test_struct() {
+    let error = Overflow::<16> { excess: 2 };
+    assert_eq!(error.to_string(), "buffer of 16 bytes overflowed by 2");
+    assert_eq!(error.clone(), error);
+    assert_eq!(error.name(), "Overflow");
+}
+
+#[test]
+fn test_enum() {
+    let error = QueueError::<4, u8>::Full(9);
+    assert_eq!(error.to_string(), "queue of 4 is full, dropped 9");
+    assert_eq!(error.clone(), error);
+
+    let error = QueueError::<4, u8>::from(fmt::Error);
+    assert!(error.source().unwrap().is::<fmt::Error>());
+
+    let error = QueueError::<8, u8>::Overflow(Overflow { excess: 1 });
+    assert_eq!(error.to_string(), "buffer of 8 bytes overflowed by 1");
+}
+
+#[test]
+fn test_source() {
+    let error = ArrayError {
+        source: fmt::Error,
+        items: [0; 3],
+    };
+    assert_eq!(error.to_string(), "array of 3 failed");
+    assert!(error.source().unwrap().is::<fmt::Error>());
+    assert_eq!(error.items.len(), 3);
+}
+
+#[derive(Error, Debug)]
+#[error("{N} slots borrowed from {pool}")]
+#[error(owned)]
+pub struct Exhausted<'a, const N: usize> {
+    pool: &'a str,
+}
+
+#[test]
+fn test_owned() {
+    let error = Exhausted::<2> { pool: "workers" };
+    let owned: ExhaustedOwned<2> = error.into_owned();
+    assert_eq!(owned.to_string(), "2 slots borrowed from workers");
+}
@@ -0,0 +1,124 @@
+#![cfg_attr(thiserror_nightly_testing, feature(backtrace))]
+
+use std::io;
+use std::panic::Location;
+use std::path::PathBuf;
+use thiserror::Error;
+use tracing_error::{ErrorLayer, SpanTrace, SpanTraceStatus};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+// Runs `f` inside a span, under a subscriber that lets SpanTrace capture it.
+fn in_span<T>(f: impl FnOnce() -> T) -> T {
+    let subscriber = Registry::default().with(ErrorLayer::default());
+    tracing::subscriber::with_default(subscriber, || tracing::info_span!("test").in_scope(f))
+}
+
+#[derive(Error, Debug)]
+#[error(constructors)]
+pub enum StoreError {
+    #[error("{} not found", path.display())]
+    NotFound { path: PathBuf },
+    #[error("I/O error at offset {1}")]
+    Io(#[source] io::Error, u64),
+    #[error("store is closed")]
+    Closed,
+    #[error("{message}")]
+    Other {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    #[error("invalid type {0}")]
+    Type(String),
+    #[error("crate {0} is unavailable")]
+    Crate(String),
+}
+
+#[derive(Error, Debug)]
+#[error(constructors)]
+pub enum Captured {
+    #[error("lock poisoned")]
+    Poisoned {
+        span_trace: SpanTrace,
+        location: &'static Location<'static>,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error(constructors)]
+pub enum Generic<T: std::fmt::Debug> {
+    #[error("unexpected {0:?}")]
+    Unexpected(T),
+}
+
+#[test]
+fn test_constructors() {
+    let error = StoreError::not_found("/var/db");
+    assert_eq!(error.to_string(), "/var/db not found");
+
+    let error = StoreError::io(io::Error::new(io::ErrorKind::NotFound, "oh no!"), 64u32);
+    assert_eq!(error.to_string(), "I/O error at offset 64");
+    assert!(std::error::Error::source(&error).is_some());
+
+    assert_eq!(StoreError::closed().to_string(), "store is closed");
+
+    let error = StoreError::other("oh no!", None);
+    assert_eq!(error.to_string(), "oh no!");
+
+    assert_eq!(StoreError::r#type("x").to_string(), "invalid type x");
+    assert_eq!(
+        StoreError::crate_("x").to_string(),
+        "crate x is unavailable"
+    );
+
+    assert_eq!(Generic::<i32>::unexpected(1).to_string(), "unexpected 1");
+}
+
+#[test]
+fn test_captured() {
+    let (error, line) = in_span(|| (Captured::poisoned(), line!()));
+    let Captured::Poisoned {
+        span_trace,
+        location,
+    } = error;
+    assert_eq!(span_trace.status(), SpanTraceStatus::CAPTURED);
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+}
+
+#[cfg(thiserror_nightly_testing)]
+pub mod backtrace {
+    use std::backtrace::Backtrace;
+    use thiserror::Error;
+    use tracing_error::{ErrorLayer, SpanTrace, SpanTraceStatus};
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::Registry;
+
+    #[derive(Error, Debug)]
+    #[error(constructors)]
+    pub enum Error {
+        #[error("timed out after {0} ms")]
+        Timeout(u64, Backtrace),
+        #[error("...")]
+        Optional {
+            code: u16,
+            backtrace: Option<Backtrace>,
+        },
+    }
+
+    #[test]
+    fn test_backtrace() {
+        match Error::timeout(5u8) {
+            Error::Timeout(ms, _backtrace) => assert_eq!(ms, 5),
+            Error::Optional { .. } => unreachable!(),
+        }
+        assert!(matches!(
+            Error::optional(7u16),
+            Error::Optional {
+                backtrace: Some(_),
+                ..
+            }
+        ));
+    }
+}
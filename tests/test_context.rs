@@ -0,0 +1,58 @@
+use std::error::Error as _;
+use std::io;
+use std::num::ParseIntError;
+use std::panic::Location;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(context)]
+pub enum ConfigError {
+    #[error("failed to read {}", path.display())]
+    Read {
+        #[source]
+        source: io::Error,
+        path: PathBuf,
+    },
+    #[error("invalid port on line {1}")]
+    Port(#[source] ParseIntError, usize),
+    #[error("plugin failed")]
+    Plugin {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        location: &'static Location<'static>,
+    },
+    #[error("missing section")]
+    Missing,
+}
+
+fn read(path: &str) -> Result<String, ConfigError> {
+    Err(io::Error::new(io::ErrorKind::NotFound, "oh no!")).ctx_read(path)
+}
+
+#[test]
+fn test_context() {
+    let error = read("/etc/app.toml").unwrap_err();
+    assert_eq!(error.to_string(), "failed to read /etc/app.toml");
+    assert_eq!(error.source().unwrap().to_string(), "oh no!");
+
+    let error = "80x".parse::<u16>().ctx_port(3usize).unwrap_err();
+    assert_eq!(error.to_string(), "invalid port on line 3");
+    assert!(matches!(error, ConfigError::Port(_, 3)));
+}
+
+#[test]
+fn test_ok_passes_through() {
+    let port: Result<u16, ConfigError> = "8080".parse::<u16>().ctx_port(1usize);
+    assert_eq!(port.unwrap(), 8080);
+}
+
+#[test]
+fn test_boxed_source_and_location() {
+    let line = line!() + 1;
+    let error = Err::<(), _>("plugin crashed").ctx_plugin().unwrap_err();
+    assert_eq!(error.source().unwrap().to_string(), "plugin crashed");
+    match error {
+        ConfigError::Plugin { location, .. } => assert_eq!(location.line(), line),
+        _ => unreachable!(),
+    }
+}
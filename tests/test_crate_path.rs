@@ -0,0 +1,91 @@
+// A facade crate re-exporting thiserror, as seen by one of its users that
+// does not depend on thiserror directly.
+mod facade {
+    pub mod __private {
+        pub use ::thiserror;
+    }
+    pub use ::thiserror::Error;
+}
+
+mod user {
+    #![allow(dead_code)]
+
+    // Stands in for thiserror being absent from the user's dependencies.
+    mod thiserror {}
+
+    use crate::facade::Error;
+    use std::io;
+    use std::path::PathBuf;
+
+    #[derive(Error, Debug)]
+    #[error(crate = crate::facade::__private::thiserror)]
+    pub enum LoadError {
+        #[error("failed to read {}", path.display())]
+        Read {
+            path: PathBuf,
+            #[source]
+            source: io::Error,
+        },
+        #[error("missing {0}")]
+        Missing(PathBuf),
+        #[error(transparent)]
+        Other(#[from] io::Error),
+        #[error("timed out")]
+        #[retryable]
+        Timeout,
+    }
+
+    #[derive(Error, Debug)]
+    #[error(crate = crate::facade::__private::thiserror, dispatch)]
+    pub enum Dispatched {
+        #[error("wrapped")]
+        Wrapped(#[from] LoadError),
+        #[error("plain")]
+        Plain,
+    }
+
+    #[derive(Error, Debug)]
+    #[error(crate = crate::facade::__private::thiserror)]
+    #[error("config: {0}")]
+    pub struct ConfigError(pub String);
+
+    #[derive(Error, Debug)]
+    #[error(crate = crate::facade::__private::thiserror, catalog)]
+    #[severity(Warning)]
+    pub enum Cataloged {
+        #[error("busy")]
+        #[code("E503")]
+        Busy,
+    }
+}
+
+use std::error::Error as _;
+use std::io;
+use user::{Cataloged, ConfigError, Dispatched, LoadError};
+
+#[test]
+fn test_crate_path() {
+    let error = LoadError::Missing("app.toml".into());
+    assert_eq!("missing app.toml", error.to_string());
+
+    let error = LoadError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("oh no!", error.to_string());
+
+    assert!(LoadError::Timeout.is_retryable());
+
+    let error = Dispatched::from(LoadError::Timeout);
+    assert_eq!("wrapped", error.to_string());
+    assert!(error.source().is_some());
+
+    let error = ConfigError("bad".to_owned());
+    assert_eq!("config: bad", error.to_string());
+
+    assert_eq!(Cataloged::CATALOG[0].code, Some("E503"));
+    assert_eq!(Cataloged::CATALOG[0].severity, thiserror::Severity::Warning);
+}
+
+#[cfg(feature = "registry")]
+#[test]
+fn test_crate_path_registry() {
+    assert!(thiserror::registry().any(|registered| registered.type_name == "Cataloged"));
+}
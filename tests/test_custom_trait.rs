@@ -0,0 +1,73 @@
+use std::error::Error as _;
+use std::fmt::{Debug, Display};
+use thiserror::{CustomError, Error};
+
+pub trait RichError: Debug + Display {
+    fn origin(&self) -> Option<&dyn RichError>;
+}
+
+impl<T: CustomError<dyn RichError>> RichError for T {
+    fn origin(&self) -> Option<&dyn RichError> {
+        self.custom_source()
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("page {0} is missing")]
+#[error(trait = RichError)]
+pub struct MissingPage(u32);
+
+#[derive(Error, Debug)]
+#[error(trait = RichError)]
+pub enum RenderError {
+    #[error("template failed")]
+    Template(#[source] MissingPage),
+    #[error("layout failed")]
+    Layout {
+        #[source]
+        page: Option<MissingPage>,
+    },
+    #[error(transparent)]
+    Page(MissingPage),
+    #[error("out of memory")]
+    OutOfMemory,
+}
+
+#[derive(Error, Debug)]
+#[error("request failed")]
+#[error(trait = RichError)]
+pub struct RequestError<E: std::error::Error + 'static> {
+    #[source]
+    source: E,
+}
+
+#[test]
+fn test_struct() {
+    let error = MissingPage(4);
+    assert!(error.origin().is_none());
+
+    let error = RequestError {
+        source: MissingPage(2),
+    };
+    assert_eq!(error.origin().unwrap().to_string(), "page 2 is missing");
+    assert_eq!(error.source().unwrap().to_string(), "page 2 is missing");
+}
+
+#[test]
+fn test_enum() {
+    let error = RenderError::Template(MissingPage(1));
+    assert_eq!(error.origin().unwrap().to_string(), "page 1 is missing");
+
+    let error = RenderError::Layout {
+        page: Some(MissingPage(3)),
+    };
+    assert_eq!(error.origin().unwrap().to_string(), "page 3 is missing");
+    let error = RenderError::Layout { page: None };
+    assert!(error.origin().is_none());
+
+    let error = RenderError::Page(MissingPage(5));
+    assert_eq!(error.to_string(), "page 5 is missing");
+    assert!(error.origin().is_none());
+
+    assert!(RenderError::OutOfMemory.origin().is_none());
+}
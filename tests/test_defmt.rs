@@ -0,0 +1,75 @@
+#![cfg(feature = "defmt")]
+
+use defmt::export::{fetch_bytes, fetch_string_index, make_formatter};
+use defmt::Format;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(defmt)]
+pub enum SensorError {
+    #[error("sensor {0} timed out")]
+    Timeout(u32),
+    #[error("reading {value} out of range on {name}")]
+    OutOfRange { name: &'static str, value: u32 },
+    #[error(transparent)]
+    Bus(BusError),
+}
+
+#[derive(Error, Debug)]
+#[error(defmt)]
+#[error("bus fault at {addr}")]
+pub struct BusError {
+    addr: u32,
+}
+
+// Encodes `value` with defmt's test interner, which numbers strings in the
+// order they are interned instead of placing them in the binary.
+fn encode(value: &impl Format) -> (u16, Vec<u8>) {
+    let index = fetch_string_index();
+    value.format(make_formatter());
+    (index, fetch_bytes())
+}
+
+fn bytes(parts: &[&[u8]]) -> Vec<u8> {
+    parts.concat()
+}
+
+#[test]
+fn test_format() {
+    // The format string, then the Format tag of the u32 and its value.
+    let (index, encoded) = encode(&SensorError::Timeout(7));
+    let expected = bytes(&[
+        &index.to_le_bytes(),
+        &(index + 1).to_le_bytes(),
+        &7u32.to_le_bytes(),
+    ]);
+    assert_eq!(expected, encoded);
+
+    // Named placeholders become positional, in the order they appear.
+    let error = SensorError::OutOfRange {
+        name: "temp",
+        value: 300,
+    };
+    let (index, encoded) = encode(&error);
+    let expected = bytes(&[
+        &index.to_le_bytes(),
+        &(index + 1).to_le_bytes(),
+        &300u32.to_le_bytes(),
+        &(index + 2).to_le_bytes(),
+        &4u32.to_le_bytes(),
+        b"temp",
+    ]);
+    assert_eq!(expected, encoded);
+
+    let (index, encoded) = encode(&SensorError::Bus(BusError { addr: 16 }));
+    let expected = bytes(&[
+        &index.to_le_bytes(),
+        &(index + 1).to_le_bytes(),
+        &16u32.to_le_bytes(),
+    ]);
+    assert_eq!(expected, encoded);
+
+    // Display is unaffected.
+    assert_eq!("sensor 7 timed out", SensorError::Timeout(7).to_string());
+    assert_eq!("reading 300 out of range on temp", error.to_string());
+}
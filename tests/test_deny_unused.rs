@@ -0,0 +1,43 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(deny_unused)]
+pub enum FetchError {
+    #[error("fetching {url} failed after {} attempts", .attempts + 1)]
+    Exhausted { url: String, attempts: u32 },
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error("status {0} from {1:?}")]
+    Status(u16, String),
+    #[error("cancelled")]
+    Cancelled { _reason: String },
+    #[error(transparent)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+#[error("parse failed at line {line}")]
+#[error(deny_unused)]
+pub struct ParseError {
+    line: usize,
+    source: io::Error,
+}
+
+#[test]
+fn test_deny_unused() {
+    let error = FetchError::Exhausted {
+        url: "https://example.com".to_owned(),
+        attempts: 2,
+    };
+    assert_eq!(
+        error.to_string(),
+        "fetching https://example.com failed after 3 attempts",
+    );
+
+    let error = ParseError {
+        line: 4,
+        source: io::Error::new(io::ErrorKind::Other, "oh no!"),
+    };
+    assert_eq!(error.to_string(), "parse failed at line 4");
+}
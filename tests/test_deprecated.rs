@@ -0,0 +1,32 @@
+#![deny(deprecated)]
+
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(accessors, kind, constructors)]
+pub enum Error {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[deprecated(note = "use Io")]
+    #[error("legacy failure {code}")]
+    Legacy { code: u32 },
+}
+
+#[deprecated]
+#[derive(Error, Debug)]
+#[error("old error {0}")]
+pub struct OldError(#[from] io::Error);
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated() {
+    let error = Error::Legacy { code: 2 };
+    assert_eq!("legacy failure 2", error.to_string());
+    assert!(error.is_legacy());
+    assert_eq!(ErrorKind::Legacy, error.kind());
+    assert_eq!("legacy failure 3", Error::legacy(3u32).to_string());
+
+    let error = OldError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("old error oh no!", error.to_string());
+}
@@ -0,0 +1,32 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(transparent, deref)]
+pub struct WrappedIo(io::Error);
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+#[error(deref)]
+pub struct Wrapped<E: std::error::Error + 'static> {
+    inner: E,
+}
+
+#[test]
+fn test_deref() {
+    let error = WrappedIo(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    assert_eq!(error.to_string(), "oh no!");
+
+    let inner: &io::Error = error.as_ref();
+    assert_eq!(inner.to_string(), "oh no!");
+}
+
+#[test]
+fn test_deref_mut() {
+    let mut error = Wrapped {
+        inner: WrappedIo(io::Error::new(io::ErrorKind::NotFound, "oh no!")),
+    };
+    *error = WrappedIo(io::Error::new(io::ErrorKind::TimedOut, "too slow"));
+    assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+}
@@ -0,0 +1,39 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(describe)]
+pub enum ConfigError {
+    /// The configuration file could not be found.
+    ///
+    /// Create one with `app init`, or point `APP_CONFIG` at an existing file.
+    #[error("config file missing")]
+    Missing,
+    /** The configuration file is not valid TOML. */
+    #[error("invalid config at line {line}")]
+    Invalid { line: usize },
+    #[error("config is read-only")]
+    ReadOnly,
+}
+
+/// The operation took longer than its deadline.
+#[derive(Error, Debug)]
+#[error("timed out")]
+#[error(describe)]
+pub struct Timeout;
+
+#[test]
+fn test_describe() {
+    assert_eq!(
+        ConfigError::Missing.describe(),
+        "The configuration file could not be found.\n\nCreate one with `app init`, or point `APP_CONFIG` at an existing file.",
+    );
+    assert_eq!(
+        ConfigError::Invalid { line: 3 }.describe(),
+        "The configuration file is not valid TOML.",
+    );
+    assert_eq!(ConfigError::ReadOnly.describe(), "");
+    assert_eq!(
+        Timeout.describe(),
+        "The operation took longer than its deadline."
+    );
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "serde")]
+
+use serde::Serialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Serialize)]
+#[error(deserialize)]
+pub enum RpcError {
+    #[error("no such method {0}")]
+    NoSuchMethod(String),
+    #[error("invalid params at {position}")]
+    InvalidParams {
+        position: usize,
+        #[serde(default)]
+        hint: Option<String>,
+    },
+    #[error("timed out")]
+    Timeout,
+}
+
+#[derive(Error, Debug, PartialEq, Serialize)]
+#[error("quota of {limit} exceeded")]
+#[error(deserialize)]
+pub struct QuotaExceeded {
+    limit: u64,
+}
+
+#[test]
+fn test_deserialize() {
+    let error: RpcError = serde_json::from_value(json!({"NoSuchMethod": "ping"})).unwrap();
+    assert_eq!(error, RpcError::NoSuchMethod("ping".to_owned()));
+
+    let error: RpcError =
+        serde_json::from_value(json!({"InvalidParams": {"position": 2}})).unwrap();
+    assert_eq!(
+        error,
+        RpcError::InvalidParams {
+            position: 2,
+            hint: None,
+        },
+    );
+
+    let error: QuotaExceeded = serde_json::from_value(json!({"limit": 10})).unwrap();
+    assert_eq!(error.to_string(), "quota of 10 exceeded");
+}
+
+#[test]
+fn test_round_trip() {
+    let error = RpcError::Timeout;
+    let json = serde_json::to_string(&error).unwrap();
+    assert_eq!(serde_json::from_str::<RpcError>(&json).unwrap(), error);
+}
+
+#[test]
+fn test_error_message_names_type() {
+    let error = serde_json::from_value::<QuotaExceeded>(json!(1)).unwrap_err();
+    assert!(error.to_string().contains("struct QuotaExceeded"));
+}
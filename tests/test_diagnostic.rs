@@ -0,0 +1,76 @@
+#![cfg(feature = "miette")]
+
+use thiserror::Error;
+
+use miette::Diagnostic;
+
+fn render(message: Option<Box<dyn std::fmt::Display + '_>>) -> Option<String> {
+    message.map(|message| message.to_string())
+}
+
+#[derive(Error, Debug)]
+#[error(diagnostic)]
+#[error("invalid port {port}")]
+#[diagnostic(code(config::invalid_port), help("ports must be below {}", self.max))]
+pub struct PortError {
+    port: u32,
+    max: u32,
+}
+
+#[derive(Error, Debug)]
+#[error(diagnostic)]
+#[diagnostic(url("https://example.com/errors/{}", self.code().unwrap()))]
+pub enum ParseError {
+    #[error("unexpected end of input")]
+    #[code("parse::eof")]
+    Eof,
+    #[error("unexpected token {0}")]
+    #[diagnostic(code(parse::token), help("remove `{0}`"))]
+    Token(char),
+    #[error("...")]
+    #[diagnostic(url("https://example.com/other"), severity(Warning))]
+    Other,
+}
+
+#[test]
+fn test_struct() {
+    let error = PortError {
+        port: 70000,
+        max: 65535,
+    };
+    assert_eq!(
+        render(error.code()).as_deref(),
+        Some("config::invalid_port")
+    );
+    assert_eq!(
+        render(error.help()).as_deref(),
+        Some("ports must be below 65535")
+    );
+    assert_eq!(render(error.url()), None);
+}
+
+#[test]
+fn test_enum() {
+    let error = ParseError::Eof;
+    assert_eq!(render(error.code()).as_deref(), Some("parse::eof"));
+    assert_eq!(render(error.help()), None);
+    assert_eq!(
+        render(error.url()).as_deref(),
+        Some("https://example.com/errors/parse::eof"),
+    );
+
+    let error = ParseError::Token('}');
+    assert_eq!(render(error.code()).as_deref(), Some("parse::token"));
+    assert_eq!(render(error.help()).as_deref(), Some("remove `}`"));
+    assert_eq!(
+        render(error.url()).as_deref(),
+        Some("https://example.com/errors/parse::token"),
+    );
+
+    let error = ParseError::Other;
+    assert_eq!(render(error.code()), None);
+    assert_eq!(
+        render(error.url()).as_deref(),
+        Some("https://example.com/other")
+    );
+}
@@ -0,0 +1,73 @@
+use thiserror::diff::{self, Change};
+use thiserror::{ErrorDescriptor, Severity};
+
+const OLD: &[ErrorDescriptor] = &[
+    ErrorDescriptor {
+        name: "NotFound",
+        message: Some("{0} not found"),
+        code: None,
+        severity: Severity::Error,
+    },
+    ErrorDescriptor {
+        name: "Io",
+        message: None,
+        code: None,
+        severity: Severity::Error,
+    },
+    ErrorDescriptor {
+        name: "Timeout",
+        message: Some("timed out"),
+        code: None,
+        severity: Severity::Error,
+    },
+];
+
+#[test]
+fn test_unchanged() {
+    assert!(diff::diff(OLD, OLD).is_empty());
+}
+
+#[test]
+fn test_changes() {
+    let new = &[
+        ErrorDescriptor {
+            name: "Timeout",
+            message: Some("timed out after {0:?}"),
+            code: None,
+            severity: Severity::Error,
+        },
+        ErrorDescriptor {
+            name: "NotFound",
+            message: Some("{0} not found"),
+            code: None,
+            severity: Severity::Error,
+        },
+        ErrorDescriptor {
+            name: "Denied",
+            message: Some("permission denied"),
+            code: None,
+            severity: Severity::Error,
+        },
+    ];
+
+    let changes = diff::diff(OLD, new);
+    assert_eq!(
+        changes,
+        [
+            Change::ChangedMessage {
+                name: "Timeout",
+                old: Some("timed out"),
+                new: Some("timed out after {0:?}"),
+            },
+            Change::RemovedVariant { name: "Io" },
+            Change::AddedVariant { name: "Denied" },
+        ],
+    );
+
+    let breaking: Vec<String> = changes
+        .iter()
+        .filter(|change| change.is_breaking())
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(breaking, ["removed variant `Io`"]);
+}
@@ -0,0 +1,49 @@
+use std::error::Error as _;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(dispatch)]
+pub enum Error {
+    #[error("failed to read {path}")]
+    Read { path: PathBuf, source: io::Error },
+    #[error("retry {0} of {1}")]
+    Retry(u32, u32, #[source] Option<io::Error>),
+    #[error("unit")]
+    Unit,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Error, Debug)]
+#[error(dispatch)]
+pub enum Void {}
+
+#[test]
+fn test_display() {
+    let error = Error::Read {
+        path: PathBuf::from("/thiserror"),
+        source: io::Error::new(io::ErrorKind::Other, "oh no!"),
+    };
+    assert_eq!("failed to read /thiserror", error.to_string());
+    assert_eq!("retry 1 of 3", Error::Retry(1, 3, None).to_string());
+    assert_eq!("unit", Error::Unit.to_string());
+    let error = Error::from(anyhow::anyhow!("inner"));
+    assert_eq!("inner", error.to_string());
+}
+
+#[test]
+fn test_source() {
+    let error = Error::Read {
+        path: PathBuf::from("/thiserror"),
+        source: io::Error::new(io::ErrorKind::Other, "oh no!"),
+    };
+    assert_eq!("oh no!", error.source().unwrap().to_string());
+    assert!(Error::Retry(1, 3, None).source().is_none());
+    let error = Error::Retry(1, 3, Some(io::Error::new(io::ErrorKind::Other, "io")));
+    assert_eq!("io", error.source().unwrap().to_string());
+    assert!(Error::Unit.source().is_none());
+    let error = Error::from(anyhow::anyhow!("inner").context("outer"));
+    assert_eq!("inner", error.source().unwrap().to_string());
+}
@@ -123,6 +123,7 @@ fn test_nested() {
 
 #[test]
 fn test_void() {
+    #[allow(dead_code)]
     #[derive(Error, Debug)]
     #[error("...")]
     pub enum Error {}
@@ -179,3 +180,34 @@ fn test_field() {
 
     assert("0", Error(Inner { data: 0 }));
 }
+
+#[test]
+fn test_only_field() {
+    #[derive(Error, Debug)]
+    pub enum Error {
+        #[error("{0}")]
+        Tuple(usize),
+        #[error("{0:>4}")]
+        Padded(usize),
+    }
+
+    assert("7", Error::Tuple(7));
+    assert("   7", Error::Padded(7));
+}
+
+#[test]
+fn test_only_field_ignores_outer_flags() {
+    #[derive(Error, Debug)]
+    #[error("{0}")]
+    pub struct Plain(&'static str);
+
+    #[derive(Error, Debug)]
+    #[error("{0}")]
+    pub struct Wrapper(anyhow::Error);
+
+    assert_eq!("[x]", format!("[{:>8}]", Plain("x")));
+    assert_eq!("[xyz]", format!("[{:.1}]", Plain("xyz")));
+
+    let error = anyhow::anyhow!("inner").context("outer");
+    assert_eq!("[outer]", format!("[{:#}]", Wrapper(error)));
+}
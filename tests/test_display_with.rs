@@ -0,0 +1,101 @@
+use std::fmt;
+use thiserror::Error;
+
+fn hex(bytes: &Vec<u8>, formatter: &mut fmt::Formatter) -> fmt::Result {
+    for byte in bytes {
+        write!(formatter, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+mod foreign {
+    #[derive(Debug)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub fn fmt_point(point: &Point, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "({}, {})", point.x, point.y)
+    }
+}
+
+#[derive(Error, Debug)]
+#[error("checksum mismatch, expected {expected} but got {actual:?}")]
+pub struct ChecksumError {
+    #[display(with = hex)]
+    expected: Vec<u8>,
+    #[display(with = hex)]
+    actual: Vec<u8>,
+}
+
+#[derive(Error, Debug)]
+pub enum DrawError {
+    #[error("point {0} is out of bounds")]
+    OutOfBounds(#[display(with = foreign::fmt_point)] foreign::Point),
+    #[error("cannot draw from {from} to {to}")]
+    Line {
+        #[display(with = foreign::fmt_point)]
+        from: foreign::Point,
+        #[display(with = foreign::fmt_point)]
+        to: foreign::Point,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("bad token {token}")]
+pub struct TokenError {
+    #[display(with = hex)]
+    #[sensitive(hash)]
+    token: Vec<u8>,
+}
+
+// derive_more's own #[display(...)] on a type or variant is not thiserror's.
+#[derive(Error, Debug)]
+pub enum LegacyError {
+    #[error("first {0}")]
+    #[display(fmt = "first {}", _0)]
+    First(u8),
+}
+
+#[test]
+fn test_struct() {
+    let error = ChecksumError {
+        expected: vec![0xca, 0xfe],
+        actual: vec![0xbe, 0xef],
+    };
+    assert_eq!(
+        "checksum mismatch, expected cafe but got beef",
+        error.to_string(),
+    );
+}
+
+#[test]
+fn test_enum() {
+    let error = DrawError::OutOfBounds(foreign::Point { x: -1, y: 2 });
+    assert_eq!("point (-1, 2) is out of bounds", error.to_string());
+
+    let error = DrawError::Line {
+        from: foreign::Point { x: 0, y: 0 },
+        to: foreign::Point { x: 3, y: 4 },
+    };
+    assert_eq!("cannot draw from (0, 0) to (3, 4)", error.to_string());
+}
+
+#[test]
+fn test_sensitive() {
+    let one = TokenError {
+        token: vec![1, 2, 3],
+    };
+    let two = TokenError {
+        token: vec![1, 2, 3],
+    };
+    assert_eq!(one.to_string(), two.to_string());
+    assert!(!one.to_string().contains("010203"));
+}
+
+#[test]
+fn test_foreign_display() {
+    let error = LegacyError::First(1);
+    assert_eq!("first 1", error.to_string());
+}
@@ -0,0 +1,41 @@
+use std::error::Error as StdError;
+use std::io;
+use thiserror::{Error, ErrorDowncastExt};
+
+#[derive(Error, Debug)]
+#[error("outer")]
+pub struct Outer(#[from] Middle);
+
+#[derive(Error, Debug)]
+pub enum Middle {
+    #[error("middle")]
+    Io(#[from] io::Error),
+    #[error("leaf")]
+    Leaf,
+}
+
+#[test]
+fn test_downcast_ref_chain() {
+    let error = Outer::from(Middle::from(io::Error::new(
+        io::ErrorKind::NotFound,
+        "oh no!",
+    )));
+    let io = error.downcast_ref_chain::<io::Error>().unwrap();
+    assert_eq!(io.kind(), io::ErrorKind::NotFound);
+    assert!(error.downcast_ref_chain::<Middle>().is_some());
+    assert!(error.downcast_ref_chain::<Outer>().is_some());
+    assert!(error.downcast_ref_chain::<std::fmt::Error>().is_none());
+
+    let leaf = Outer::from(Middle::Leaf);
+    assert!(leaf.downcast_ref_chain::<io::Error>().is_none());
+}
+
+#[test]
+fn test_dyn_error() {
+    let error: Box<dyn StdError + Send + Sync> = Box::new(Outer::from(Middle::from(
+        io::Error::new(io::ErrorKind::Other, "oh no!"),
+    )));
+    assert!(error.downcast_ref_chain::<io::Error>().is_some());
+    let error: &(dyn StdError + 'static) = &*error;
+    assert!(error.downcast_ref_chain::<Middle>().is_some());
+}
@@ -0,0 +1,48 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FsError {
+    #[error("no such file or directory")]
+    #[errno(libc::ENOENT)]
+    NotFound,
+    #[error("permission denied")]
+    #[errno(libc::EACCES)]
+    PermissionDenied { path: Option<String> },
+    #[error("interrupted")]
+    #[errno(4)]
+    Interrupted,
+    #[error("os error {0}")]
+    #[errno(other)]
+    Other(i32),
+    #[error("path is not valid UTF-8")]
+    InvalidPath,
+}
+
+#[derive(Error, Debug)]
+#[error("resource busy")]
+#[errno(16)]
+pub struct Busy;
+
+#[test]
+fn test_raw_os_error() {
+    assert_eq!(Some(2), FsError::NotFound.raw_os_error());
+    assert_eq!(
+        Some(13),
+        FsError::PermissionDenied { path: None }.raw_os_error(),
+    );
+    assert_eq!(Some(4), FsError::Interrupted.raw_os_error());
+    assert_eq!(Some(28), FsError::Other(28).raw_os_error());
+    assert_eq!(None, FsError::InvalidPath.raw_os_error());
+    assert_eq!(Some(16), Busy.raw_os_error());
+}
+
+#[test]
+fn test_from_errno() {
+    assert!(matches!(FsError::from(2), FsError::NotFound));
+    assert!(matches!(
+        FsError::from(13),
+        FsError::PermissionDenied { path: None },
+    ));
+    assert!(matches!(FsError::from(4), FsError::Interrupted));
+    assert!(matches!(FsError::from(28), FsError::Other(28)));
+}
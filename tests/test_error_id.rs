@@ -0,0 +1,75 @@
+use std::io;
+use thiserror::{Error, ErrorId};
+
+#[derive(Error, Debug)]
+#[error("request failed ({id})")]
+pub struct RequestError {
+    #[from]
+    source: io::Error,
+    #[error_id]
+    id: ErrorId,
+}
+
+#[derive(Error, Debug)]
+#[error(constructors)]
+pub enum ServiceError {
+    #[error("i/o failed")]
+    Io {
+        #[from]
+        source: io::Error,
+        #[error_id]
+        id: u64,
+    },
+    #[error("user {user} not found")]
+    NotFound {
+        user: String,
+        #[error_id]
+        id: u64,
+    },
+    #[error("shutting down")]
+    ShuttingDown,
+}
+
+#[derive(Error, Debug)]
+#[error(constructors, partial_eq)]
+pub enum LookupError {
+    #[error("user {user} not found")]
+    NotFound {
+        user: String,
+        #[error_id]
+        id: ErrorId,
+    },
+}
+
+#[test]
+fn test_from() {
+    let one = RequestError::from(io::Error::new(io::ErrorKind::Other, "..."));
+    let two = RequestError::from(io::Error::new(io::ErrorKind::Other, "..."));
+    assert_ne!(one.error_id(), two.error_id());
+    assert!(two.error_id().get() > one.error_id().get());
+    assert_eq!(
+        format!("request failed ({:016x})", one.error_id().get()),
+        one.to_string(),
+    );
+}
+
+#[test]
+fn test_constructors() {
+    let one = ServiceError::not_found("alice".to_owned());
+    let two = ServiceError::not_found("alice".to_owned());
+    let (one_id, two_id) = (*one.error_id().unwrap(), *two.error_id().unwrap());
+    assert_ne!(one_id, two_id);
+    assert_eq!(None, ServiceError::ShuttingDown.error_id());
+
+    let error = ServiceError::from(io::Error::new(io::ErrorKind::Other, "..."));
+    assert!(error.error_id().is_some());
+}
+
+#[test]
+fn test_partial_eq() {
+    let one = LookupError::not_found("alice".to_owned());
+    let two = LookupError::not_found("alice".to_owned());
+    assert_ne!(one.error_id(), two.error_id());
+    assert_eq!(one, two);
+    assert_ne!(one, LookupError::not_found("bob".to_owned()));
+}
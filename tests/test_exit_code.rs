@@ -0,0 +1,37 @@
+use std::process::{ExitCode, Termination};
+use thiserror::{Error, Exit};
+
+#[derive(Error, Debug)]
+#[exit_code(70)]
+pub enum CliError {
+    #[error("invalid arguments")]
+    #[exit_code(2)]
+    Usage,
+    #[error("input file is empty")]
+    #[exit_code(65)]
+    EmptyInput,
+    #[error("internal error")]
+    Internal,
+}
+
+#[derive(Error, Debug)]
+#[error("config not found")]
+#[exit_code(78)]
+pub struct ConfigNotFound;
+
+#[test]
+fn test_exit_code() {
+    assert_eq!(ExitCode::from(2), CliError::Usage.exit_code());
+    assert_eq!(ExitCode::from(65), CliError::EmptyInput.exit_code());
+    assert_eq!(ExitCode::from(70), CliError::Internal.exit_code());
+    assert_eq!(ExitCode::from(78), ConfigNotFound.exit_code());
+}
+
+#[test]
+fn test_termination() {
+    let exit: Exit<CliError> = Err(CliError::EmptyInput).into();
+    assert_eq!(ExitCode::from(65), exit.report());
+
+    let exit: Exit<CliError> = Ok(()).into();
+    assert_eq!(ExitCode::SUCCESS, exit.report());
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "eyre")]
+
+use color_eyre::config::HookBuilder;
+use std::io;
+use std::sync::Once;
+use thiserror::Error;
+
+// Sections attached to a report only show in its Debug output, and only if
+// color-eyre's handler was installed before the report was created.
+fn install_handler() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| HookBuilder::blank().install().unwrap());
+}
+
+fn sections(report: &eyre::Report) -> Vec<String> {
+    let debug = format!("{:?}", report);
+    debug
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("Code: "))
+        .map(str::to_owned)
+        .collect()
+}
+
+#[derive(Error, Debug)]
+#[error("invalid port {0}")]
+pub struct PortError(u16);
+
+#[derive(Error, Debug)]
+#[error(from_eyre, into_report)]
+pub enum ApiError {
+    #[error("i/o failed")]
+    #[code("E0001")]
+    Io(#[from] io::Error),
+    #[error("bad config")]
+    Config(#[from] PortError),
+    #[error(transparent)]
+    #[error(fallback)]
+    Other(eyre::Report),
+}
+
+#[derive(Error, Debug)]
+#[error("connection lost")]
+#[error(into_report)]
+pub struct ConnectionError;
+
+#[test]
+fn test_downcast() {
+    install_handler();
+    let report = eyre::Report::new(PortError(0));
+    assert!(matches!(ApiError::from(report), ApiError::Config(_)));
+
+    let report = eyre::Report::msg("something else");
+    let error = ApiError::from(report);
+    assert!(matches!(error, ApiError::Other(_)));
+    assert_eq!("something else", error.to_string());
+}
+
+#[test]
+fn test_into_report() {
+    install_handler();
+    let source = io::Error::new(io::ErrorKind::NotFound, "oh no");
+    let report = ApiError::Io(source).into_report();
+    assert_eq!("i/o failed", report.to_string());
+    assert_eq!("oh no", report.source().unwrap().to_string());
+    assert_eq!(["Code: E0001"], *sections(&report));
+
+    let report = ApiError::Config(PortError(0)).into_report();
+    assert!(sections(&report).is_empty());
+
+    let report = ConnectionError.into_report();
+    assert_eq!("connection lost", report.to_string());
+    assert!(sections(&report).is_empty());
+}
@@ -1,4 +1,6 @@
-use std::io;
+use std::error::Error as StdError;
+use std::sync::Arc;
+use std::{fmt, io};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -28,6 +30,61 @@ pub enum Many {
     Io(#[from] io::Error),
 }
 
+#[derive(Error, Debug)]
+#[error("...")]
+pub enum Collapsed {
+    Foreign(#[from(io::Error, fmt::Error)] Box<dyn StdError + Send + Sync>),
+    Message(#[from(&str, String)] Box<dyn StdError + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct CollapsedStruct {
+    #[from(io::Error, fmt::Error)]
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub enum WithContext {
+    Io {
+        #[from]
+        source: io::Error,
+        path: Option<String>,
+        #[from(default)]
+        attempts: usize,
+    },
+}
+
+#[derive(Error, Debug, Clone)]
+#[error("...")]
+pub enum Shared {
+    Io(#[from(arc)] Arc<io::Error>),
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub enum Boxed {
+    Io(#[from(box)] Box<io::Error>),
+    Fmt(#[from(raw)] Box<fmt::Error>),
+    Any(#[from] Box<dyn StdError + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub enum Mapped {
+    Io(#[from(with = normalize_io)] io::Error),
+    Fmt(#[from(fmt::Error, with = fmt_to_io)] io::Error),
+}
+
+fn normalize_io(error: io::Error) -> io::Error {
+    io::Error::new(error.kind(), format!("io: {}", error))
+}
+
+fn fmt_to_io(error: fmt::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
 fn assert_impl<T: From<io::Error>>() {}
 
 #[test]
@@ -36,4 +93,78 @@ fn test_from() {
     assert_impl::<ErrorTuple>();
     assert_impl::<ErrorEnum>();
     assert_impl::<Many>();
+    assert_impl::<Collapsed>();
+    assert_impl::<CollapsedStruct>();
+    assert_impl::<WithContext>();
+    assert_impl::<Shared>();
+    assert_impl::<Boxed>();
+}
+
+#[test]
+fn test_from_box() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    match Boxed::from(io) {
+        Boxed::Io(io) => assert_eq!(io.kind(), io::ErrorKind::Other),
+        _ => panic!("expected Io"),
+    }
+
+    match Boxed::from(Box::new(fmt::Error)) {
+        Boxed::Fmt(_) => {}
+        _ => panic!("expected Fmt"),
+    }
+
+    let any: Box<dyn StdError + Send + Sync> = Box::from("oh no!");
+    match Boxed::from(any) {
+        Boxed::Any(any) => assert_eq!(any.to_string(), "oh no!"),
+        _ => panic!("expected Any"),
+    }
+}
+
+#[test]
+fn test_from_arc() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let error = Shared::from(io);
+    let clone = error.clone();
+    let Shared::Io(source) = error;
+    assert_eq!(Arc::strong_count(&source), 2);
+    assert_eq!("oh no!", clone.source().unwrap().to_string());
+}
+
+#[test]
+fn test_from_default_fields() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let WithContext::Io { path, attempts, .. } = WithContext::from(io);
+    assert_eq!(path, None);
+    assert_eq!(attempts, 0);
+}
+
+#[test]
+fn test_from_multiple_types() {
+    let error = Collapsed::from(fmt::Error);
+    match error {
+        Collapsed::Foreign(source) => assert!(source.is::<fmt::Error>()),
+        Collapsed::Message(_) => panic!("expected Foreign"),
+    }
+
+    match Collapsed::from("oh no!") {
+        Collapsed::Message(message) => assert_eq!(message.to_string(), "oh no!"),
+        Collapsed::Foreign(_) => panic!("expected Message"),
+    }
+
+    let error = CollapsedStruct::from(fmt::Error);
+    assert!(error.source().unwrap().is::<fmt::Error>());
+}
+
+#[test]
+fn test_from_with() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    match Mapped::from(io) {
+        Mapped::Io(io) => assert_eq!(io.to_string(), "io: oh no!"),
+        Mapped::Fmt(_) => panic!("expected Io"),
+    }
+
+    match Mapped::from(fmt::Error) {
+        Mapped::Fmt(io) => assert_eq!(io.kind(), io::ErrorKind::InvalidData),
+        Mapped::Io(_) => panic!("expected Fmt"),
+    }
 }
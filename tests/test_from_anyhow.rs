@@ -0,0 +1,79 @@
+#![cfg(feature = "anyhow")]
+
+use anyhow::anyhow;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid port {0}")]
+pub struct PortError(u16);
+
+#[derive(Error, Debug)]
+#[error(from_anyhow)]
+pub enum ApiError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error("bad config")]
+    Config {
+        #[from]
+        source: PortError,
+    },
+    #[error(transparent)]
+    #[error(fallback)]
+    Other(anyhow::Error),
+}
+
+#[test]
+fn test_downcast() {
+    let error = anyhow::Error::new(io::Error::new(io::ErrorKind::NotFound, "oh no"));
+    match ApiError::from(error) {
+        ApiError::Io(io) => assert_eq!("oh no", io.to_string()),
+        other => panic!("expected Io, got {:?}", other),
+    }
+
+    let error = anyhow::Error::new(PortError(0));
+    match ApiError::from(error) {
+        ApiError::Config { source } => assert_eq!(0, source.0),
+        other => panic!("expected Config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fallback() {
+    let error = ApiError::from(anyhow!("something else"));
+    assert!(matches!(error, ApiError::Other(_)));
+    assert_eq!("something else", error.to_string());
+}
+
+fn load() -> anyhow::Result<()> {
+    Err(PortError(8080).into())
+}
+
+#[test]
+fn test_question_mark() {
+    fn api() -> Result<(), ApiError> {
+        load()?;
+        Ok(())
+    }
+    assert!(matches!(api(), Err(ApiError::Config { .. })));
+}
+
+#[derive(Error, Debug)]
+#[error(from_anyhow)]
+pub enum BoxedError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    #[error(other)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[test]
+fn test_other_variant() {
+    let error = anyhow::Error::new(io::Error::new(io::ErrorKind::NotFound, "oh no"));
+    assert!(matches!(BoxedError::from(error), BoxedError::Io(_)));
+
+    let error = BoxedError::from(anyhow!("something else"));
+    assert!(matches!(error, BoxedError::Other(_)));
+    assert_eq!("something else", error.to_string());
+}
@@ -0,0 +1,38 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("not found")]
+    #[from_match(io::Error, e if e.kind() == io::ErrorKind::NotFound)]
+    NotFound { source: io::Error, retries: usize },
+    #[error("permission denied")]
+    #[from_match(io::Error, e if e.kind() == io::ErrorKind::PermissionDenied)]
+    Denied(#[source] io::Error),
+    #[error("io error")]
+    #[from_match(io::Error)]
+    Other(#[source] io::Error),
+    #[error("format error")]
+    Fmt(#[from] std::fmt::Error),
+}
+
+#[test]
+fn test_from_match() {
+    let error = StorageError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+    match error {
+        StorageError::NotFound { source, retries } => {
+            assert_eq!(source.to_string(), "missing");
+            assert_eq!(retries, 0);
+        }
+        _ => panic!("expected NotFound"),
+    }
+
+    let error = StorageError::from(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+    assert!(matches!(error, StorageError::Denied(_)));
+
+    let error = StorageError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert!(matches!(error, StorageError::Other(_)));
+
+    let error = StorageError::from(std::fmt::Error);
+    assert!(matches!(error, StorageError::Fmt(_)));
+}
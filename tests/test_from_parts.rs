@@ -0,0 +1,44 @@
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FsError {
+    #[error("failed to read {path:?}")]
+    #[from_parts]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to copy from {0:?} to {1:?}")]
+    #[from_parts]
+    Copy(PathBuf, PathBuf, #[source] io::Error),
+}
+
+fn read(path: &str) -> Result<(), (PathBuf, io::Error)> {
+    Err((
+        PathBuf::from(path),
+        io::Error::new(io::ErrorKind::NotFound, "oh no!"),
+    ))
+}
+
+#[test]
+fn test_from_parts() {
+    let error = FsError::from(read("config.toml").unwrap_err());
+    match error {
+        FsError::Read { path, source } => {
+            assert_eq!(path, PathBuf::from("config.toml"));
+            assert_eq!(source.kind(), io::ErrorKind::NotFound);
+        }
+        FsError::Copy(..) => panic!("expected Read"),
+    }
+
+    let parts = (
+        PathBuf::from("a"),
+        PathBuf::from("b"),
+        io::Error::new(io::ErrorKind::Other, "oh no!"),
+    );
+    let error: FsError = parts.into();
+    assert_eq!(error.to_string(), "failed to copy from \"a\" to \"b\"");
+}
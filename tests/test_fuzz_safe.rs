@@ -0,0 +1,88 @@
+use std::fmt::Display;
+use thiserror::Error;
+
+fn assert<T: Display>(expected: &str, value: T) {
+    assert_eq!(expected, value.to_string());
+}
+
+#[test]
+fn test_control_characters() {
+    #[derive(Error, Debug)]
+    #[error(fuzz_safe)]
+    #[error("invalid input {input}, debug {input:?}")]
+    struct Error {
+        input: String,
+    }
+
+    let input = "a\nb\x1b[31m".to_owned();
+    assert(
+        "invalid input a\\nb\\u{1b}[31m, debug \"a\\nb\\u{1b}[31m\"",
+        Error { input },
+    );
+}
+
+#[test]
+fn test_truncation() {
+    #[derive(Error, Debug)]
+    #[error(fuzz_safe)]
+    #[error("{0}")]
+    struct Error(String);
+
+    let input = "x".repeat(1000);
+    let expected = format!("{}...", "x".repeat(256));
+    assert(&expected, Error(input));
+}
+
+#[test]
+fn test_variant() {
+    #[derive(Error, Debug)]
+    enum Error {
+        #[error(fuzz_safe)]
+        #[error("untrusted {0}")]
+        Untrusted(String),
+        #[error("trusted {0}")]
+        Trusted(String),
+    }
+
+    assert("untrusted \\t", Error::Untrusted("\t".to_owned()));
+    assert("trusted \t", Error::Trusted("\t".to_owned()));
+}
+
+#[test]
+fn test_explicit_args() {
+    #[derive(Error, Debug)]
+    #[error(fuzz_safe)]
+    #[error("invalid input {}, debug {:?}", .0, .0)]
+    struct Error(String);
+
+    assert(
+        "invalid input a\\nb, debug \"a\\nb\"",
+        Error("a\nb".to_owned()),
+    );
+}
+
+#[test]
+fn test_width() {
+    #[derive(Error, Debug)]
+    #[error(fuzz_safe)]
+    #[error("[{0:>8}] [{0:<8}] [{0:^8}] [{0:*>4}]")]
+    struct Error(String);
+
+    assert(
+        "[    a\\tb] [a\\tb    ] [  a\\tb  ] [a\\tb]",
+        Error("a\tb".to_owned()),
+    );
+}
+
+#[test]
+fn test_invisible_characters() {
+    #[derive(Error, Debug)]
+    #[error(fuzz_safe)]
+    #[error("file {0}")]
+    struct Error(String);
+
+    assert(
+        "file invoice\\u{202e}fdp.exe \\u{200b}\\u{2028}",
+        Error("invoice\u{202e}fdp.exe \u{200b}\u{2028}".to_owned()),
+    );
+}
@@ -0,0 +1,139 @@
+use std::error::Error as _;
+use std::fmt::{self, Debug, Display};
+use thiserror::Error;
+
+pub struct NoFormat;
+
+#[derive(Debug)]
+pub struct DebugOnly;
+
+pub struct DisplayOnly;
+
+impl Display for DisplayOnly {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("display only")
+    }
+}
+
+#[derive(Debug)]
+pub struct DisplayAndDebug;
+
+impl Display for DisplayAndDebug {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("display and debug")
+    }
+}
+
+// Only the Display impl needs T: Display; the definition leaves it unbounded.
+#[derive(Error)]
+pub enum EnumDisplay<T> {
+    #[error("bad value {0}")]
+    Bad(T),
+    #[error("other")]
+    Other,
+}
+
+impl<T> Debug for EnumDisplay<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("EnumDisplay")
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum EnumDebugField<E> {
+    #[error("{0:?}")]
+    Debug(E),
+    #[error("{0:x}")]
+    Hex(u8),
+}
+
+#[derive(Error, Debug)]
+#[error("{value} in {context:?}")]
+pub struct StructDisplayAndDebug<T, C> {
+    value: T,
+    context: Vec<C>,
+}
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct StructTransparent<E>(E);
+
+#[derive(Error, Debug)]
+#[error("wrapped")]
+pub struct StructSource<E> {
+    #[source]
+    source: E,
+    unformatted: Option<E>,
+}
+
+#[test]
+fn test_display_bound() {
+    let error = EnumDisplay::Bad(DisplayOnly);
+    assert_eq!(error.to_string(), "bad value display only");
+    assert!(error.source().is_none());
+
+    // Still constructible with a T that has no Display impl at all.
+    let _ = EnumDisplay::<NoFormat>::Other;
+    assert_eq!(EnumDisplay::<DisplayOnly>::Other.to_string(), "other");
+}
+
+#[test]
+fn test_debug_bound() {
+    let error = EnumDebugField::Debug(DebugOnly);
+    assert_eq!(error.to_string(), "DebugOnly");
+    assert_eq!(EnumDebugField::<DebugOnly>::Hex(31).to_string(), "1f");
+}
+
+#[test]
+fn test_mixed_bounds() {
+    let error = StructDisplayAndDebug {
+        value: DisplayOnly,
+        context: vec![DebugOnly],
+    };
+    assert_eq!(error.to_string(), "display only in [DebugOnly]");
+}
+
+#[test]
+fn test_transparent() {
+    let error = StructTransparent(fmt::Error);
+    assert_eq!(
+        error.to_string(),
+        "an error occurred when formatting an argument"
+    );
+}
+
+#[test]
+fn test_source_bound() {
+    let error = StructSource {
+        source: fmt::Error,
+        unformatted: None,
+    };
+    assert_eq!(error.to_string(), "wrapped");
+    assert!(error.source().unwrap().is::<fmt::Error>());
+}
+
+#[derive(Error, Debug)]
+#[error("wrapped {0}")]
+#[error(bound = "T: Display + Send")]
+pub struct SendWrapper<T>(T);
+
+#[derive(Error, Debug)]
+#[error(bound = "E: std::error::Error + Send + 'static, T: Display")]
+pub enum BoundedEnum<E, T> {
+    #[error("failed on {0}")]
+    Failed(T, #[source] E),
+}
+
+fn assert_error<E: std::error::Error>(_: &E) {}
+
+#[test]
+fn test_bound_override() {
+    let error = SendWrapper("value");
+    assert_error(&error);
+    assert_eq!(error.to_string(), "wrapped value");
+
+    let error = BoundedEnum::Failed(7, fmt::Error);
+    assert_error(&error);
+    assert_eq!(error.to_string(), "failed on 7");
+    assert!(error.source().unwrap().is::<fmt::Error>());
+}
@@ -0,0 +1,46 @@
+use std::error::Error as StdError;
+use std::{fmt, io};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("wrapped")]
+pub struct Wrapper<E> {
+    #[from]
+    source: E,
+}
+
+#[derive(Error, Debug)]
+pub enum EnumWrapper<E> {
+    #[error("wrapped")]
+    Wrapped(#[from] E),
+    #[error("retried {attempts} times")]
+    Retried {
+        #[source]
+        source: Option<E>,
+        attempts: u32,
+    },
+}
+
+#[test]
+fn test_generic_from() {
+    let error = Wrapper::from(fmt::Error);
+    assert!(error.source().unwrap().is::<fmt::Error>());
+
+    let error = EnumWrapper::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!(error.source().unwrap().to_string(), "oh no!");
+}
+
+#[test]
+fn test_generic_source() {
+    let error = EnumWrapper::<fmt::Error>::Retried {
+        source: Some(fmt::Error),
+        attempts: 3,
+    };
+    assert!(error.source().unwrap().is::<fmt::Error>());
+
+    let error = EnumWrapper::<fmt::Error>::Retried {
+        source: None,
+        attempts: 3,
+    };
+    assert!(error.source().is_none());
+}
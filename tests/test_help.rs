@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InstallError {
+    #[error("{} already exists", path.display())]
+    #[help("try running with --force to overwrite {}", path.display())]
+    Exists { path: PathBuf },
+    #[error("version {0} not found")]
+    #[help("available versions are {1:?}")]
+    NotFound(String, Vec<String>),
+    #[error("download failed")]
+    Download,
+}
+
+#[derive(Error, Debug)]
+#[help("see the log for {id}")]
+pub enum Defaulted {
+    #[error("failed")]
+    Failed { id: u32 },
+    #[error("crashed")]
+    #[help("restart the service")]
+    Crashed { id: u32 },
+}
+
+#[derive(Error, Debug)]
+#[error("port {port} is in use")]
+#[help("pick a port other than {port}")]
+pub struct PortInUse {
+    port: u16,
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[help("{path:?} with {}", path.display())]
+pub struct Path {
+    path: PathBuf,
+}
+
+#[test]
+fn test_variant_help() {
+    let error = InstallError::Exists {
+        path: PathBuf::from("/opt/tool"),
+    };
+    assert_eq!(
+        error.help().unwrap(),
+        "try running with --force to overwrite /opt/tool",
+    );
+    let error = InstallError::NotFound("9".to_owned(), vec!["1".to_owned()]);
+    assert_eq!(error.help().unwrap(), "available versions are [\"1\"]");
+    assert_eq!(InstallError::Download.help(), None);
+}
+
+#[test]
+fn test_enum_default() {
+    assert_eq!(
+        Defaulted::Failed { id: 7 }.help().unwrap(),
+        "see the log for 7",
+    );
+    assert_eq!(
+        Defaulted::Crashed { id: 7 }.help().unwrap(),
+        "restart the service",
+    );
+}
+
+#[test]
+fn test_struct_help() {
+    assert_eq!(
+        PortInUse { port: 80 }.help().unwrap(),
+        "pick a port other than 80",
+    );
+    let error = Path {
+        path: PathBuf::from("/tmp"),
+    };
+    assert_eq!(error.help().unwrap(), "\"/tmp\" with /tmp");
+}
@@ -0,0 +1,29 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(cold)]
+pub enum ColdError {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Parse(String),
+}
+
+#[derive(Error, Debug)]
+#[error("lookup failed")]
+#[error(inline)]
+pub struct InlineError(#[from] io::Error);
+
+#[test]
+fn test_cold() {
+    let error = ColdError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("i/o failed", error.to_string());
+    assert_eq!("bad", ColdError::Parse("bad".to_owned()).to_string());
+}
+
+#[test]
+fn test_inline() {
+    let error = InlineError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    assert_eq!("lookup failed", error.to_string());
+}
@@ -0,0 +1,61 @@
+use std::error::Error as StdError;
+use std::io;
+use std::sync::Mutex;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(hook)]
+#[error("request failed")]
+pub struct RequestError {
+    #[from]
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[error(hook, constructors)]
+pub enum StoreError {
+    #[error("key {0} not found")]
+    NotFound(String),
+    #[error("store is closed")]
+    Closed,
+}
+
+#[derive(Error, Debug)]
+#[error("not hooked")]
+pub struct QuietError {
+    #[from]
+    source: io::Error,
+}
+
+static SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record(error: &dyn StdError) {
+    SEEN.lock().unwrap().push(error.to_string());
+}
+
+fn record_source(error: &dyn StdError) {
+    if let Some(source) = error.source() {
+        SEEN.lock()
+            .unwrap()
+            .push(format!("  caused by: {}", source));
+    }
+}
+
+#[test]
+fn test_hooks() {
+    thiserror::on_error(record);
+    thiserror::on_error(record_source);
+
+    let _ = RequestError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    let _ = QuietError::from(io::Error::new(io::ErrorKind::Other, "..."));
+    let _ = StoreError::not_found("user:1");
+    let _ = StoreError::Closed;
+
+    let seen = SEEN.lock().unwrap();
+    let expected = [
+        "request failed",
+        "  caused by: oh no!",
+        "key user:1 not found",
+    ];
+    assert_eq!(*seen, expected);
+}
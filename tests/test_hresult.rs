@@ -0,0 +1,61 @@
+#![cfg(feature = "windows")]
+
+use thiserror::Error;
+use windows::core::{Error as WinError, HRESULT};
+
+// The windows crate, which re-exports windows-core as windows::core, only
+// builds on Windows.
+mod windows {
+    pub use windows_core as core;
+}
+
+const E_ACCESSDENIED: HRESULT = HRESULT(0x8007_0005u32 as i32);
+
+#[derive(Error, Debug)]
+pub enum ComError {
+    #[error("access denied")]
+    #[hresult(E_ACCESSDENIED)]
+    AccessDenied,
+    #[error("not implemented")]
+    #[hresult(0x8000_4001)]
+    NotImplemented { interface: Option<String> },
+    #[error("unexpected failure")]
+    Unexpected,
+    #[error(transparent)]
+    #[hresult(other)]
+    Other(WinError),
+}
+
+#[derive(Error, Debug)]
+#[error("invalid argument")]
+#[hresult(0x8007_0057)]
+pub struct InvalidArgument;
+
+#[test]
+fn test_hresult() {
+    assert_eq!(0x8007_0005u32 as i32, ComError::AccessDenied.hresult());
+    let error = ComError::NotImplemented { interface: None };
+    assert_eq!(0x8000_4001u32 as i32, error.hresult());
+    assert_eq!(0x8000_4005u32 as i32, ComError::Unexpected.hresult());
+    let error = ComError::Other(WinError::from_hresult(HRESULT(0x8000_ffffu32 as i32)));
+    assert_eq!(0x8000_ffffu32 as i32, error.hresult());
+    assert_eq!(0x8007_0057u32 as i32, InvalidArgument.hresult());
+}
+
+#[test]
+fn test_from_windows_error() {
+    let error = WinError::from_hresult(HRESULT(0x8007_0005u32 as i32));
+    assert!(matches!(ComError::from(error), ComError::AccessDenied));
+
+    let error = WinError::from_hresult(HRESULT(0x8000_4001u32 as i32));
+    assert!(matches!(
+        ComError::from(error),
+        ComError::NotImplemented { interface: None },
+    ));
+
+    let error = WinError::from_hresult(HRESULT(0x8000_ffffu32 as i32));
+    match ComError::from(error) {
+        ComError::Other(error) => assert_eq!(HRESULT(0x8000_ffffu32 as i32), error.code()),
+        other => panic!("expected Other, got {:?}", other),
+    }
+}
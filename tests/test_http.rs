@@ -0,0 +1,69 @@
+use http::StatusCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(status = 500, expose = false)]
+pub enum ApiError {
+    #[error(status = 404, expose = true)]
+    #[error("no user named {0}")]
+    UnknownUser(String),
+    #[error(status = 503)]
+    #[error("database at {0} is down")]
+    Database(String),
+    #[error("internal invariant violated: {0}")]
+    Internal(&'static str),
+}
+
+#[derive(Error, Debug)]
+#[error(status = 429)]
+#[error("slow down")]
+pub struct RateLimited;
+
+#[test]
+fn test_status_code() {
+    assert_eq!(404, ApiError::UnknownUser("alice".to_owned()).status_code());
+    assert_eq!(503, ApiError::Database("10.0.0.1".to_owned()).status_code());
+    assert_eq!(500, ApiError::Internal("oops").status_code());
+    assert_eq!(429, RateLimited.status_code());
+}
+
+#[test]
+fn test_public_message() {
+    let error = ApiError::UnknownUser("alice".to_owned());
+    assert_eq!("no user named alice", error.public_message());
+
+    let error = ApiError::Database("10.0.0.1".to_owned());
+    assert_eq!("Service Unavailable", error.public_message());
+    assert_eq!("database at 10.0.0.1 is down", error.to_string());
+
+    let error = ApiError::Internal("oops");
+    assert_eq!("Internal Server Error", error.public_message());
+
+    assert_eq!("slow down", RateLimited.public_message());
+}
+
+#[derive(Error, Debug)]
+#[status(400)]
+pub enum RequestError {
+    #[error("no such document")]
+    #[status(StatusCode::NOT_FOUND)]
+    Missing,
+    #[error("revision conflict")]
+    #[status(StatusCode::CONFLICT)]
+    #[error(expose = false)]
+    Conflict,
+    #[error("gateway timed out")]
+    #[status(504)]
+    Timeout,
+    #[error("malformed request")]
+    Malformed,
+}
+
+#[test]
+fn test_status_attribute() {
+    assert_eq!(404, RequestError::Missing.status_code());
+    assert_eq!(409, RequestError::Conflict.status_code());
+    assert_eq!(504, RequestError::Timeout.status_code());
+    assert_eq!(400, RequestError::Malformed.status_code());
+    assert_eq!("Conflict", RequestError::Conflict.public_message());
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "humantime")]
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("request timed out after {elapsed}")]
+pub struct Timeout {
+    #[display(humantime)]
+    elapsed: Duration,
+}
+
+#[derive(Error, Debug)]
+pub enum LeaseError {
+    #[error("lease expired at {0}")]
+    Expired(#[display(humantime)] SystemTime),
+    #[error("lease of {0:>6} is too short")]
+    TooShort(#[display(humantime)] Duration),
+}
+
+fn timeout(elapsed: Duration) -> String {
+    Timeout { elapsed }.to_string()
+}
+
+#[test]
+fn test_duration() {
+    assert_eq!(
+        "request timed out after 2.3s",
+        timeout(Duration::from_millis(2_300))
+    );
+    assert_eq!(
+        "request timed out after 2s",
+        timeout(Duration::from_millis(2_020))
+    );
+    assert_eq!(
+        "request timed out after 150ms",
+        timeout(Duration::from_millis(150))
+    );
+    assert_eq!(
+        "request timed out after 12µs",
+        timeout(Duration::from_micros(12))
+    );
+    assert_eq!(
+        "request timed out after 7ns",
+        timeout(Duration::from_nanos(7))
+    );
+    assert_eq!(
+        "request timed out after 1h 2m 3s",
+        timeout(Duration::from_millis(3_723_400)),
+    );
+}
+
+#[test]
+fn test_system_time() {
+    let at = UNIX_EPOCH + Duration::from_millis(1_714_566_896_789);
+    assert_eq!(
+        "lease expired at 2024-05-01T12:34:56.789Z",
+        LeaseError::Expired(at).to_string(),
+    );
+
+    let before_epoch = UNIX_EPOCH - Duration::from_secs(1);
+    assert_eq!(
+        "lease expired at unknown",
+        LeaseError::Expired(before_epoch).to_string(),
+    );
+
+    let after_9999 = UNIX_EPOCH + Duration::from_secs(253_402_300_800);
+    assert_eq!(
+        "lease expired at unknown",
+        LeaseError::Expired(after_9999).to_string(),
+    );
+}
+
+#[test]
+fn test_padding() {
+    let error = LeaseError::TooShort(Duration::from_millis(150));
+    assert_eq!("lease of  150ms is too short", error.to_string());
+}
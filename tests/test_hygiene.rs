@@ -0,0 +1,56 @@
+// The expansion must keep working when the surrounding module shadows the
+// standard library, its prelude or its macros.
+mod shadowed {
+    #![allow(dead_code, non_camel_case_types)]
+
+    mod core {}
+    mod std {}
+
+    struct Option;
+    struct Result;
+    struct Some;
+    struct None;
+    struct Ok;
+    struct Err;
+    struct Box;
+    struct String;
+
+    #[allow(unused_macros)]
+    macro_rules! write {
+        ($($tt:tt)*) => {
+            compile_error!("shadowed write!")
+        };
+    }
+
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Error {
+        #[error("i/o failed: {0}")]
+        Io(#[from] ::std::io::Error),
+        #[error("code {code:04}")]
+        Code { code: u32 },
+        #[error("plain")]
+        Plain,
+        #[error(transparent)]
+        Other(#[from] ::std::fmt::Error),
+    }
+
+    #[derive(Error, Debug)]
+    #[error("wrapped {}", .0)]
+    pub struct Wrapped(#[source] pub Error);
+}
+
+#[test]
+fn test_shadowed() {
+    use std::error::Error as _;
+
+    let error = shadowed::Error::Code { code: 7 };
+    assert_eq!("code 0007", error.to_string());
+    let error = shadowed::Wrapped(shadowed::Error::from(std::fmt::Error));
+    assert_eq!(
+        "wrapped an error occurred when formatting an argument",
+        error.to_string()
+    );
+    assert!(error.source().is_some());
+}
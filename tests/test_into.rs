@@ -0,0 +1,42 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[into(io::Error, kind = io::ErrorKind::Other)]
+pub enum MyError {
+    #[error("missing {0}")]
+    #[into(kind = io::ErrorKind::NotFound)]
+    Missing(String),
+    #[error("timed out")]
+    #[into(with = timeout_to_io)]
+    Timeout,
+    #[error("unknown")]
+    Unknown,
+}
+
+fn timeout_to_io(_error: MyError) -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "deadline elapsed")
+}
+
+#[derive(Error, Debug)]
+#[error("denied")]
+#[into(io::Error, kind = io::ErrorKind::PermissionDenied)]
+pub struct Denied;
+
+#[test]
+fn test_into() {
+    let io = io::Error::from(MyError::Missing("config".to_owned()));
+    assert_eq!(io.kind(), io::ErrorKind::NotFound);
+    assert_eq!(io.to_string(), "missing config");
+    assert!(io.into_inner().unwrap().is::<MyError>());
+
+    let io = io::Error::from(MyError::Timeout);
+    assert_eq!(io.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(io.to_string(), "deadline elapsed");
+
+    let io = io::Error::from(MyError::Unknown);
+    assert_eq!(io.kind(), io::ErrorKind::Other);
+
+    let io: io::Error = Denied.into();
+    assert_eq!(io.kind(), io::ErrorKind::PermissionDenied);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "axum")]
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use futures::executor::block_on;
+use thiserror::Error;
+
+fn into_parts(response: Response) -> (StatusCode, String) {
+    let status = response.status();
+    let body = block_on(axum::body::to_bytes(response.into_body(), usize::MAX)).unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[derive(Error, Debug)]
+#[error(into_response, status = 400)]
+pub enum ApiError {
+    #[error("no user named {0}")]
+    #[status(404)]
+    UnknownUser(String),
+    #[error("connection to {0} reset")]
+    #[status(503)]
+    #[error(expose = false)]
+    Database(String),
+    #[error("bad request")]
+    BadRequest,
+}
+
+#[derive(Error, Debug)]
+#[error(into_response)]
+#[error("invalid status")]
+#[status(1000)]
+pub struct InvalidStatus;
+
+#[test]
+fn test_into_response() {
+    let response = into_parts(ApiError::UnknownUser("alice".to_owned()).into_response());
+    assert_eq!(
+        response,
+        (StatusCode::NOT_FOUND, "no user named alice".to_owned())
+    );
+
+    let response = into_parts(ApiError::Database("10.0.0.1".to_owned()).into_response());
+    assert_eq!(
+        response,
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Service Unavailable".to_owned()
+        )
+    );
+
+    let response = into_parts(ApiError::BadRequest.into_response());
+    assert_eq!(
+        response,
+        (StatusCode::BAD_REQUEST, "bad request".to_owned())
+    );
+
+    let response = into_parts(InvalidStatus.into_response());
+    assert_eq!(
+        response,
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "invalid status".to_owned()
+        )
+    );
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use super::into_parts;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    #[error(into_response(json))]
+    pub enum JsonError {
+        #[error("no user named {0}")]
+        #[status(404)]
+        #[code("user.unknown")]
+        UnknownUser(String),
+        #[error("internal error")]
+        Internal,
+    }
+
+    #[test]
+    fn test_json_body() {
+        let response = into_parts(JsonError::UnknownUser("alice".to_owned()).into_response());
+        let expected = r#"{"code":"user.unknown","message":"no user named alice"}"#;
+        assert_eq!(response, (StatusCode::NOT_FOUND, expected.to_owned()));
+
+        let response = into_parts(JsonError::Internal.into_response());
+        let expected = r#"{"message":"internal error"}"#;
+        assert_eq!(
+            response,
+            (StatusCode::INTERNAL_SERVER_ERROR, expected.to_owned())
+        );
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "utoipa")]
+
+use serde_json::json;
+use thiserror::Error;
+
+use serde_json::Value;
+use utoipa::{IntoResponses, PartialSchema, ToSchema};
+
+#[derive(Error, Debug)]
+#[error(into_responses)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[status(404)]
+    UserNotFound(u64),
+    #[error("team {0} not found")]
+    #[status(404)]
+    TeamNotFound(u64),
+    #[error("database password rejected")]
+    #[error(expose = false)]
+    Database,
+}
+
+#[cfg(all(feature = "axum", feature = "serde"))]
+#[derive(Error, Debug)]
+#[error(into_responses)]
+#[error("slow down")]
+#[status(429)]
+#[error(into_response(json))]
+pub struct RateLimited;
+
+fn response(description: &str, content_type: &str, name: &str) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            content_type: {
+                "schema": { "$ref": format!("#/components/schemas/{}", name) },
+            },
+        },
+    })
+}
+
+#[test]
+fn test_responses() {
+    let responses = ApiError::responses();
+    assert_eq!(2, responses.len());
+    assert_eq!(
+        response(
+            "user {0} not found\nteam {0} not found",
+            "text/plain",
+            "ApiError",
+        ),
+        json!(responses["404"]),
+    );
+    assert_eq!(
+        response("Internal Server Error", "text/plain", "ApiError"),
+        json!(responses["500"]),
+    );
+}
+
+#[cfg(all(feature = "axum", feature = "serde"))]
+#[test]
+fn test_json_body() {
+    let responses = RateLimited::responses();
+    assert_eq!(
+        response("slow down", "application/json", "RateLimited"),
+        json!(responses["429"]),
+    );
+
+    assert_eq!("RateLimited", RateLimited::name());
+    let expected = json!({
+        "type": "object",
+        "properties": {
+            "code": { "type": "string" },
+            "message": { "type": "string" },
+        },
+        "required": ["message"],
+    });
+    assert_eq!(expected, json!(RateLimited::schema()));
+}
+
+#[test]
+fn test_schema() {
+    assert_eq!("ApiError", ApiError::name());
+    assert_eq!(json!({ "type": "string" }), json!(ApiError::schema()));
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "wasm")]
+
+use js_sys::Reflect;
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+
+#[derive(Error, Debug)]
+#[error(js_value)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[code("user.not_found")]
+    NotFound(String),
+    #[error("rate limited")]
+    RateLimited,
+}
+
+#[derive(Error, Debug)]
+#[error(js_value)]
+#[error("invalid input")]
+pub struct InvalidInput;
+
+// The message of the JavaScript Error and its `code` property, if any.
+fn js_error(value: JsValue) -> (String, Option<String>) {
+    let error = value.dyn_into::<js_sys::Error>().unwrap();
+    let code = Reflect::get(&error, &JsValue::from_str("code")).unwrap();
+    (String::from(error.message()), code.as_string())
+}
+
+// js-sys can only call into JavaScript when compiled to WebAssembly.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(not(target_arch = "wasm32"), ignore = "requires a JavaScript host")]
+fn test_js_value() {
+    let value = JsValue::from(ApiError::NotFound("alice".to_owned()));
+    let expected = (
+        "user alice not found".to_owned(),
+        Some("user.not_found".to_owned()),
+    );
+    assert_eq!(expected, js_error(value));
+
+    let value = JsValue::from(ApiError::RateLimited);
+    assert_eq!(("rate limited".to_owned(), None), js_error(value));
+
+    let value = JsValue::from(InvalidInput);
+    assert_eq!(("invalid input".to_owned(), None), js_error(value));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "schemars")]
+
+use schemars::{JsonSchema, SchemaGenerator};
+use serde_json::{json, Value};
+use std::io;
+use thiserror::Error;
+
+fn json_schema<T: JsonSchema>() -> Value {
+    T::json_schema(&mut SchemaGenerator::default()).to_value()
+}
+
+#[derive(Error, Debug)]
+#[error(serialize, json_schema)]
+pub enum ApiError {
+    #[error("rate limited")]
+    #[code("E0001")]
+    RateLimited,
+    #[error("i/o failed")]
+    Io(#[source] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error(serialize, json_schema)]
+#[error("not found")]
+#[code("E0404")]
+pub struct NotFound;
+
+#[derive(Error, Debug)]
+#[error(serialize, json_schema)]
+#[error("timed out")]
+pub struct Timeout;
+
+#[test]
+fn test_enum_schema() {
+    assert_eq!("ApiError", ApiError::schema_name());
+    let schema = json_schema::<ApiError>();
+    let expected = json!({
+        "type": "object",
+        "properties": {
+            "variant": { "type": "string", "enum": ["RateLimited", "Io"] },
+            "message": { "type": "string" },
+            "code": { "type": "string", "enum": ["E0001"] },
+            "chain": { "type": "array", "items": { "type": "string" } },
+        },
+        "required": ["variant", "message", "chain"],
+    });
+    assert_eq!(expected, schema);
+}
+
+#[test]
+fn test_struct_schema() {
+    let schema = json_schema::<NotFound>();
+    assert_eq!(
+        json!({ "type": "string", "enum": ["E0404"] }),
+        schema["properties"]["code"]
+    );
+    assert_eq!(
+        json!(["variant", "message", "code", "chain"]),
+        schema["required"]
+    );
+
+    let schema = json_schema::<Timeout>();
+    assert!(schema["properties"].get("code").is_none());
+    assert_eq!(json!(["variant", "message", "chain"]), schema["required"]);
+}
+
+#[test]
+fn test_schema_matches_serialization() {
+    let value = serde_json::to_value(ApiError::RateLimited).unwrap();
+    let schema = json_schema::<ApiError>();
+    let properties = schema["properties"].as_object().unwrap();
+    for key in value.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "{} missing from schema", key);
+    }
+}
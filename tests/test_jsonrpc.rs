@@ -0,0 +1,75 @@
+#![cfg(feature = "jsonrpc")]
+
+use serde_json::json;
+use thiserror::{Error, JsonRpcError};
+
+#[derive(Error, Debug)]
+#[jsonrpc(code = -32000)]
+pub enum RpcError {
+    #[error("method {0} not found")]
+    #[jsonrpc(code = -32601)]
+    MethodNotFound(String),
+    #[error("invalid params")]
+    #[jsonrpc(code = -32602, data = fields)]
+    InvalidParams { fields: Vec<&'static str> },
+    #[error("node is syncing")]
+    Syncing,
+}
+
+#[derive(Error, Debug)]
+#[error("transaction {hash} was rejected")]
+#[jsonrpc(code = 3, data = reason)]
+pub struct Rejected {
+    hash: String,
+    reason: String,
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[jsonrpc]
+pub struct Internal;
+
+#[test]
+fn test_enum() {
+    let expected = JsonRpcError {
+        code: -32601,
+        message: "method eth_foo not found".to_owned(),
+        data: None,
+    };
+    let error = RpcError::MethodNotFound("eth_foo".to_owned());
+    assert_eq!(expected, error.to_jsonrpc_error());
+
+    let error = RpcError::InvalidParams {
+        fields: vec!["from", "to"],
+    };
+    let expected = JsonRpcError {
+        code: -32602,
+        message: "invalid params".to_owned(),
+        data: Some(json!(["from", "to"])),
+    };
+    assert_eq!(expected, error.to_jsonrpc_error());
+
+    assert_eq!(-32000, RpcError::Syncing.to_jsonrpc_error().code);
+}
+
+#[test]
+fn test_struct() {
+    let error = Rejected {
+        hash: "0xabc".to_owned(),
+        reason: "nonce too low".to_owned(),
+    };
+    let object = serde_json::to_value(error.to_jsonrpc_error()).unwrap();
+    let expected = json!({
+        "code": 3,
+        "message": "transaction 0xabc was rejected",
+        "data": "nonce too low",
+    });
+    assert_eq!(expected, object);
+
+    let object = serde_json::to_value(Internal.to_jsonrpc_error()).unwrap();
+    let expected = json!({
+        "code": -32603,
+        "message": "...",
+    });
+    assert_eq!(expected, object);
+}
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(kind)]
+pub enum FetchError {
+    /// The server did not answer in time.
+    #[error("request timed out")]
+    Timeout,
+    #[error("server returned {status}")]
+    Status { status: u16 },
+    #[error("I/O error")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error(kind)]
+pub enum Generic<T: std::fmt::Debug> {
+    #[error("{0:?}")]
+    Value(T),
+}
+
+#[test]
+fn test_kind() {
+    assert_eq!(FetchError::Timeout.kind(), FetchErrorKind::Timeout);
+    assert_eq!(
+        FetchError::Status { status: 503 }.kind(),
+        FetchErrorKind::Status,
+    );
+    let error = FetchError::from(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    assert_eq!(error.kind(), FetchErrorKind::Io);
+    assert_ne!(error.kind(), FetchErrorKind::Timeout);
+}
+
+#[test]
+fn test_map_key() {
+    let errors = [
+        FetchError::Timeout,
+        FetchError::Status { status: 500 },
+        FetchError::Timeout,
+    ];
+    let mut counts = HashMap::new();
+    for error in &errors {
+        *counts.entry(error.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&FetchErrorKind::Timeout], 2);
+    assert_eq!(counts[&FetchErrorKind::Status], 1);
+    assert!(!counts.contains_key(&FetchErrorKind::Io));
+}
+
+#[test]
+fn test_generic() {
+    let kind = Generic::Value("oh no!").kind();
+    let copy = kind;
+    assert_eq!(kind, copy);
+    assert_eq!(format!("{:?}", kind), "Value");
+}
+
+#[derive(Error, Debug)]
+#[error(kind)]
+#[non_exhaustive]
+pub enum Evolving {
+    #[error("first")]
+    First,
+    #[error("second {0}")]
+    #[non_exhaustive]
+    Second(u8),
+}
+
+#[test]
+fn test_non_exhaustive() {
+    assert_eq!(Evolving::Second(1).kind(), EvolvingKind::Second);
+    assert_eq!(format!("{:?}", Evolving::First.kind()), "First");
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "log")]
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::cell::RefCell;
+use std::io;
+use thiserror::Error;
+
+// Records every record logged on this thread.
+struct Recorder;
+
+thread_local! {
+    static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+impl Log for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let entry = (record.level(), record.args().to_string());
+        RECORDS.with(|records| records.borrow_mut().push(entry));
+    }
+
+    fn flush(&self) {}
+}
+
+fn take() -> Vec<(Level, String)> {
+    RECORDS.with(|records| records.borrow_mut().split_off(0))
+}
+
+#[derive(Error, Debug)]
+#[error(log)]
+#[level(info)]
+pub enum SyncError {
+    #[error("failed to load state")]
+    #[level(error)]
+    Load(#[source] LoadError),
+    #[error("peer went away")]
+    #[level(warn)]
+    Disconnected,
+    #[error("nothing to do")]
+    Idle,
+}
+
+#[derive(Error, Debug)]
+#[error(log)]
+#[error("could not read {path}")]
+pub struct LoadError {
+    path: &'static str,
+    source: io::Error,
+}
+
+#[test]
+fn test_log() {
+    log::set_logger(&Recorder).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let load = LoadError {
+        path: "state.db",
+        source: io::Error::new(io::ErrorKind::NotFound, "no such file"),
+    };
+    load.log();
+    SyncError::Load(load).log();
+    SyncError::Disconnected.log();
+    SyncError::Idle.log();
+
+    let expected = vec![
+        (
+            Level::Error,
+            "could not read state.db: no such file".to_owned(),
+        ),
+        (
+            Level::Error,
+            "failed to load state: could not read state.db: no such file".to_owned(),
+        ),
+        (Level::Warn, "peer went away".to_owned()),
+        (Level::Info, "nothing to do".to_owned()),
+    ];
+    assert_eq!(expected, take());
+}
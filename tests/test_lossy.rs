@@ -0,0 +1,70 @@
+use std::ffi::{CStr, CString, OsStr, OsString};
+use std::fmt::Display;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("variable {name} is not set")]
+struct StructOsString {
+    name: OsString,
+}
+
+#[derive(Error, Debug)]
+#[error("failed to open {0}")]
+struct StructOsStr<'a>(&'a OsStr);
+
+#[derive(Error, Debug)]
+enum EnumFfi<'a> {
+    #[error("library failed: {0}")]
+    Owned(CString),
+    #[error("library failed: {0}")]
+    Borrowed(&'a CStr),
+}
+
+#[derive(Error, Debug)]
+enum EnumBytes<'a> {
+    #[error("unexpected response {body}")]
+    Owned { body: Vec<u8> },
+    #[error("unexpected response {body}")]
+    Borrowed { body: &'a [u8] },
+}
+
+fn assert<T: Display>(expected: &str, value: T) {
+    assert_eq!(expected, value.to_string());
+}
+
+#[test]
+fn test_os_str() {
+    let name = OsString::from("HOME");
+    assert("variable HOME is not set", StructOsString { name });
+    assert("failed to open /etc", StructOsStr(OsStr::new("/etc")));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let name = OsStr::from_bytes(b"HO\xffME").to_owned();
+        assert("variable HO\u{fffd}ME is not set", StructOsString { name });
+    }
+}
+
+#[test]
+fn test_c_str() {
+    let message = CString::new(b"bad \xff byte".to_vec()).unwrap();
+    assert("library failed: bad \\xFF byte", EnumFfi::Owned(message));
+    let message = CStr::from_bytes_with_nul(b"out of memory\0").unwrap();
+    assert("library failed: out of memory", EnumFfi::Borrowed(message));
+}
+
+#[test]
+fn test_bytes() {
+    let body = b"ok".to_vec();
+    assert("unexpected response ok", EnumBytes::Owned { body });
+    let body = b"\xe2\x82\xac\xe2\x82 \x00\xc0".to_vec();
+    assert(
+        "unexpected response \u{20ac}\\xE2\\x82 \u{0}\\xC0",
+        EnumBytes::Owned { body },
+    );
+    assert(
+        "unexpected response ok\\xFF",
+        EnumBytes::Borrowed { body: b"ok\xff" },
+    );
+}
@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+#[error(macros)]
+pub enum StoreError {
+    #[error("{} not found", path.display())]
+    NotFound { path: PathBuf },
+    #[error("key {0} is too long")]
+    KeyTooLong(usize),
+    #[error("store is read-only")]
+    ReadOnly,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("limit {limit} exceeded")]
+#[error(macros)]
+pub struct LimitError {
+    limit: usize,
+}
+
+fn get(key: &str, read_only: bool) -> Result<(), StoreError> {
+    store_error_ensure!(key.len() <= 8, KeyTooLong(key.len()));
+    if read_only {
+        store_error_bail!(ReadOnly);
+    }
+    let path = PathBuf::from(key);
+    store_error_bail!(NotFound { path });
+}
+
+fn check(count: usize) -> Result<usize, LimitError> {
+    let limit = 3;
+    limit_error_ensure!(count <= limit, { limit });
+    Ok(count)
+}
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+fn app() -> Result<(), AppError> {
+    store_error_bail!(ReadOnly);
+}
+
+#[test]
+fn test_bail() {
+    assert_eq!(get("verylongkey", false), Err(StoreError::KeyTooLong(11)));
+    assert_eq!(get("key", true), Err(StoreError::ReadOnly));
+    assert_eq!(
+        get("key", false),
+        Err(StoreError::NotFound {
+            path: PathBuf::from("key"),
+        }),
+    );
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(check(2), Ok(2));
+    assert_eq!(check(4), Err(LimitError { limit: 3 }));
+}
+
+#[test]
+fn test_converts_into_return_type() {
+    let error = app().unwrap_err();
+    assert_eq!(error.to_string(), "store is read-only");
+}
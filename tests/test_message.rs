@@ -0,0 +1,42 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("io error")]
+    Io(#[from] io::Error),
+    #[error(message)]
+    Other(String),
+}
+
+#[derive(Error, Debug)]
+pub enum NamedError {
+    #[error("invalid input")]
+    Invalid,
+    #[error(message)]
+    Message { text: String },
+}
+
+#[test]
+fn test_message_from() {
+    let error = AppError::from("something went wrong");
+    assert_eq!(error.to_string(), "something went wrong");
+
+    let error = AppError::from(String::from("owned message"));
+    assert_eq!(error.to_string(), "owned message");
+
+    let error = NamedError::from("named");
+    match error {
+        NamedError::Message { text } => assert_eq!(text, "named"),
+        NamedError::Invalid => panic!("expected Message"),
+    }
+}
+
+#[test]
+fn test_msg() {
+    let error = AppError::msg(format_args!("retry {} of {}", 1, 3));
+    assert_eq!(error.to_string(), "retry 1 of 3");
+
+    let error = NamedError::msg(42);
+    assert_eq!(error.to_string(), "42");
+}
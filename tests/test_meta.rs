@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[meta(subsystem = "storage", retries = 3)]
+pub enum StorageError {
+    #[error("tenant {tenant_id} is over quota")]
+    #[meta(tenant = .tenant_id, reason = "quota")]
+    OverQuota { tenant_id: u64 },
+    #[error("bucket {0} not found")]
+    #[meta(bucket = .0, subsystem = "buckets")]
+    NotFound(String),
+    #[error("corrupt")]
+    Corrupt,
+    #[error("bad key")]
+    #[meta(key = .key, credential = .key)]
+    BadKey {
+        #[sensitive]
+        key: String,
+    },
+}
+
+#[derive(Error, Debug)]
+#[error("request {id} failed")]
+#[meta(request_id = .id, "http.method" = "GET")]
+pub struct RequestError {
+    id: u32,
+}
+
+fn collect(
+    metadata: impl Iterator<Item = (&'static str, String)>,
+) -> BTreeMap<&'static str, String> {
+    metadata.collect()
+}
+
+fn map(entries: &[(&'static str, &str)]) -> BTreeMap<&'static str, String> {
+    entries
+        .iter()
+        .map(|&(key, value)| (key, value.to_owned()))
+        .collect()
+}
+
+#[test]
+fn test_enum() {
+    let error = StorageError::OverQuota { tenant_id: 7 };
+    assert_eq!(
+        map(&[
+            ("reason", "quota"),
+            ("retries", "3"),
+            ("subsystem", "storage"),
+            ("tenant", "7"),
+        ]),
+        collect(error.metadata()),
+    );
+
+    let error = StorageError::NotFound("logs".to_owned());
+    assert_eq!(
+        map(&[
+            ("bucket", "logs"),
+            ("retries", "3"),
+            ("subsystem", "buckets")
+        ]),
+        collect(error.metadata()),
+    );
+
+    assert_eq!(
+        map(&[("retries", "3"), ("subsystem", "storage")]),
+        collect(StorageError::Corrupt.metadata()),
+    );
+}
+
+#[test]
+fn test_sensitive() {
+    let error = StorageError::BadKey {
+        key: "hunter2".to_owned(),
+    };
+    let metadata = collect(error.metadata());
+    assert_eq!("<redacted>", metadata["key"]);
+    assert_eq!("<redacted>", metadata["credential"]);
+}
+
+#[test]
+fn test_struct() {
+    let error = RequestError { id: 42 };
+    let metadata: Vec<_> = error.metadata().collect();
+    assert_eq!(
+        vec![
+            ("request_id", "42".to_owned()),
+            ("http.method", "GET".to_owned()),
+        ],
+        metadata,
+    );
+}
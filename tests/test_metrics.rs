@@ -0,0 +1,122 @@
+#![cfg(feature = "metrics")]
+
+use metrics::{Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, SharedString, Unit};
+use std::io;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+type Increments = Arc<Mutex<Vec<(String, Vec<(String, String)>)>>>;
+
+// Records every counter increment made while it is the local recorder.
+#[derive(Default)]
+struct Recorder {
+    increments: Increments,
+}
+
+struct Increment {
+    key: Key,
+    increments: Increments,
+}
+
+impl CounterFn for Increment {
+    fn increment(&self, value: u64) {
+        assert_eq!(value, 1);
+        let name = self.key.name().to_owned();
+        let labels = self
+            .key
+            .labels()
+            .map(|label| (label.key().to_owned(), label.value().to_owned()))
+            .collect();
+        self.increments.lock().unwrap().push((name, labels));
+    }
+
+    fn absolute(&self, _value: u64) {
+        unimplemented!()
+    }
+}
+
+impl metrics::Recorder for Recorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata) -> Counter {
+        Counter::from_arc(Arc::new(Increment {
+            key: key.clone(),
+            increments: Arc::clone(&self.increments),
+        }))
+    }
+
+    fn register_gauge(&self, _key: &Key, _metadata: &Metadata) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _key: &Key, _metadata: &Metadata) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+// Runs `f` with a Recorder installed and returns the increments it made.
+fn record(f: impl FnOnce()) -> Vec<(String, Vec<(String, String)>)> {
+    let recorder = Recorder::default();
+    metrics::with_local_recorder(&recorder, f);
+    let increments = recorder.increments.lock().unwrap().split_off(0);
+    increments
+}
+
+fn labels(labels: &[(&str, &str)]) -> Vec<(String, String)> {
+    labels
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[derive(Error, Debug)]
+#[error(metrics, constructors)]
+pub enum FetchError {
+    #[error("request timed out")]
+    Timeout,
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error(metrics)]
+#[error("parse failed")]
+pub struct ParseError {
+    #[from]
+    source: std::num::ParseIntError,
+}
+
+#[test]
+fn test_enum() {
+    let increments = record(|| {
+        let _ = FetchError::timeout();
+        let _ = FetchError::from(io::Error::new(io::ErrorKind::Other, "..."));
+        let _ = FetchError::Timeout;
+    });
+    assert_eq!(
+        increments,
+        [
+            (
+                "errors_total".to_owned(),
+                labels(&[("type", "FetchError"), ("variant", "Timeout")]),
+            ),
+            (
+                "errors_total".to_owned(),
+                labels(&[("type", "FetchError"), ("variant", "Io")]),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_struct() {
+    let increments = record(|| {
+        let _ = ParseError::from("x".parse::<u32>().unwrap_err());
+    });
+    assert_eq!(
+        increments,
+        [("errors_total".to_owned(), labels(&[("type", "ParseError")]))],
+    );
+}
@@ -0,0 +1,51 @@
+use miette::{Diagnostic, Severity};
+use thiserror::Error;
+
+// Without #[error(diagnostic)], #[diagnostic(...)] is left entirely to
+// miette's own derive, whether or not thiserror's miette feature is on.
+#[derive(Error, Diagnostic, Debug)]
+#[error("invalid port {port}")]
+#[diagnostic(
+    code(config::invalid_port),
+    severity(Warning),
+    help("ports go up to 65535")
+)]
+pub struct PortError {
+    port: u32,
+}
+
+#[derive(Error, Diagnostic, Debug)]
+#[error("unknown command {command}")]
+pub struct CommandError {
+    command: String,
+    #[help]
+    advice: Option<String>,
+}
+
+#[test]
+fn test_derive_both() {
+    let error = PortError { port: 70000 };
+    assert_eq!("invalid port 70000", error.to_string());
+    assert_eq!(
+        Some("config::invalid_port".to_owned()),
+        error.code().map(|code| code.to_string()),
+    );
+    assert_eq!(Some(Severity::Warning), error.severity());
+    assert_eq!(
+        Some("ports go up to 65535".to_owned()),
+        error.help().map(|help| help.to_string()),
+    );
+}
+
+#[test]
+fn test_help_field() {
+    let error = CommandError {
+        command: "pul".to_owned(),
+        advice: Some("did you mean `pull`?".to_owned()),
+    };
+    assert_eq!("unknown command pul", error.to_string());
+    assert_eq!(
+        Some("did you mean `pull`?".to_owned()),
+        error.help().map(|help| help.to_string()),
+    );
+}
@@ -0,0 +1,43 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(name)]
+pub enum FetchError {
+    #[error("request timed out")]
+    Timeout,
+    #[error("server returned {0}")]
+    Status(u16),
+    #[error("I/O error")]
+    Io {
+        #[from]
+        source: io::Error,
+    },
+    #[error(transparent)]
+    r#Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+#[error(name)]
+#[error("unit error")]
+pub struct UnitError;
+
+#[derive(Error, Debug)]
+#[error(name)]
+#[error("...")]
+pub struct Generic<T: std::fmt::Debug>(T);
+
+#[test]
+fn test_enum() {
+    assert_eq!(FetchError::Timeout.name(), "Timeout");
+    assert_eq!(FetchError::Status(503).name(), "Status");
+    let error = FetchError::from(io::Error::new(io::ErrorKind::NotFound, "oh no!"));
+    assert_eq!(error.name(), "Io");
+    assert_eq!(FetchError::Other("oh no!".into()).name(), "Other");
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(UnitError.name(), "UnitError");
+    assert_eq!(Generic(0).name(), "Generic");
+}
@@ -0,0 +1,54 @@
+use std::error::Error as _;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(no_alloc, name, kind)]
+#[severity(Warning)]
+pub enum SensorError {
+    #[error("sensor {id} timed out after {millis}ms")]
+    Timeout { id: u8, millis: u32 },
+    #[error("bus fault")]
+    Bus(#[from] fmt::Error),
+    #[error(transparent)]
+    Inner(InnerError),
+}
+
+#[derive(Error, Debug)]
+#[error("register {0:#04x} unreadable")]
+#[error(no_alloc)]
+pub struct InnerError(u8);
+
+#[derive(Error, Debug)]
+#[error("calibration failed")]
+#[error(no_alloc)]
+pub struct CalibrationError {
+    #[source]
+    inner: InnerError,
+}
+
+#[test]
+fn test_enum() {
+    let error = SensorError::Timeout { id: 3, millis: 20 };
+    assert_eq!(error.to_string(), "sensor 3 timed out after 20ms");
+    assert_eq!(error.name(), "Timeout");
+
+    let error = SensorError::from(fmt::Error);
+    assert_eq!(error.to_string(), "bus fault");
+    assert!(error.source().is_some());
+
+    let error = SensorError::Inner(InnerError(0x1f));
+    assert_eq!(error.to_string(), "register 0x1f unreadable");
+}
+
+#[test]
+fn test_struct() {
+    let error = CalibrationError {
+        inner: InnerError(2),
+    };
+    assert_eq!(error.to_string(), "calibration failed");
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "register 0x02 unreadable"
+    );
+}
@@ -0,0 +1,61 @@
+use std::error::Error as _;
+use std::fmt::{self, Display};
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug)]
+struct Custom;
+
+impl Display for Custom {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("custom failure")
+    }
+}
+
+impl std::error::Error for Custom {}
+
+#[derive(Error, Debug)]
+pub enum StoreError {
+    #[error("not found")]
+    #[from_match(io::Error, e if e.kind() == io::ErrorKind::NotFound)]
+    NotFound(#[source] io::Error),
+    #[error("store error")]
+    #[error(other)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[derive(Error, Debug)]
+pub enum Transparent {
+    #[error("closed")]
+    Closed,
+    #[error(transparent)]
+    #[error(other)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[test]
+fn test_constructor() {
+    let error = StoreError::other(Custom);
+    assert_eq!(error.to_string(), "store error");
+    assert_eq!(error.source().unwrap().to_string(), "custom failure");
+    assert!(error.source().unwrap().is::<Custom>());
+}
+
+#[test]
+fn test_transparent() {
+    let error = Transparent::other(Custom);
+    assert_eq!(error.to_string(), "custom failure");
+    assert_eq!(Transparent::Closed.to_string(), "closed");
+}
+
+#[test]
+fn test_from_match_fallback() {
+    let error = StoreError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+    assert!(matches!(error, StoreError::NotFound(_)));
+
+    let error = StoreError::from(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+    match error {
+        StoreError::Other(other) => assert!(other.is::<io::Error>()),
+        StoreError::NotFound(_) => panic!("expected Other"),
+    }
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(owned)]
+pub enum ParseError<'a> {
+    #[error("unexpected token {token:?} at {offset}")]
+    Unexpected { token: &'a str, offset: usize },
+    #[error("invalid bytes {0:?}")]
+    Bytes(&'a [u8]),
+    #[error("{0}")]
+    Message(Cow<'a, str>),
+    #[error("failed to read input")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error(owned)]
+#[error("bad excerpt {excerpt:?}")]
+pub struct Excerpt<'a> {
+    excerpt: &'a str,
+}
+
+fn parse(input: &str) -> Result<(), ParseError<'_>> {
+    Err(ParseError::Unexpected {
+        token: &input[2..4],
+        offset: 2,
+    })
+}
+
+#[test]
+fn test_into_owned() {
+    let owned = {
+        let input = String::from("a bc d");
+        parse(&input).unwrap_err().into_owned()
+    };
+    assert_eq!("unexpected token \"bc\" at 2", owned.to_string());
+    match owned {
+        ParseErrorOwned::Unexpected { token, offset } => {
+            let token: String = token;
+            assert_eq!(token, "bc");
+            assert_eq!(offset, 2);
+        }
+        _ => panic!("expected Unexpected"),
+    }
+
+    let owned = ParseError::Bytes(b"\xff").into_owned();
+    assert_eq!("invalid bytes [255]", owned.to_string());
+
+    let owned = ParseError::Message(Cow::Borrowed("borrowed")).into_owned();
+    match owned {
+        ParseErrorOwned::Message(Cow::Owned(message)) => assert_eq!(message, "borrowed"),
+        _ => panic!("expected owned Message"),
+    }
+
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let owned: ParseErrorOwned = ParseErrorOwned::from(io);
+    assert_eq!("failed to read input", owned.to_string());
+}
+
+#[test]
+fn test_struct_into_owned() {
+    let input = String::from("xyz");
+    let owned: ExcerptOwned = Excerpt { excerpt: &input }.into_owned();
+    drop(input);
+    assert_eq!("bad excerpt \"xyz\"", owned.to_string());
+}
+
+#[derive(Error, Debug)]
+#[error(owned)]
+#[non_exhaustive]
+pub enum LexError<'a> {
+    #[error("stray {0:?}")]
+    #[non_exhaustive]
+    Stray(&'a str),
+}
+
+#[test]
+fn test_non_exhaustive() {
+    let input = String::from("@");
+    let owned: LexErrorOwned = LexError::Stray(&input).into_owned();
+    drop(input);
+    assert_eq!("stray \"@\"", owned.to_string());
+}
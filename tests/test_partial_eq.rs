@@ -0,0 +1,100 @@
+#![cfg_attr(thiserror_nightly_testing, feature(backtrace))]
+
+use thiserror::Error;
+use tracing_error::SpanTrace;
+
+#[derive(Error, Debug)]
+#[error("parse error at line {line}")]
+#[error(partial_eq)]
+pub struct ParseError {
+    line: usize,
+    // SpanTrace does not implement PartialEq, so it is left out.
+    span_trace: SpanTrace,
+}
+
+#[derive(Error, Debug)]
+#[error(partial_eq)]
+pub enum ConfigError {
+    #[error("missing key {0}")]
+    Missing(String, Option<SpanTrace>),
+    #[error("invalid value for {key}")]
+    Invalid {
+        key: String,
+        #[source]
+        source: ParseError,
+    },
+    #[error("config is read-only")]
+    ReadOnly,
+}
+
+#[derive(Error, Debug)]
+#[error("wrapped {0:?}")]
+#[error(partial_eq)]
+pub struct Generic<T: std::fmt::Debug>(T);
+
+fn parse_error(line: usize) -> ParseError {
+    ParseError {
+        line,
+        span_trace: SpanTrace::capture(),
+    }
+}
+
+#[test]
+fn test_struct() {
+    assert_eq!(parse_error(1), parse_error(1));
+    assert_ne!(parse_error(1), parse_error(2));
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(
+        ConfigError::Missing("port".to_owned(), Some(SpanTrace::capture())),
+        ConfigError::Missing("port".to_owned(), None),
+    );
+    assert_ne!(
+        ConfigError::Missing("port".to_owned(), None),
+        ConfigError::Missing("host".to_owned(), None),
+    );
+    assert_ne!(
+        ConfigError::Missing("port".to_owned(), None),
+        ConfigError::ReadOnly
+    );
+    assert_eq!(ConfigError::ReadOnly, ConfigError::ReadOnly);
+
+    let invalid = |line| ConfigError::Invalid {
+        key: "port".to_owned(),
+        source: parse_error(line),
+    };
+    assert_eq!(invalid(1), invalid(1));
+    assert_ne!(invalid(1), invalid(2));
+}
+
+#[test]
+fn test_generic() {
+    assert_eq!(Generic(1), Generic(1));
+    assert_ne!(Generic("a"), Generic("b"));
+}
+
+#[cfg(thiserror_nightly_testing)]
+pub mod nightly {
+    use std::backtrace::Backtrace;
+    use thiserror::Error;
+    use tracing_error::SpanTrace;
+
+    #[derive(Error, Debug)]
+    #[error("timed out after {0}s")]
+    #[error(partial_eq)]
+    pub struct Timeout(u64, Backtrace);
+
+    #[test]
+    fn test_backtrace() {
+        assert_eq!(
+            Timeout(5, Backtrace::capture()),
+            Timeout(5, Backtrace::capture()),
+        );
+        assert_ne!(
+            Timeout(5, Backtrace::capture()),
+            Timeout(6, Backtrace::capture()),
+        );
+    }
+}
@@ -20,6 +20,8 @@ struct StructPath {
 enum EnumPathBuf {
     #[error("failed to read '{0}'")]
     Read(PathBuf),
+    #[error("{0}")]
+    Bare(PathBuf),
 }
 
 fn assert<T: Display>(expected: &str, value: T) {
@@ -33,5 +35,7 @@ fn test_display() {
     assert("failed to read '/thiserror'", StructPathBuf { file });
     let file = path.to_owned();
     assert("failed to read '/thiserror'", EnumPathBuf::Read(file));
+    let file = path.to_owned();
+    assert("/thiserror", EnumPathBuf::Bare(file));
     assert("failed to read '/thiserror'", StructPath::ref_cast(path));
 }
@@ -0,0 +1,62 @@
+#![cfg(feature = "pyo3")]
+
+use pyo3::exceptions::{PyKeyError, PyOSError, PyRuntimeError, PyValueError};
+use pyo3::types::PyAnyMethods;
+use pyo3::{PyErr, PyTypeInfo, Python};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[py(exception = pyo3::exceptions::PyValueError)]
+pub enum ParseError {
+    #[error("invalid digit {0:?}")]
+    InvalidDigit(char),
+    #[error("unknown key {0}")]
+    #[py(exception = pyo3::exceptions::PyKeyError)]
+    UnknownKey(String),
+}
+
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("could not read {0}")]
+    #[py(exception = pyo3::exceptions::PyOSError)]
+    Io(String),
+    #[error("corrupt cache")]
+    Corrupt,
+}
+
+#[derive(Error, Debug)]
+#[error("negative length {0}")]
+#[py(exception = pyo3::exceptions::PyValueError)]
+pub struct NegativeLength(i64);
+
+// Checks the exact type of the exception and its message, taken from its
+// arguments since str() of a KeyError puts the message in quotes.
+fn assert_err<T: PyTypeInfo>(message: &str, error: PyErr) {
+    Python::attach(|py| {
+        assert!(error.get_type(py).is(T::type_object(py)), "{}", error);
+        let args = error.value(py).getattr("args").unwrap();
+        let (arg,) = args.extract::<(String,)>().unwrap();
+        assert_eq!(message, arg);
+    });
+}
+
+#[test]
+fn test_enum() {
+    let error = PyErr::from(ParseError::InvalidDigit('x'));
+    assert_err::<PyValueError>("invalid digit 'x'", error);
+
+    let error = PyErr::from(ParseError::UnknownKey("size".to_owned()));
+    assert_err::<PyKeyError>("unknown key size", error);
+
+    let error = PyErr::from(LoadError::Io("cache.db".to_owned()));
+    assert_err::<PyOSError>("could not read cache.db", error);
+
+    let error = PyErr::from(LoadError::Corrupt);
+    assert_err::<PyRuntimeError>("corrupt cache", error);
+}
+
+#[test]
+fn test_struct() {
+    let error = PyErr::from(NegativeLength(-1));
+    assert_err::<PyValueError>("negative length -1", error);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "registry")]
+
+use thiserror::{Error, ErrorDescriptor, Severity};
+
+#[derive(Error, Debug)]
+pub enum RegisteredEnum {
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error(transparent)]
+    Other(std::io::Error),
+}
+
+#[derive(Error, Debug)]
+#[error("registered struct")]
+pub struct RegisteredStruct;
+
+#[derive(Error, Debug)]
+#[error("registered generic {0}")]
+pub struct RegisteredGeneric<T: std::fmt::Debug + std::fmt::Display>(T);
+
+#[test]
+fn test_registry() {
+    let registered = thiserror::registry()
+        .find(|registered| registered.type_name == "RegisteredEnum")
+        .unwrap();
+    assert_eq!(registered.module_path, "test_registry");
+    assert_eq!(
+        registered.descriptors,
+        [
+            ErrorDescriptor {
+                name: "NotFound",
+                message: Some("not found: {0}"),
+                code: None,
+                severity: Severity::Error,
+            },
+            ErrorDescriptor {
+                name: "Other",
+                message: None,
+                code: None,
+                severity: Severity::Error,
+            },
+        ],
+    );
+
+    let registered = thiserror::registry()
+        .find(|registered| registered.type_name == "RegisteredStruct")
+        .unwrap();
+    assert_eq!(registered.descriptors[0].message, Some("registered struct"));
+}
+
+#[test]
+fn test_register_by_hand() {
+    thiserror::register::<RegisteredStruct>();
+    thiserror::register::<RegisteredGeneric<u8>>();
+    thiserror::register::<RegisteredGeneric<String>>();
+    let count = thiserror::registry()
+        .filter(|registered| registered.type_name.starts_with("Registered"))
+        .count();
+    assert_eq!(count, 3);
+}
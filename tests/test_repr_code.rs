@@ -0,0 +1,64 @@
+use std::os::raw::c_char;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[repr_code]
+pub enum StoreError {
+    #[error("key {0} not found")]
+    NotFound(String),
+    #[error("store is read only")]
+    ReadOnly,
+    #[error("store is corrupt")]
+    #[repr_code(-10)]
+    Corrupt,
+    #[error("store is locked")]
+    Locked,
+}
+
+#[derive(Error, Debug)]
+#[error("connection refused")]
+#[repr_code(7)]
+pub struct ConnectError;
+
+#[derive(Error, Debug)]
+#[error("HTTP request failed")]
+#[repr_code]
+pub struct HTTPRequestError;
+
+fn message(
+    f: unsafe extern "C" fn(&StoreError, *mut c_char, usize) -> usize,
+    error: &StoreError,
+    len: usize,
+) -> (usize, Vec<u8>) {
+    let mut buf = vec![0x7f as c_char; len];
+    let n = unsafe { f(error, buf.as_mut_ptr(), len) };
+    let bytes = buf.iter().map(|&ch| ch as u8).collect();
+    (n, bytes)
+}
+
+#[test]
+fn test_codes() {
+    assert_eq!(1, StoreError::NotFound("a".to_owned()).repr_code());
+    assert_eq!(2, store_error_code(&StoreError::ReadOnly));
+    assert_eq!(-10, store_error_code(&StoreError::Corrupt));
+    assert_eq!(-9, store_error_code(&StoreError::Locked));
+    assert_eq!(7, connect_error_code(&ConnectError));
+    assert_eq!(1, http_request_error_code(&HTTPRequestError));
+}
+
+#[test]
+fn test_message() {
+    let error = StoreError::NotFound("a".to_owned());
+
+    let (n, buf) = message(store_error_message, &error, 32);
+    assert_eq!(15, n);
+    assert_eq!(b"key a not found\0", &buf[..16]);
+
+    // Truncated to fit, still terminated, and the full length is reported.
+    let (n, buf) = message(store_error_message, &error, 6);
+    assert_eq!(15, n);
+    assert_eq!(b"key a\0", &buf[..]);
+
+    let n = unsafe { store_error_message(&error, std::ptr::null_mut(), 0) };
+    assert_eq!(15, n);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "actix")]
+
+use thiserror::Error;
+
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, HttpResponseBuilder, ResponseError};
+use futures::executor::block_on;
+
+fn into_parts(response: HttpResponse) -> (StatusCode, String) {
+    let status = response.status();
+    let body = block_on(actix_web::body::to_bytes(response.into_body())).unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[derive(Error, Debug)]
+#[error(response_error)]
+pub enum ApiError {
+    #[error("no user named {0}")]
+    #[status(404)]
+    UnknownUser(String),
+    #[error("connection to {0} reset")]
+    #[status(503)]
+    #[error(expose = false)]
+    Database(String),
+}
+
+fn teapot(error: &Teapot, builder: &mut HttpResponseBuilder) -> HttpResponse {
+    builder.body(format!("<h1>{}</h1>", error))
+}
+
+#[derive(Error, Debug)]
+#[error(response_error(with = teapot))]
+#[error("short and stout")]
+#[status(418)]
+pub struct Teapot;
+
+#[test]
+fn test_response_error() {
+    let error = ApiError::UnknownUser("alice".to_owned());
+    assert_eq!(StatusCode::NOT_FOUND, ResponseError::status_code(&error));
+    assert_eq!(404, error.status_code());
+    let expected = (StatusCode::NOT_FOUND, "no user named alice".to_owned());
+    assert_eq!(expected, into_parts(error.error_response()));
+
+    let error = ApiError::Database("10.0.0.1".to_owned());
+    let expected = (
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Service Unavailable".to_owned(),
+    );
+    assert_eq!(expected, into_parts(error.error_response()));
+}
+
+#[test]
+fn test_builder_hook() {
+    let expected = (
+        StatusCode::IM_A_TEAPOT,
+        "<h1>short and stout</h1>".to_owned(),
+    );
+    assert_eq!(expected, into_parts(Teapot.error_response()));
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use super::into_parts;
+    use actix_web::http::StatusCode;
+    use actix_web::ResponseError;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    #[error(response_error(json))]
+    #[error("quota exceeded")]
+    #[status(429)]
+    #[code("quota.exceeded")]
+    pub struct QuotaExceeded;
+
+    #[test]
+    fn test_json_body() {
+        let expected = (
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"code":"quota.exceeded","message":"quota exceeded"}"#.to_owned(),
+        );
+        assert_eq!(expected, into_parts(QuotaExceeded.error_response()));
+    }
+}
@@ -0,0 +1,83 @@
+use std::io;
+use thiserror::{Error, Retryable};
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("request timed out")]
+    #[retryable]
+    Timeout,
+    #[error("server returned {0}")]
+    #[retryable(if = is_server_error)]
+    Status(u16),
+    #[error("invalid url")]
+    InvalidUrl,
+    #[error(transparent)]
+    Pool(PoolError),
+    #[error(transparent)]
+    Io(io::Error),
+}
+
+fn is_server_error(error: &FetchError) -> bool {
+    matches!(error, FetchError::Status(500..=599))
+}
+
+#[derive(Error, Debug)]
+#[retryable]
+pub enum PoolError {
+    #[error("pool exhausted")]
+    Exhausted,
+    #[error("pool closed")]
+    #[retryable(if = never)]
+    Closed,
+}
+
+fn never(_: &PoolError) -> bool {
+    false
+}
+
+#[derive(Error, Debug)]
+#[error("connection reset")]
+#[retryable]
+pub struct Reset;
+
+#[derive(Error, Debug)]
+pub enum Generic<E: std::fmt::Debug + std::fmt::Display> {
+    #[error("{0}")]
+    #[retryable]
+    Wrapped(E),
+}
+
+#[test]
+fn test_variants() {
+    assert!(FetchError::Timeout.is_retryable());
+    assert!(FetchError::Status(503).is_retryable());
+    assert!(!FetchError::Status(404).is_retryable());
+    assert!(!FetchError::InvalidUrl.is_retryable());
+    assert!(Reset.is_retryable());
+    assert!(Generic::Wrapped("oh no!").is_retryable());
+}
+
+#[test]
+fn test_enum_default() {
+    assert!(PoolError::Exhausted.is_retryable());
+    assert!(!PoolError::Closed.is_retryable());
+}
+
+#[test]
+fn test_transparent() {
+    assert!(FetchError::Pool(PoolError::Exhausted).is_retryable());
+    assert!(!FetchError::Pool(PoolError::Closed).is_retryable());
+    let error = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    assert!(!FetchError::Io(error).is_retryable());
+}
+
+#[test]
+fn test_trait() {
+    let errors: Vec<Box<dyn Retryable>> = vec![
+        Box::new(FetchError::Timeout),
+        Box::new(PoolError::Closed),
+        Box::new(Reset),
+    ];
+    let retryable: Vec<bool> = errors.iter().map(|error| error.is_retryable()).collect();
+    assert_eq!(retryable, [true, false, true]);
+}
@@ -0,0 +1,161 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("invalid token {token}")]
+    InvalidToken {
+        #[sensitive]
+        token: String,
+    },
+    #[error("unknown key {key} for {user}")]
+    UnknownKey {
+        #[sensitive(last4)]
+        key: String,
+        user: String,
+    },
+    #[error("session {0} expired")]
+    Expired(#[sensitive(hash)] String),
+    #[error("cannot read {path}")]
+    Credentials {
+        #[sensitive]
+        path: PathBuf,
+    },
+    #[error("{0:?} rejected")]
+    Rejected(#[sensitive(last4)] String),
+}
+
+#[derive(Error, Debug)]
+#[error("password for {user} is {password:>12}")]
+pub struct PasswordError {
+    user: String,
+    #[sensitive]
+    password: String,
+}
+
+#[derive(Error, Debug)]
+pub enum ExplicitError {
+    #[error("invalid token {}", .token)]
+    InvalidToken {
+        #[sensitive]
+        token: String,
+    },
+    #[error("unknown key {} for {}", .key, .user)]
+    UnknownKey {
+        #[sensitive(last4)]
+        key: String,
+        user: String,
+    },
+    #[error("session {} expired", .0)]
+    Expired(#[sensitive(hash)] String),
+}
+
+#[derive(Error, Debug)]
+pub enum PaddedError {
+    #[error("[{0:>24}]")]
+    Key(#[sensitive(last4)] String),
+    #[error("[{0:<24}]")]
+    Session(#[sensitive(hash)] String),
+}
+
+// A sensitive field need not implement Display.
+pub struct Secret;
+
+#[derive(Error, Debug)]
+#[error("secret {0}")]
+pub struct SecretError(#[sensitive] pub Secret);
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("Secret")
+    }
+}
+
+#[test]
+fn test_redacted() {
+    let error = AuthError::InvalidToken {
+        token: "hunter2".to_owned(),
+    };
+    assert_eq!("invalid token <redacted>", error.to_string());
+
+    let error = AuthError::Credentials {
+        path: PathBuf::from("/home/user/.token"),
+    };
+    assert_eq!("cannot read <redacted>", error.to_string());
+
+    let error = PasswordError {
+        user: "root".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert_eq!("password for root is   <redacted>", error.to_string());
+
+    assert_eq!("secret <redacted>", SecretError(Secret).to_string());
+}
+
+#[test]
+fn test_last4() {
+    let error = AuthError::UnknownKey {
+        key: "sk_live_4242424242".to_owned(),
+        user: "alice".to_owned(),
+    };
+    assert_eq!(
+        "unknown key <redacted ...4242> for alice",
+        error.to_string()
+    );
+
+    let error = AuthError::UnknownKey {
+        key: "short".to_owned(),
+        user: "alice".to_owned(),
+    };
+    assert_eq!("unknown key <redacted> for alice", error.to_string());
+
+    let error = AuthError::Rejected("sk_live_4242424242".to_owned());
+    assert_eq!("<redacted ...4242> rejected", error.to_string());
+}
+
+#[test]
+fn test_hash() {
+    let first = AuthError::Expired("a1b2c3".to_owned()).to_string();
+    let again = AuthError::Expired("a1b2c3".to_owned()).to_string();
+    let other = AuthError::Expired("d4e5f6".to_owned()).to_string();
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert!(first.starts_with("session <redacted #"));
+    assert!(!first.contains("a1b2c3"));
+    assert_eq!(
+        "session <redacted #811c9dc5> expired",
+        AuthError::Expired(String::new()).to_string()
+    );
+}
+
+#[test]
+fn test_explicit_args() {
+    let error = ExplicitError::InvalidToken {
+        token: "hunter2".to_owned(),
+    };
+    assert_eq!("invalid token <redacted>", error.to_string());
+
+    let error = ExplicitError::UnknownKey {
+        key: "sk_live_4242424242".to_owned(),
+        user: "alice".to_owned(),
+    };
+    assert_eq!(
+        "unknown key <redacted ...4242> for alice",
+        error.to_string()
+    );
+
+    let error = ExplicitError::Expired("a1b2c3".to_owned());
+    assert_eq!(
+        AuthError::Expired("a1b2c3".to_owned()).to_string(),
+        error.to_string(),
+    );
+}
+
+#[test]
+fn test_padding() {
+    let error = PaddedError::Key("sk_live_4242424242".to_owned());
+    assert_eq!("[      <redacted ...4242>]", error.to_string());
+
+    let error = PaddedError::Session(String::new());
+    assert_eq!("[<redacted #811c9dc5>    ]", error.to_string());
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "sentry")]
+
+use std::collections::BTreeMap;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[sentry(tag(component = "store"))]
+pub enum StoreError {
+    #[error("i/o failed")]
+    #[sentry(fingerprint = "store-io", tag(subsystem = "io", "os.family" = "unix"))]
+    Io(#[source] io::Error),
+    #[error("key {0} not found")]
+    #[sentry(fingerprint = "{{ default }}", fingerprint = "not-found")]
+    NotFound(String),
+    #[error("store is locked")]
+    #[sentry(tag(component = "lock"))]
+    Locked,
+}
+
+#[derive(Error, Debug)]
+#[error("connection lost")]
+#[sentry(fingerprint = "connection")]
+pub struct ConnectionError;
+
+fn tags(pairs: &[(&'static str, &'static str)]) -> BTreeMap<&'static str, &'static str> {
+    pairs.iter().cloned().collect()
+}
+
+#[test]
+fn test_fingerprint() {
+    let error = StoreError::Io(io::Error::new(io::ErrorKind::NotFound, "oh no"));
+    assert_eq!(vec!["store-io"], error.sentry_fingerprint());
+    let error = StoreError::NotFound("a".to_owned());
+    assert_eq!(
+        vec!["{{ default }}", "not-found"],
+        error.sentry_fingerprint(),
+    );
+    assert_eq!(
+        vec!["{{ default }}"],
+        StoreError::Locked.sentry_fingerprint()
+    );
+    assert_eq!(vec!["connection"], ConnectionError.sentry_fingerprint());
+}
+
+#[test]
+fn test_tags() {
+    let error = StoreError::Io(io::Error::new(io::ErrorKind::NotFound, "oh no"));
+    assert_eq!(
+        tags(&[
+            ("component", "store"),
+            ("os.family", "unix"),
+            ("subsystem", "io"),
+        ]),
+        error.sentry_tags(),
+    );
+    let error = StoreError::NotFound("a".to_owned());
+    assert_eq!(tags(&[("component", "store")]), error.sentry_tags());
+    assert_eq!(
+        tags(&[("component", "lock")]),
+        StoreError::Locked.sentry_tags()
+    );
+    assert!(ConnectionError.sentry_tags().is_empty());
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(serialize)]
+pub enum ApiError {
+    #[error("user {0} not found")]
+    #[code("E0404")]
+    NotFound(String),
+    #[error("storage failed")]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Error, Debug)]
+#[error("could not write block {block}")]
+#[error(serialize)]
+pub struct StorageError {
+    block: u64,
+    source: io::Error,
+}
+
+#[test]
+fn test_serialize_variant() {
+    let error = ApiError::NotFound("alice".to_owned());
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "variant": "NotFound",
+            "message": "user alice not found",
+            "code": "E0404",
+            "chain": [],
+        }),
+    );
+}
+
+#[test]
+fn test_serialize_chain() {
+    let error = ApiError::from(StorageError {
+        block: 7,
+        source: io::Error::new(io::ErrorKind::Other, "disk full"),
+    });
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "variant": "Storage",
+            "message": "storage failed",
+            "chain": ["could not write block 7", "disk full"],
+        }),
+    );
+}
@@ -0,0 +1,53 @@
+use thiserror::{Error, Severity};
+
+#[derive(Error, Debug)]
+#[severity(Warning)]
+pub enum SyncError {
+    #[error("retrying after timeout")]
+    Timeout,
+    #[error("database is corrupt")]
+    #[severity(Fatal)]
+    Corrupt,
+    #[error("cache miss for {0}")]
+    #[severity(Info)]
+    CacheMiss(String),
+}
+
+#[derive(Error, Debug)]
+pub enum Partial {
+    #[error("disk full")]
+    #[severity(Fatal)]
+    DiskFull,
+    #[error("unlabelled")]
+    Unlabelled,
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[severity(Info)]
+pub struct Notice;
+
+#[test]
+fn test_severity() {
+    assert_eq!(SyncError::Timeout.severity(), Severity::Warning);
+    assert_eq!(SyncError::Corrupt.severity(), Severity::Fatal);
+    assert_eq!(
+        SyncError::CacheMiss("key".to_owned()).severity(),
+        Severity::Info,
+    );
+    assert_eq!(Notice.severity(), Severity::Info);
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(Partial::DiskFull.severity(), Severity::Fatal);
+    assert_eq!(Partial::Unlabelled.severity(), Severity::Error);
+}
+
+#[test]
+fn test_ordering() {
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+    assert!(Severity::Error < Severity::Fatal);
+    assert_eq!(Severity::Warning.to_string(), "warning");
+}
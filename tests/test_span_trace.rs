@@ -0,0 +1,51 @@
+use std::io;
+use thiserror::Error;
+use tracing_error::{ErrorLayer, SpanTrace, SpanTraceStatus};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+// Runs `f` inside a span, under a subscriber that lets SpanTrace capture it.
+fn in_span<T>(f: impl FnOnce() -> T) -> T {
+    let subscriber = Registry::default().with(ErrorLayer::default());
+    tracing::subscriber::with_default(subscriber, || tracing::info_span!("test").in_scope(f))
+}
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct StructError {
+    #[from]
+    source: io::Error,
+    span_trace: SpanTrace,
+}
+
+#[derive(Error, Debug)]
+pub enum EnumError {
+    #[error("...")]
+    Io {
+        #[from]
+        source: io::Error,
+        context: tracing_error::SpanTrace,
+    },
+    #[error("...")]
+    Fmt(#[from] std::fmt::Error, Option<SpanTrace>),
+}
+
+#[test]
+fn test_capture() {
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    let error = in_span(|| StructError::from(io));
+    assert_eq!(error.span_trace.status(), SpanTraceStatus::CAPTURED);
+
+    let io = io::Error::new(io::ErrorKind::Other, "oh no!");
+    match in_span(|| EnumError::from(io)) {
+        EnumError::Io { context, .. } => assert_eq!(context.status(), SpanTraceStatus::CAPTURED),
+        EnumError::Fmt(..) => panic!("expected Io"),
+    }
+
+    match in_span(|| EnumError::from(std::fmt::Error)) {
+        EnumError::Fmt(_, span_trace) => {
+            assert_eq!(span_trace.unwrap().status(), SpanTraceStatus::CAPTURED);
+        }
+        EnumError::Io { .. } => panic!("expected Fmt"),
+    }
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(style(lowercase_start, no_trailing_period))]
+pub enum ConnectError {
+    #[error("connection refused by {0}")]
+    Refused(String),
+    #[error("HTTP status {0}")]
+    Status(u16),
+    #[error("{0} timed out")]
+    Timeout(String),
+    #[error("waiting for handshake...")]
+    Handshake,
+    #[error("i/o failed: e.g. reset")]
+    Io,
+}
+
+#[derive(Error, Debug)]
+#[error("TLS certificate rejected")]
+#[error(style(lowercase_start))]
+pub struct CertificateError;
+
+#[test]
+fn test_style() {
+    assert_eq!(
+        ConnectError::Refused("db".to_owned()).to_string(),
+        "connection refused by db",
+    );
+    assert_eq!(ConnectError::Status(503).to_string(), "HTTP status 503");
+    assert_eq!(CertificateError.to_string(), "TLS certificate rejected");
+}
@@ -0,0 +1,92 @@
+use std::io;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("upload failed at {at}")]
+pub struct UploadError {
+    #[from]
+    source: io::Error,
+    #[timestamp]
+    at: SystemTime,
+}
+
+#[derive(Error, Debug)]
+#[error(constructors, partial_eq)]
+pub enum JobError {
+    #[error("job {name} timed out, started {started:?}")]
+    TimedOut {
+        name: String,
+        #[timestamp]
+        started: Instant,
+    },
+    #[error("job {name} failed at {at}")]
+    Failed {
+        name: String,
+        #[timestamp]
+        at: Option<SystemTime>,
+    },
+}
+
+#[test]
+fn test_from() {
+    let before = SystemTime::now();
+    let error = UploadError::from(io::Error::new(io::ErrorKind::Other, "..."));
+    assert!(error.at >= before);
+    assert!(error.at <= SystemTime::now());
+}
+
+#[test]
+fn test_display() {
+    let error = UploadError {
+        source: io::Error::new(io::ErrorKind::Other, "..."),
+        at: UNIX_EPOCH + Duration::from_millis(1_714_566_896_789),
+    };
+    assert_eq!(
+        "upload failed at 2024-05-01T12:34:56.789Z",
+        error.to_string()
+    );
+
+    let error = UploadError {
+        source: io::Error::new(io::ErrorKind::Other, "..."),
+        at: UNIX_EPOCH + Duration::from_secs(951_782_400),
+    };
+    assert_eq!(
+        "upload failed at 2000-02-29T00:00:00.000Z",
+        error.to_string()
+    );
+
+    let error = UploadError {
+        source: io::Error::new(io::ErrorKind::Other, "..."),
+        at: UNIX_EPOCH + Duration::from_secs(253_402_300_800),
+    };
+    assert_eq!("upload failed at unknown", error.to_string());
+
+    let error = JobError::Failed {
+        name: "backup".to_owned(),
+        at: None,
+    };
+    assert_eq!("job backup failed at unknown", error.to_string());
+}
+
+#[test]
+fn test_constructors() {
+    let before = Instant::now();
+    match JobError::timed_out("backup".to_owned()) {
+        JobError::TimedOut { started, .. } => assert!(started >= before),
+        JobError::Failed { .. } => unreachable!(),
+    }
+    match JobError::failed("backup".to_owned()) {
+        JobError::Failed { at, .. } => assert!(at.is_some()),
+        JobError::TimedOut { .. } => unreachable!(),
+    }
+}
+
+#[test]
+fn test_partial_eq() {
+    let one = JobError::timed_out("backup".to_owned());
+    std::thread::sleep(Duration::from_millis(1));
+    let two = JobError::timed_out("backup".to_owned());
+    assert_eq!(one, two);
+    assert_ne!(one, JobError::timed_out("restore".to_owned()));
+}
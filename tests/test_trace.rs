@@ -0,0 +1,135 @@
+use std::fmt::Debug;
+use std::io;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tracing::field::{Field, Visit};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::Registry;
+
+// Records every event emitted while it is the default subscriber.
+#[derive(Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Event {
+    level: Level,
+    message: String,
+    source: Option<String>,
+}
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_event(&self, event: &tracing::Event, _ctx: Context<S>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(Event {
+            level: *event.metadata().level(),
+            message: fields.message,
+            source: fields.source,
+        });
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    message: String,
+    source: Option<String>,
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "source" => self.source = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+// Runs `f` with a Recorder as the default subscriber and returns the events
+// it emitted.
+fn record(f: impl FnOnce()) -> Vec<Event> {
+    let recorder = Recorder::default();
+    let events = Arc::clone(&recorder.events);
+    tracing::subscriber::with_default(Registry::default().with(recorder), f);
+    Arc::try_unwrap(events).unwrap().into_inner().unwrap()
+}
+
+#[derive(Error, Debug)]
+#[error(trace)]
+pub enum AppError {
+    #[error("failed to read config")]
+    Config(#[from] io::Error),
+    #[error("request timed out")]
+    #[level(warn)]
+    Timeout(#[from] std::fmt::Error),
+    #[error("{0}")]
+    #[error(message)]
+    Other(String),
+}
+
+#[derive(Error, Debug)]
+#[error(trace)]
+#[level(warn)]
+#[error("cache miss")]
+pub struct CacheMiss {
+    #[from]
+    source: io::Error,
+}
+
+#[derive(Error, Debug)]
+#[error("not traced")]
+pub struct Untraced(#[from] std::fmt::Error);
+
+#[test]
+fn test_from_emits_event() {
+    let events = record(|| {
+        let _ = AppError::from(io::Error::new(io::ErrorKind::Other, "disk on fire"));
+        let _ = AppError::from(std::fmt::Error);
+        let _ = CacheMiss::from(io::Error::new(io::ErrorKind::NotFound, "evicted"));
+        let _ = Untraced::from(std::fmt::Error);
+    });
+
+    let expected = vec![
+        Event {
+            level: Level::ERROR,
+            message: "failed to read config".to_owned(),
+            source: Some("disk on fire".to_owned()),
+        },
+        Event {
+            level: Level::WARN,
+            message: "request timed out".to_owned(),
+            source: Some("an error occurred when formatting an argument".to_owned()),
+        },
+        Event {
+            level: Level::WARN,
+            message: "cache miss".to_owned(),
+            source: Some("evicted".to_owned()),
+        },
+    ];
+    assert_eq!(expected, events);
+}
+
+#[test]
+fn test_constructor_emits_event() {
+    let events = record(|| {
+        let _ = AppError::msg("something odd");
+        let _ = AppError::from("something else");
+    });
+
+    let expected = vec![
+        Event {
+            level: Level::ERROR,
+            message: "something odd".to_owned(),
+            source: None,
+        },
+        Event {
+            level: Level::ERROR,
+            message: "something else".to_owned(),
+            source: None,
+        },
+    ];
+    assert_eq!(expected, events);
+}
@@ -0,0 +1,35 @@
+use std::convert::TryFrom;
+use std::{fmt, io};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MyError {
+    #[error("io error")]
+    #[try_into]
+    Io {
+        #[from]
+        source: io::Error,
+        attempts: usize,
+    },
+    #[error("format error")]
+    #[try_into]
+    Fmt(fmt::Error),
+    #[error("invalid {0}")]
+    Invalid(String),
+}
+
+#[test]
+fn test_try_into() {
+    let error = MyError::from(io::Error::new(io::ErrorKind::Other, "oh no!"));
+    let io = io::Error::try_from(error).unwrap();
+    assert_eq!(io.to_string(), "oh no!");
+
+    let error = MyError::Fmt(fmt::Error);
+    assert!(fmt::Error::try_from(error).is_ok());
+
+    let error = MyError::Invalid("input".to_owned());
+    match io::Error::try_from(error) {
+        Err(MyError::Invalid(input)) => assert_eq!(input, "input"),
+        _ => panic!("expected the original error back"),
+    }
+}
@@ -0,0 +1,61 @@
+use std::error::Error as _;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("config missing")]
+    ConfigMissing,
+    #[whatever]
+    Whatever {
+        message: String,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+}
+
+#[derive(Error, Debug)]
+pub enum Prototype {
+    #[error("failed: {message}")]
+    #[whatever]
+    Whatever {
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+        message: String,
+    },
+}
+
+#[test]
+fn test_message() {
+    let error = AppError::whatever("something broke");
+    assert_eq!(error.to_string(), "something broke");
+    assert!(error.source().is_none());
+    assert_eq!(AppError::ConfigMissing.to_string(), "config missing");
+}
+
+#[test]
+fn test_with_source() {
+    let io = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    let error = AppError::whatever_with(io, "could not load");
+    assert_eq!(error.to_string(), "could not load");
+    assert_eq!(error.source().unwrap().to_string(), "oh no!");
+}
+
+#[test]
+fn test_macro() {
+    let retries = 3;
+    let error = app_error_whatever!("gave up after {} retries", retries);
+    assert_eq!(error.to_string(), "gave up after 3 retries");
+
+    let error = app_error_whatever!("plain");
+    assert_eq!(error.to_string(), "plain");
+
+    let io = io::Error::new(io::ErrorKind::NotFound, "oh no!");
+    let error = app_error_whatever!(io, "reading {path}", path = "/etc/app");
+    assert_eq!(error.to_string(), "reading /etc/app");
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_custom_display() {
+    let error = prototype_whatever!("{}", 1);
+    assert_eq!(error.to_string(), "failed: 1");
+}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(accessors)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("wrapped {0}")]
+#[error(bound = "T Display")]
+pub struct Wrapper<T>(T);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    #[error(builder)]
+    Tuple(String),
+}
+
+fn main() {}
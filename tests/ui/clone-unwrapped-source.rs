@@ -0,0 +1,11 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(clone)]
+pub enum Error {
+    #[error("i/o failed")]
+    Io(#[from] io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(code)]
+pub enum Error {
+    #[error("not found")]
+    #[code("S0001")]
+    NotFound,
+    #[error("corrupt")]
+    Corrupt,
+}
+
+fn main() {}
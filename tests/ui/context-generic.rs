@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(context)]
+pub enum Error<T: std::fmt::Debug> {
+    #[error("{0:?}")]
+    Value(T),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(defmt)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(deny_unused)]
+pub enum Error {
+    #[error("request to {url} failed")]
+    Request { url: String, status: u16 },
+    #[error("retry {0} failed")]
+    Retry(u32, String),
+}
+
+fn main() {}
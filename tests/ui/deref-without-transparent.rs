@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("wrapped: {0}")]
+#[error(deref)]
+pub struct Wrapped(std::io::Error);
+
+fn main() {}
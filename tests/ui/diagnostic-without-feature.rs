@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(diagnostic)]
+#[error("...")]
+#[diagnostic(code(app::failed))]
+pub struct Error;
+
+fn main() {}
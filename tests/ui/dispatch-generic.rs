@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(dispatch)]
+pub enum Error<T> {
+    #[error("{0}")]
+    Value(T),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+fn hex(bytes: &Vec<u8>, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(formatter, "{:x?}", bytes)
+}
+
+#[derive(Error, Debug)]
+#[error("bad checksum {0}")]
+pub struct Error(#[display(wiht = hex)] Vec<u8>);
+
+fn main() {}
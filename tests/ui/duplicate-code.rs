@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ErrorEnum {
+    #[error("...")]
+    #[code("E0001")]
+    First,
+    #[error("...")]
+    #[code("E0001")]
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(status = 404)]
+#[status(410)]
+#[error("gone")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("os error {code}")]
+    #[errno(other)]
+    Other { code: i32, context: String },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use thiserror::{Error, ErrorId};
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("first")]
+    First {
+        #[error_id]
+        id: ErrorId,
+    },
+    #[error("second")]
+    Second {
+        #[error_id]
+        id: u64,
+    },
+}
+
+fn main() {}
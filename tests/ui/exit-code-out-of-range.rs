@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[exit_code(256)]
+pub struct Error;
+
+fn main() {}
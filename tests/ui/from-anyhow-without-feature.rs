@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(from_anyhow)]
+pub enum Error {
+    #[error(transparent)]
+    #[error(fallback)]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[from(arc)]
+    source: Box<std::io::Error>,
+}
+
+fn main() {}
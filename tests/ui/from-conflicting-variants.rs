@@ -0,0 +1,14 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("read failed")]
+    Read(#[from] io::Error),
+    #[error("parse failed")]
+    Parse(#[from] std::num::ParseIntError),
+    #[error("write failed")]
+    Write(#[from] io::Error),
+}
+
+fn main() {}
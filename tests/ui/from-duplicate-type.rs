@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[from(std::io::Error, std::fmt::Error, std::io::Error)]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+fn main() {}
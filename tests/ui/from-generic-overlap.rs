@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ErrorEnum<E> {
+    #[error("...")]
+    Generic(#[from] E),
+    #[error("...")]
+    Fmt(#[from] std::fmt::Error),
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ErrorEnum {
+    #[error("...")]
+    Read(#[from] io::Error),
+    #[error("...")]
+    #[from_match(io::Error)]
+    Write(#[source] io::Error),
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("access denied")]
+#[hresult(0x8007_0005)]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("timed out after {elapsed}")]
+pub struct Error {
+    #[display(humantime)]
+    elapsed: Duration,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[error(inline, cold)]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[into(std::io::Error)]
+pub enum ErrorEnum {
+    #[error("...")]
+    #[into(kind = std::io::ErrorKind::NotFound)]
+    Missing,
+    #[error("...")]
+    Other,
+}
+
+fn main() {}
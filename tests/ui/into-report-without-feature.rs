@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(into_report)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
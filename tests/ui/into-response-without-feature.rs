@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(into_response)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(into_responses)]
+#[error("slow down")]
+#[status(429)]
+pub struct Error;
+
+fn main() {}
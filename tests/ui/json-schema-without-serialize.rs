@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(json_schema)]
+#[error("timed out")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("invalid params")]
+    #[jsonrpc(code = -32602, data = params)]
+    InvalidParams { fields: Vec<String> },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ErrorEnum {
+    #[error(message)]
+    Other(u32),
+}
+
+fn main() {}
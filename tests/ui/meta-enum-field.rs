@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[meta(tenant = .tenant_id)]
+pub enum Error {
+    #[error("over quota")]
+    OverQuota { tenant_id: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("over quota")]
+    #[meta(tenant = .tenant)]
+    OverQuota { tenant_id: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(metrics)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,14 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("connection reset")]
+    Reset,
+    InvalidHeader(String),
+    Timeout { after_ms: u64, retries: u32 },
+    Io(#[from] io::Error),
+    Closed,
+}
+
+fn main() {}
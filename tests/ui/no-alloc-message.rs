@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(no_alloc)]
+pub enum Error {
+    #[error("device reset")]
+    Reset,
+    #[error(message)]
+    Message(String),
+}
+
+fn main() {}
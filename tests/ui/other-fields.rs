@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("other")]
+    #[error(other)]
+    Other(String, Box<dyn std::error::Error + Send + Sync>),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(owned)]
+pub enum Error<'a> {
+    #[error("...")]
+    Excerpt(Option<&'a str>),
+}
+
+fn main() {}
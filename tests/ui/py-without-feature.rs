@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+#[py(exception = pyo3::exceptions::PyValueError)]
+pub struct Error;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[repr_code]
+pub enum Error {
+    #[error("first")]
+    First,
+    #[error("second")]
+    #[repr_code(1)]
+    Second,
+}
+
+fn main() {}
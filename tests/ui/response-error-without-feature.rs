@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(response_error)]
+#[error("...")]
+pub struct Error;
+
+fn main() {}
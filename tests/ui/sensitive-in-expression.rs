@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid token {}", .token.to_uppercase())]
+pub struct Error {
+    #[sensitive]
+    token: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("connection lost")]
+#[sentry(fingerprint = "connection")]
+pub struct Error;
+
+fn main() {}
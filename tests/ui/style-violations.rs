@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(style(lowercase_start, no_trailing_period))]
+pub enum Error {
+    #[error("connection refused")]
+    Refused,
+    #[error("Timed out after {0}ms")]
+    Timeout(u64),
+    #[error("handshake failed.")]
+    Handshake,
+    #[error("Certificate expired.")]
+    Certificate,
+}
+
+fn main() {}
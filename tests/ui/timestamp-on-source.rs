@@ -0,0 +1,12 @@
+use std::time::SystemTime;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("...")]
+pub struct Error {
+    #[source]
+    #[timestamp]
+    at: SystemTime,
+}
+
+fn main() {}
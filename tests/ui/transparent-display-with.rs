@@ -0,0 +1,13 @@
+use std::fmt;
+use std::io;
+use thiserror::Error;
+
+fn io_kind(error: &io::Error, formatter: &mut fmt::Formatter) -> fmt::Result {
+    write!(formatter, "{:?}", error.kind())
+}
+
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct Error(#[display(with = io_kind)] io::Error);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ErrorEnum {
+    #[error("...")]
+    #[try_into]
+    Pair(String, String),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error(trace)]
+pub enum Error {
+    #[error("...")]
+    #[level(critical)]
+    Fatal,
+}
+
+fn main() {}